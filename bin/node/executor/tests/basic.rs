@@ -396,7 +396,10 @@ fn full_native_block_import_works() {
 			},
 			EventRecord {
 				phase: Phase::ApplyExtrinsic(1),
-				event: Event::Treasury(pallet_treasury::Event::Deposit(fees * 8 / 10)),
+				event: Event::Treasury(pallet_treasury::Event::Deposit(
+					fees * 8 / 10,
+					pallet_treasury::DepositSource::Fees,
+				)),
 				topics: vec![],
 			},
 			EventRecord {
@@ -450,7 +453,10 @@ fn full_native_block_import_works() {
 			},
 			EventRecord {
 				phase: Phase::ApplyExtrinsic(1),
-				event: Event::Treasury(pallet_treasury::Event::Deposit(fees * 8 / 10)),
+				event: Event::Treasury(pallet_treasury::Event::Deposit(
+					fees * 8 / 10,
+					pallet_treasury::DepositSource::Fees,
+				)),
 				topics: vec![],
 			},
 			EventRecord {
@@ -472,7 +478,10 @@ fn full_native_block_import_works() {
 			},
 			EventRecord {
 				phase: Phase::ApplyExtrinsic(2),
-				event: Event::Treasury(pallet_treasury::Event::Deposit(fees * 8 / 10)),
+				event: Event::Treasury(pallet_treasury::Event::Deposit(
+					fees * 8 / 10,
+					pallet_treasury::DepositSource::Fees,
+				)),
 				topics: vec![],
 			},
 			EventRecord {
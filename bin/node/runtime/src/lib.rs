@@ -26,8 +26,8 @@ use codec::{Decode, Encode};
 use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{
-		Currency, Everything, Imbalance, KeyOwnerProofSystem, LockIdentifier, OnUnbalanced,
-		U128CurrencyToVote,
+		Currency, Everything, Imbalance, KeyOwnerProofSystem, LockIdentifier, NeverEnsureOrigin,
+		OnUnbalanced, U128CurrencyToVote,
 	},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
@@ -48,6 +48,7 @@ use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
 use pallet_session::historical as pallet_session_historical;
 pub use pallet_transaction_payment::{CurrencyAdapter, Multiplier, TargetedFeeAdjustment};
 use pallet_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
+use pallet_treasury::{ProposalDetails, ProposalIndex, ProposalStatus};
 use sp_api::impl_runtime_apis;
 use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
 use sp_core::{
@@ -128,8 +129,8 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// and set impl_version to 0. If only runtime
 	// implementation changes and behavior does not, then leave spec_version as
 	// is and increment impl_version.
-	spec_version: 273,
-	impl_version: 1,
+	spec_version: 274,
+	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 2,
 };
@@ -159,7 +160,7 @@ impl OnUnbalanced<NegativeImbalance> for DealWithFees {
 				// for tips, if any, 80% to treasury, 20% to author (though this can be anything)
 				tips.ration_merge_into(80, 20, &mut split);
 			}
-			Treasury::on_unbalanced(split.0);
+			Treasury::deposit_from(pallet_treasury::DepositSource::Fees, split.0);
 			Author::on_unbalanced(split.1);
 		}
 	}
@@ -707,11 +708,43 @@ parameter_types! {
 	pub const BountyCuratorDeposit: Permill = Permill::from_percent(50);
 	pub const BountyValueMinimum: Balance = 5 * DOLLARS;
 	pub const MaxApprovals: u32 = 100;
+	pub SpendFundsWeightLimit: Weight = Perbill::from_percent(10) *
+		RuntimeBlockWeights::get().max_block;
+	pub const FlagSelfProposals: bool = true;
+	pub const EnsureAffordable: bool = false;
+	pub const AllowPartialPayouts: bool = false;
+	pub const TreasuryExpiredBondPolicy: pallet_treasury::ExpiredBondPolicy =
+		pallet_treasury::ExpiredBondPolicy::Return;
+	pub const TreasuryBurnMode: pallet_treasury::BurnMode = pallet_treasury::BurnMode::Static;
+	pub const MaxOccurrences: u32 = 100;
+	pub const MaxBurnDestinations: u32 = 8;
+	pub const MaxBeneficiaries: u32 = 16;
+	pub const MaxMetadataLen: u32 = 128;
+	pub const MetadataDepositBase: Balance = 1 * DOLLARS;
+	pub const MetadataDepositPerByte: Balance = 1 * CENTS;
+	pub const BurnFloor: Balance = 0;
+	pub const BurnThreshold: Balance = 0;
+	pub const RejectionSlashRatio: Permill = Permill::from_percent(100);
+	pub const ReporterReward: Permill = Permill::from_percent(10);
+	pub const MaxPendingPerAccount: u32 = 32;
+	pub const ProposalCooldown: BlockNumber = 1 * HOURS;
+	pub const MaxWaitingProposals: u32 = 100;
+	pub const MaxProposers: u32 = 100;
+	pub const TreasuryPayoutMode: pallet_treasury::PayoutMode = pallet_treasury::PayoutMode::Push;
+	pub const TreasuryPayoutOrdering: pallet_treasury::PayoutOrdering =
+		pallet_treasury::PayoutOrdering::Fifo;
+	pub const MaxReasonLength: u32 = 256;
+	pub const ProposalWithdrawalFee: Permill = Permill::from_percent(20);
+	pub TreasuryAccount: AccountId = Treasury::account_id();
 }
 
 impl pallet_treasury::Config for Runtime {
 	type PalletId = TreasuryPalletId;
 	type Currency = Balances;
+	type Beneficiary = AccountId;
+	type BeneficiaryLookup = Indices;
+	type NativePaymaster = pallet_treasury::CurrencyPaymaster;
+	type VestingHandler = pallet_treasury::VestingScheduleHandler<Vesting>;
 	type ApproveOrigin = EnsureOneOf<
 		AccountId,
 		EnsureRoot<AccountId>,
@@ -726,13 +759,51 @@ impl pallet_treasury::Config for Runtime {
 	type OnSlash = ();
 	type ProposalBond = ProposalBond;
 	type ProposalBondMinimum = ProposalBondMinimum;
+	type ProposalBondMaximum = ();
+	type MaxProposalValue = ();
+	type MaxPerBeneficiaryPerPeriod = ();
+	type MaxSpendPerPeriod = ();
+	type ApprovalTtlPeriods = ();
+	type SpendPeriodOffset = ();
 	type AllowedProposalPeriod = AllowedProposalPeriod;
 	type SpendPeriod = SpendPeriod;
 	type Burn = Burn;
 	type BurnDestination = ();
 	type SpendFunds = Bounties;
+	type SpendFundsWeightLimit = SpendFundsWeightLimit;
 	type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
 	type MaxApprovals = MaxApprovals;
+	type ProposalWithdrawalFee = ProposalWithdrawalFee;
+	type SpendOrigin = NeverEnsureOrigin<Balance>;
+	type Paymaster = pallet_treasury::PayFromAccount<Assets, TreasuryAccount, AccountId>;
+	type SpendAssetOrigin = NeverEnsureOrigin<Balance>;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type MaxReasonLength = MaxReasonLength;
+	type PayoutMode = TreasuryPayoutMode;
+	type PayoutOrdering = TreasuryPayoutOrdering;
+	type ProposerManagementOrigin = EnsureRoot<AccountId>;
+	type MaxProposers = MaxProposers;
+	type MaxWaitingProposals = MaxWaitingProposals;
+	type MaxPendingPerAccount = MaxPendingPerAccount;
+	type ProposalCooldown = ProposalCooldown;
+	type RejectionSlashRatio = RejectionSlashRatio;
+	type ReporterReward = ReporterReward;
+	type BurnFloor = BurnFloor;
+	type BurnThreshold = BurnThreshold;
+	type MaxBurnDestinations = MaxBurnDestinations;
+	type MaxBeneficiaries = MaxBeneficiaries;
+	type MaxMetadataLen = MaxMetadataLen;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type Preimages = ();
+	type OnProposalLifecycle = ();
+	type BondExemptOrigin = NeverEnsureOrigin<AccountId>;
+	type MaxOccurrences = MaxOccurrences;
+	type ExpiredBondPolicy = TreasuryExpiredBondPolicy;
+	type BurnMode = TreasuryBurnMode;
+	type FlagSelfProposals = FlagSelfProposals;
+	type EnsureAffordable = EnsureAffordable;
+	type AllowPartialPayouts = AllowPartialPayouts;
 }
 
 impl pallet_bounties::Config for Runtime {
@@ -1032,7 +1103,7 @@ construct_runtime!(
 		Elections: pallet_elections_phragmen::{Pallet, Call, Storage, Event<T>, Config<T>},
 		TechnicalMembership: pallet_membership::<Instance1>::{Pallet, Call, Storage, Event<T>, Config<T>},
 		Grandpa: pallet_grandpa::{Pallet, Call, Storage, Config, Event, ValidateUnsigned},
-		Treasury: pallet_treasury::{Pallet, Call, Storage, Config, Event<T>},
+		Treasury: pallet_treasury::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>},
 		ImOnline: pallet_im_online::{Pallet, Call, Storage, Event<T>, ValidateUnsigned, Config<T>},
 		AuthorityDiscovery: pallet_authority_discovery::{Pallet, Config},
@@ -1092,7 +1163,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPallets,
-	(),
+	pallet_treasury::migrations::v1::Migration<Runtime, (), traits::Identity>,
 >;
 
 /// MMR helper types.
@@ -1273,6 +1344,44 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_treasury_rpc_runtime_api::TreasuryApi<
+		Block,
+		AccountId,
+		Balance,
+		BlockNumber,
+		AccountId,
+	> for Runtime {
+		fn pot() -> Balance {
+			Treasury::pot()
+		}
+		fn proposal(
+			index: ProposalIndex,
+		) -> Option<ProposalDetails<AccountId, Balance, BlockNumber, AccountId>> {
+			Treasury::proposal_details(index)
+		}
+		fn proposals() -> Vec<(
+			ProposalIndex,
+			ProposalDetails<AccountId, Balance, BlockNumber, AccountId>,
+		)> {
+			Treasury::all_proposals()
+		}
+		fn waiting_proposals() -> Vec<(
+			ProposalIndex,
+			ProposalDetails<AccountId, Balance, BlockNumber, AccountId>,
+		)> {
+			Treasury::all_waiting_proposals()
+		}
+		fn approvals() -> Vec<ProposalIndex> {
+			Treasury::approval_queue()
+		}
+		fn proposal_status(index: ProposalIndex) -> Option<ProposalStatus> {
+			Treasury::status(index)
+		}
+		fn waiting_proposal_status(index: ProposalIndex) -> Option<ProposalStatus> {
+			Treasury::waiting_status(index)
+		}
+	}
+
 	impl pallet_mmr::primitives::MmrApi<
 			Block,
 		mmr::Hash,
@@ -504,6 +504,12 @@ pub mod pallet {
 	#[pallet::getter(fn total_issuance)]
 	pub type TotalIssuance<T: Config<I>, I: 'static = ()> = StorageValue<_, T::Balance, ValueQuery>;
 
+	/// The part of the total issuance that is not circulating, such as funds held by a treasury.
+	#[pallet::storage]
+	#[pallet::getter(fn inactive_issuance)]
+	pub type InactiveIssuance<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, T::Balance, ValueQuery>;
+
 	/// The balance of an account.
 	///
 	/// NOTE: This is only used in the case that this pallet is used to store balances.
@@ -1402,6 +1408,18 @@ where
 		<TotalIssuance<T, I>>::get()
 	}
 
+	fn active_issuance() -> Self::Balance {
+		<TotalIssuance<T, I>>::get().saturating_sub(<InactiveIssuance<T, I>>::get())
+	}
+
+	fn deactivate(amount: Self::Balance) {
+		<InactiveIssuance<T, I>>::mutate(|inactive| *inactive = inactive.saturating_add(amount));
+	}
+
+	fn reactivate(amount: Self::Balance) {
+		<InactiveIssuance<T, I>>::mutate(|inactive| *inactive = inactive.saturating_sub(amount));
+	}
+
 	fn minimum_balance() -> Self::Balance {
 		T::ExistentialDeposit::get()
 	}
@@ -49,7 +49,7 @@ frame_support::construct_runtime!(
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Bounties: pallet_bounties::{Pallet, Call, Storage, Event<T>},
-		Treasury: pallet_treasury::{Pallet, Call, Storage, Config, Event<T>},
+		Treasury: pallet_treasury::{Pallet, Call, Storage, Config<T>, Event<T>},
 	}
 );
 
@@ -110,12 +110,42 @@ parameter_types! {
 	pub const DataDepositPerByte: u64 = 1;
 	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
 	pub const MaxApprovals: u32 = 100;
+	pub const SpendFundsWeightLimit: Weight = Weight::MAX;
+	pub const FlagSelfProposals: bool = false;
+	pub const EnsureAffordable: bool = false;
+	pub const AllowPartialPayouts: bool = false;
+	pub const TreasuryExpiredBondPolicy: pallet_treasury::ExpiredBondPolicy =
+		pallet_treasury::ExpiredBondPolicy::Return;
+	pub const TreasuryBurnMode: pallet_treasury::BurnMode = pallet_treasury::BurnMode::Static;
+	pub const MaxOccurrences: u32 = 12;
+	pub const MaxBurnDestinations: u32 = 4;
+	pub const MaxBeneficiaries: u32 = 3;
+	pub const MaxMetadataLen: u32 = 32;
+	pub const MetadataDepositBase: u64 = 1;
+	pub const MetadataDepositPerByte: u64 = 1;
+	pub const BurnFloor: u64 = 0;
+	pub const BurnThreshold: u64 = 0;
+	pub const RejectionSlashRatio: Permill = Permill::from_percent(100);
+	pub const ReporterReward: Permill = Permill::zero();
+	pub const MaxPendingPerAccount: u32 = 100;
+	pub const ProposalCooldown: u64 = 0;
+	pub const MaxWaitingProposals: u32 = 5;
+	pub const MaxProposers: u32 = 2;
+	pub const TreasuryPayoutMode: pallet_treasury::PayoutMode = pallet_treasury::PayoutMode::Push;
+	pub const TreasuryPayoutOrdering: pallet_treasury::PayoutOrdering =
+		pallet_treasury::PayoutOrdering::Fifo;
+	pub const MaxReasonLength: u32 = 16;
+	pub const ProposalWithdrawalFee: Permill = Permill::from_percent(20);
 	pub const AllowedProposalPeriod: u64 = 2;
 }
 // impl pallet_treasury::Config for Test {
 impl pallet_treasury::Config for Test {
 	type PalletId = TreasuryPalletId;
 	type Currency = pallet_balances::Pallet<Test>;
+	type Beneficiary = u128;
+	type BeneficiaryLookup = IdentityLookup<u128>;
+	type NativePaymaster = pallet_treasury::CurrencyPaymaster;
+	type VestingHandler = ();
 	type ApproveOrigin = frame_system::EnsureRoot<u128>;
 	type RejectOrigin = frame_system::EnsureRoot<u128>;
 	type AllowedProposalPeriod = AllowedProposalPeriod;
@@ -123,12 +153,50 @@ impl pallet_treasury::Config for Test {
 	type OnSlash = ();
 	type ProposalBond = ProposalBond;
 	type ProposalBondMinimum = ProposalBondMinimum;
+	type ProposalBondMaximum = ();
+	type MaxProposalValue = ();
+	type MaxPerBeneficiaryPerPeriod = ();
+	type MaxSpendPerPeriod = ();
+	type ApprovalTtlPeriods = ();
+	type SpendPeriodOffset = ();
 	type SpendPeriod = SpendPeriod;
 	type Burn = Burn;
 	type BurnDestination = (); // Just gets burned.
 	type WeightInfo = ();
 	type SpendFunds = Bounties;
+	type SpendFundsWeightLimit = SpendFundsWeightLimit;
 	type MaxApprovals = MaxApprovals;
+	type ProposalWithdrawalFee = ProposalWithdrawalFee;
+	type SpendOrigin = frame_support::traits::NeverEnsureOrigin<u64>;
+	type Paymaster = ();
+	type SpendAssetOrigin = frame_support::traits::NeverEnsureOrigin<u128>;
+	type AdminOrigin = frame_system::EnsureRoot<u128>;
+	type MaxReasonLength = MaxReasonLength;
+	type PayoutMode = TreasuryPayoutMode;
+	type PayoutOrdering = TreasuryPayoutOrdering;
+	type ProposerManagementOrigin = frame_system::EnsureRoot<u128>;
+	type MaxProposers = MaxProposers;
+	type MaxWaitingProposals = MaxWaitingProposals;
+	type MaxPendingPerAccount = MaxPendingPerAccount;
+	type ProposalCooldown = ProposalCooldown;
+	type RejectionSlashRatio = RejectionSlashRatio;
+	type ReporterReward = ReporterReward;
+	type BurnFloor = BurnFloor;
+	type BurnThreshold = BurnThreshold;
+	type MaxBurnDestinations = MaxBurnDestinations;
+	type MaxBeneficiaries = MaxBeneficiaries;
+	type MaxMetadataLen = MaxMetadataLen;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type Preimages = ();
+	type OnProposalLifecycle = ();
+	type BondExemptOrigin = frame_support::traits::NeverEnsureOrigin<u128>;
+	type MaxOccurrences = MaxOccurrences;
+	type ExpiredBondPolicy = TreasuryExpiredBondPolicy;
+	type BurnMode = TreasuryBurnMode;
+	type FlagSelfProposals = FlagSelfProposals;
+	type EnsureAffordable = EnsureAffordable;
+	type AllowPartialPayouts = AllowPartialPayouts;
 }
 parameter_types! {
 	pub const BountyDepositBase: u64 = 80;
@@ -160,7 +228,8 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
-	GenesisBuild::<Test>::assimilate_storage(&pallet_treasury::GenesisConfig, &mut t).unwrap();
+	GenesisBuild::<Test>::assimilate_storage(&pallet_treasury::GenesisConfig::default(), &mut t)
+		.unwrap();
 	t.into()
 }
 
@@ -193,7 +262,7 @@ fn minting_works() {
 #[test]
 fn spend_proposal_takes_min_deposit() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
 		assert_eq!(Balances::free_balance(0), 99);
 		assert_eq!(Balances::reserved_balance(0), 1);
 	});
@@ -202,7 +271,7 @@ fn spend_proposal_takes_min_deposit() {
 #[test]
 fn spend_proposal_takes_proportional_deposit() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
 		assert_eq!(Balances::free_balance(0), 95);
 		assert_eq!(Balances::reserved_balance(0), 5);
 	});
@@ -212,7 +281,7 @@ fn spend_proposal_takes_proportional_deposit() {
 fn spend_proposal_fails_when_proposer_poor() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			Treasury::propose_spend(Origin::signed(2), 100, 3, 1),
+			Treasury::propose_spend(Origin::signed(2), 100, 3, 1, 0, None, None),
 			TreasuryError::InsufficientProposersBalance,
 		);
 	});
@@ -223,7 +292,7 @@ fn accepted_spend_proposal_ignored_outside_spend_period() {
 	new_test_ext().execute_with(|| {
 		Balances::make_free_balance_be(&Treasury::account_id(), 101);
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
 		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
 
 		<Treasury as OnInitialize<u64>>::on_initialize(1);
@@ -250,7 +319,7 @@ fn rejected_spend_proposal_ignored_on_spend_period() {
 	new_test_ext().execute_with(|| {
 		Balances::make_free_balance_be(&Treasury::account_id(), 101);
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
 		assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
 
 		<Treasury as OnInitialize<u64>>::on_initialize(2);
@@ -264,9 +333,9 @@ fn reject_already_rejected_spend_proposal_fails() {
 	new_test_ext().execute_with(|| {
 		Balances::make_free_balance_be(&Treasury::account_id(), 101);
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
 		assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
-		assert_noop!(Treasury::reject_proposal(Origin::root(), 0), TreasuryError::InvalidIndex);
+		assert_noop!(Treasury::reject_proposal(Origin::root(), 0), TreasuryError::ProposalNotFound);
 	});
 }
 
@@ -275,7 +344,7 @@ fn reject_non_existent_spend_proposal_fails() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
 			Treasury::reject_proposal(Origin::root(), 0),
-			pallet_treasury::Error::<Test, _>::InvalidIndex
+			pallet_treasury::Error::<Test, _>::ProposalNotFound
 		);
 	});
 }
@@ -283,7 +352,10 @@ fn reject_non_existent_spend_proposal_fails() {
 #[test]
 fn accept_non_existent_spend_proposal_fails() {
 	new_test_ext().execute_with(|| {
-		assert_noop!(Treasury::approve_proposal(Origin::root(), 0), TreasuryError::InvalidIndex);
+		assert_noop!(
+			Treasury::approve_proposal(Origin::root(), 0),
+			TreasuryError::ProposalNotFound
+		);
 	});
 }
 
@@ -292,9 +364,12 @@ fn accept_already_rejected_spend_proposal_fails() {
 	new_test_ext().execute_with(|| {
 		Balances::make_free_balance_be(&Treasury::account_id(), 101);
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
 		assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
-		assert_noop!(Treasury::approve_proposal(Origin::root(), 0), TreasuryError::InvalidIndex);
+		assert_noop!(
+			Treasury::approve_proposal(Origin::root(), 0),
+			TreasuryError::ProposalNotFound
+		);
 	});
 }
 
@@ -304,7 +379,7 @@ fn accepted_spend_proposal_enacted_on_spend_period() {
 		Balances::make_free_balance_be(&Treasury::account_id(), 101);
 		assert_eq!(Treasury::pot(), 100);
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
 		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
 
 		<Treasury as OnInitialize<u64>>::on_initialize(2);
@@ -319,7 +394,7 @@ fn pot_underflow_should_not_diminish() {
 		Balances::make_free_balance_be(&Treasury::account_id(), 101);
 		assert_eq!(Treasury::pot(), 100);
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 150, 3, 1));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 150, 3, 1, 0, None, None));
 		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
 
 		<Treasury as OnInitialize<u64>>::on_initialize(2);
@@ -341,13 +416,29 @@ fn treasury_account_doesnt_get_deleted() {
 		assert_eq!(Treasury::pot(), 100);
 		let treasury_balance = Balances::free_balance(&Treasury::account_id());
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), treasury_balance, 3, 1));
+		assert_ok!(Treasury::propose_spend(
+			Origin::signed(0),
+			treasury_balance,
+			3,
+			1,
+			0,
+			None,
+			None
+		));
 		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
 
 		<Treasury as OnInitialize<u64>>::on_initialize(2);
 		assert_eq!(Treasury::pot(), 100); // Pot hasn't changed
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), Treasury::pot(), 3, 1));
+		assert_ok!(Treasury::propose_spend(
+			Origin::signed(0),
+			Treasury::pot(),
+			3,
+			1,
+			0,
+			None,
+			None
+		));
 		assert_ok!(Treasury::approve_proposal(Origin::root(), 1));
 
 		<Treasury as OnInitialize<u64>>::on_initialize(4);
@@ -371,9 +462,9 @@ fn inexistent_account_works() {
 		assert_eq!(Balances::free_balance(Treasury::account_id()), 0); // Account does not exist
 		assert_eq!(Treasury::pot(), 0); // Pot is empty
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 99, 3, 1));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 99, 3, 1, 0, None, None));
 		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 1, 3, 1));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 1, 3, 1, 0, None, None));
 		assert_ok!(Treasury::approve_proposal(Origin::root(), 1));
 		<Treasury as OnInitialize<u64>>::on_initialize(2);
 		assert_eq!(Treasury::pot(), 0); // Pot hasn't changed
@@ -454,7 +545,7 @@ fn close_bounty_works() {
 	new_test_ext().execute_with(|| {
 		System::set_block_number(1);
 		Balances::make_free_balance_be(&Treasury::account_id(), 101);
-		assert_noop!(Bounties::close_bounty(Origin::root(), 0), Error::<Test>::InvalidIndex);
+		assert_noop!(Bounties::close_bounty(Origin::root(), 0), Error::<Test>::ProposalNotFound);
 
 		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 10, b"12345".to_vec()));
 
@@ -479,7 +570,7 @@ fn approve_bounty_works() {
 	new_test_ext().execute_with(|| {
 		System::set_block_number(1);
 		Balances::make_free_balance_be(&Treasury::account_id(), 101);
-		assert_noop!(Bounties::approve_bounty(Origin::root(), 0), Error::<Test>::InvalidIndex);
+		assert_noop!(Bounties::approve_bounty(Origin::root(), 0), Error::<Test>::ProposalNotFound);
 
 		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
 
@@ -537,7 +628,7 @@ fn assign_curator_works() {
 
 		assert_noop!(
 			Bounties::propose_curator(Origin::root(), 0, 4, 4),
-			Error::<Test>::InvalidIndex
+			Error::<Test>::ProposalNotFound
 		);
 
 		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
@@ -990,7 +1081,8 @@ fn genesis_funding_works() {
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
-	GenesisBuild::<Test>::assimilate_storage(&pallet_treasury::GenesisConfig, &mut t).unwrap();
+	GenesisBuild::<Test>::assimilate_storage(&pallet_treasury::GenesisConfig::default(), &mut t)
+		.unwrap();
 	let mut t: sp_io::TestExternalities = t.into();
 
 	t.execute_with(|| {
@@ -56,6 +56,19 @@ pub trait Currency<AccountId> {
 	/// The total amount of issuance in the system.
 	fn total_issuance() -> Self::Balance;
 
+	/// The total amount of issuance in the system, less the amount that has been deactivated
+	/// because it is held by the system and not circulating.
+	fn active_issuance() -> Self::Balance {
+		Self::total_issuance()
+	}
+
+	/// Take `amount` of the issuance out of the active issuance.
+	fn deactivate(_amount: Self::Balance) {}
+
+	/// Return `amount` of the deactivated issuance to the active issuance, up to the amount
+	/// deactivated.
+	fn reactivate(_amount: Self::Balance) {}
+
 	/// The minimum balance any single account may have. This is equivalent to the `Balances`
 	/// module's `ExistentialDeposit`.
 	fn minimum_balance() -> Self::Balance;
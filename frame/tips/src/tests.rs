@@ -48,7 +48,7 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
-		Treasury: pallet_treasury::{Pallet, Call, Storage, Config, Event<T>},
+		Treasury: pallet_treasury::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Tips: pallet_tips::{Pallet, Call, Storage, Event<T>},
 	}
 );
@@ -132,22 +132,92 @@ parameter_types! {
 	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
 	pub const MaximumReasonLength: u32 = 16384;
 	pub const MaxApprovals: u32 = 100;
+	pub const SpendFundsWeightLimit: Weight = Weight::MAX;
+	pub const FlagSelfProposals: bool = false;
+	pub const EnsureAffordable: bool = false;
+	pub const AllowPartialPayouts: bool = false;
+	pub const TreasuryExpiredBondPolicy: pallet_treasury::ExpiredBondPolicy =
+		pallet_treasury::ExpiredBondPolicy::Return;
+	pub const TreasuryBurnMode: pallet_treasury::BurnMode = pallet_treasury::BurnMode::Static;
+	pub const MaxOccurrences: u32 = 12;
+	pub const MaxBurnDestinations: u32 = 4;
+	pub const MaxBeneficiaries: u32 = 3;
+	pub const MaxMetadataLen: u32 = 32;
+	pub const MetadataDepositBase: u64 = 1;
+	pub const MetadataDepositPerByte: u64 = 1;
+	pub const BurnFloor: u64 = 0;
+	pub const BurnThreshold: u64 = 0;
+	pub const RejectionSlashRatio: Permill = Permill::from_percent(100);
+	pub const ReporterReward: Permill = Permill::zero();
+	pub const MaxPendingPerAccount: u32 = 100;
+	pub const ProposalCooldown: u64 = 0;
+	pub const MaxWaitingProposals: u32 = 5;
+	pub const MaxProposers: u32 = 2;
+	pub const TreasuryPayoutMode: pallet_treasury::PayoutMode = pallet_treasury::PayoutMode::Push;
+	pub const TreasuryPayoutOrdering: pallet_treasury::PayoutOrdering =
+		pallet_treasury::PayoutOrdering::Fifo;
+	pub const MaxReasonLength: u32 = 16;
+	pub const ProposalWithdrawalFee: Permill = Permill::from_percent(20);
+	pub const AllowedProposalPeriod: u64 = 2;
 }
 impl pallet_treasury::Config for Test {
 	type PalletId = TreasuryPalletId;
 	type Currency = pallet_balances::Pallet<Test>;
+	type Beneficiary = u128;
+	type BeneficiaryLookup = IdentityLookup<u128>;
+	type NativePaymaster = pallet_treasury::CurrencyPaymaster;
+	type VestingHandler = ();
 	type ApproveOrigin = frame_system::EnsureRoot<u128>;
 	type RejectOrigin = frame_system::EnsureRoot<u128>;
+	type AllowedProposalPeriod = AllowedProposalPeriod;
 	type Event = Event;
 	type OnSlash = ();
 	type ProposalBond = ProposalBond;
 	type ProposalBondMinimum = ProposalBondMinimum;
+	type ProposalBondMaximum = ();
+	type MaxProposalValue = ();
+	type MaxPerBeneficiaryPerPeriod = ();
+	type MaxSpendPerPeriod = ();
+	type ApprovalTtlPeriods = ();
+	type SpendPeriodOffset = ();
 	type SpendPeriod = SpendPeriod;
 	type Burn = Burn;
 	type BurnDestination = (); // Just gets burned.
 	type WeightInfo = ();
 	type SpendFunds = ();
+	type SpendFundsWeightLimit = SpendFundsWeightLimit;
 	type MaxApprovals = MaxApprovals;
+	type ProposalWithdrawalFee = ProposalWithdrawalFee;
+	type SpendOrigin = frame_support::traits::NeverEnsureOrigin<u64>;
+	type Paymaster = ();
+	type SpendAssetOrigin = frame_support::traits::NeverEnsureOrigin<u128>;
+	type AdminOrigin = frame_system::EnsureRoot<u128>;
+	type MaxReasonLength = MaxReasonLength;
+	type PayoutMode = TreasuryPayoutMode;
+	type PayoutOrdering = TreasuryPayoutOrdering;
+	type ProposerManagementOrigin = frame_system::EnsureRoot<u128>;
+	type MaxProposers = MaxProposers;
+	type MaxWaitingProposals = MaxWaitingProposals;
+	type MaxPendingPerAccount = MaxPendingPerAccount;
+	type ProposalCooldown = ProposalCooldown;
+	type RejectionSlashRatio = RejectionSlashRatio;
+	type ReporterReward = ReporterReward;
+	type BurnFloor = BurnFloor;
+	type BurnThreshold = BurnThreshold;
+	type MaxBurnDestinations = MaxBurnDestinations;
+	type MaxBeneficiaries = MaxBeneficiaries;
+	type MaxMetadataLen = MaxMetadataLen;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type Preimages = ();
+	type OnProposalLifecycle = ();
+	type BondExemptOrigin = frame_support::traits::NeverEnsureOrigin<u128>;
+	type MaxOccurrences = MaxOccurrences;
+	type ExpiredBondPolicy = TreasuryExpiredBondPolicy;
+	type BurnMode = TreasuryBurnMode;
+	type FlagSelfProposals = FlagSelfProposals;
+	type EnsureAffordable = EnsureAffordable;
+	type AllowPartialPayouts = AllowPartialPayouts;
 }
 parameter_types! {
 	pub const TipCountdown: u64 = 1;
@@ -173,7 +243,8 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
-	GenesisBuild::<Test>::assimilate_storage(&pallet_treasury::GenesisConfig, &mut t).unwrap();
+	GenesisBuild::<Test>::assimilate_storage(&pallet_treasury::GenesisConfig::default(), &mut t)
+		.unwrap();
 	t.into()
 }
 
@@ -537,7 +608,8 @@ fn genesis_funding_works() {
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
-	GenesisBuild::<Test>::assimilate_storage(&pallet_treasury::GenesisConfig, &mut t).unwrap();
+	GenesisBuild::<Test>::assimilate_storage(&pallet_treasury::GenesisConfig::default(), &mut t)
+		.unwrap();
 	let mut t: sp_io::TestExternalities = t.into();
 
 	t.execute_with(|| {
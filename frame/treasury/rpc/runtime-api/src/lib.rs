@@ -0,0 +1,58 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the treasury pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+pub use pallet_treasury::{ProposalDetails, ProposalIndex, ProposalStatus};
+
+sp_api::decl_runtime_apis! {
+	/// The API to query the state of a treasury.
+	pub trait TreasuryApi<AccountId, Balance, BlockNumber, Beneficiary> where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+		Beneficiary: Codec,
+	{
+		/// The amount of money in the pot.
+		fn pot() -> Balance;
+		/// The proposal at `index`, if any.
+		fn proposal(
+			index: ProposalIndex,
+		) -> Option<ProposalDetails<AccountId, Balance, BlockNumber, Beneficiary>>;
+		/// All proposals, by index.
+		fn proposals() -> Vec<(
+			ProposalIndex,
+			ProposalDetails<AccountId, Balance, BlockNumber, Beneficiary>,
+		)>;
+		/// All waiting proposals, in the order they will be promoted.
+		fn waiting_proposals() -> Vec<(
+			ProposalIndex,
+			ProposalDetails<AccountId, Balance, BlockNumber, Beneficiary>,
+		)>;
+		/// The indices of the approved proposals, in the order they are paid.
+		fn approvals() -> Vec<ProposalIndex>;
+		/// The status of the proposal at `index`, if any.
+		fn proposal_status(index: ProposalIndex) -> Option<ProposalStatus>;
+		/// The status of the waiting proposal at `index`, if any.
+		fn waiting_proposal_status(index: ProposalIndex) -> Option<ProposalStatus>;
+	}
+}
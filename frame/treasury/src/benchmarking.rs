@@ -21,21 +21,25 @@
 
 use super::{Pallet as Treasury, *};
 
-use frame_benchmarking::{account, benchmarks_instance_pallet, impl_benchmark_test_suite};
-use frame_support::{ensure, traits::OnInitialize};
+use frame_benchmarking::{
+	account, benchmarks_instance_pallet, impl_benchmark_test_suite, whitelisted_caller,
+};
+use frame_support::{ensure, traits::OnInitialize, BoundedVec};
 use frame_system::RawOrigin;
+use sp_runtime::traits::TrailingZeroInput;
+use sp_std::convert::TryInto;
 
 const SEED: u32 = 0;
 
 // Create the pre-requisite information needed to create a treasury `propose_spend`.
 fn setup_proposal<T: Config<I>, I: 'static>(
 	u: u32,
-) -> (T::AccountId, BalanceOf<T, I>, <T::Lookup as StaticLookup>::Source, u32) {
+) -> (T::AccountId, BalanceOf<T, I>, BeneficiaryLookupOf<T, I>, u32) {
 	let caller = account("caller", u, SEED);
 	let value: BalanceOf<T, I> = T::ProposalBondMinimum::get().saturating_mul(100u32.into());
 	let _ = T::Currency::make_free_balance_be(&caller, value);
-	let beneficiary = account("beneficiary", u, SEED);
-	let beneficiary_lookup = T::Lookup::unlookup(beneficiary);
+	let beneficiary = T::NativePaymaster::beneficiary_of(account("beneficiary", u, SEED));
+	let beneficiary_lookup = T::BeneficiaryLookup::unlookup(beneficiary);
 	let chunks = 1;
 	(caller, value, beneficiary_lookup, chunks)
 }
@@ -44,7 +48,15 @@ fn setup_proposal<T: Config<I>, I: 'static>(
 fn create_approved_proposals<T: Config<I>, I: 'static>(n: u32) -> Result<(), &'static str> {
 	for i in 0..n {
 		let (caller, value, lookup, chunks) = setup_proposal::<T, I>(i);
-		Treasury::<T, I>::propose_spend(RawOrigin::Signed(caller).into(), value, lookup, 1)?;
+		Treasury::<T, I>::propose_spend(
+			RawOrigin::Signed(caller).into(),
+			value,
+			lookup,
+			chunks,
+			Zero::zero(),
+			None,
+			None,
+		)?;
 		let proposal_id = <ProposalCount<T, I>>::get() - 1;
 		Treasury::<T, I>::approve_proposal(RawOrigin::Root.into(), proposal_id)?;
 	}
@@ -52,6 +64,69 @@ fn create_approved_proposals<T: Config<I>, I: 'static>(n: u32) -> Result<(), &'s
 	Ok(())
 }
 
+// Create proposals that wait to be promoted in `on_initialize`.
+fn create_waiting_proposals<T: Config<I>, I: 'static>(n: u32) -> Result<(), &'static str> {
+	for i in 0..n {
+		let (caller, value, _, chunks) = setup_proposal::<T, I>(i);
+		let beneficiary = T::NativePaymaster::beneficiary_of(account("beneficiary", i, SEED));
+		let bond = Treasury::<T, I>::calculate_bond(value);
+		T::Currency::reserve(&caller, bond).map_err(|_| "Failed to reserve bond")?;
+		<WaitingProposals<T, I>>::insert(
+			i,
+			Proposal {
+				proposer: caller,
+				total_value: value,
+				beneficiary,
+				bond,
+				remaining_bond: bond,
+				occurs: chunks,
+				remaining_occurs: chunks,
+				interval: Zero::zero(),
+				last_paid_at: None,
+				paused: false,
+				schedule: None,
+				expires_at: None,
+				milestone_gated: false,
+				milestone_confirmed: false,
+				partially_paid: Zero::zero(),
+				vesting_period: None,
+				splits: Default::default(),
+				proposed_at: Zero::zero(),
+				approved_at: None,
+			},
+		);
+	}
+	<WaitingQueueTail<T, I>>::put(n);
+	Ok(())
+}
+
+// Create the arguments of an asset spend that `T::Paymaster` will be able to pay out.
+fn setup_asset_spend<T: Config<I>, I: 'static>(
+) -> Result<(AssetKindOf<T, I>, PayBalanceOf<T, I>, PayBeneficiaryOf<T, I>), &'static str> {
+	let asset_kind = AssetKindOf::<T, I>::decode(&mut TrailingZeroInput::new(&[][..]))
+		.map_err(|_| "Failed to create asset kind")?;
+	let beneficiary = PayBeneficiaryOf::<T, I>::decode(&mut TrailingZeroInput::new(&[][..]))
+		.map_err(|_| "Failed to create beneficiary")?;
+	let amount = 100u32.into();
+	T::Paymaster::ensure_successful(&beneficiary, asset_kind.clone(), amount);
+	Ok((asset_kind, amount, beneficiary))
+}
+
+// Approve an asset spend at index 0 and pay it out.
+fn create_paid_asset_spend<T: Config<I>, I: 'static>(
+	caller: &T::AccountId,
+) -> Result<(), &'static str> {
+	let (asset_kind, amount, beneficiary) = setup_asset_spend::<T, I>()?;
+	Treasury::<T, I>::spend_asset(
+		T::SpendAssetOrigin::successful_origin(),
+		asset_kind,
+		amount,
+		beneficiary,
+	)?;
+	Treasury::<T, I>::payout_asset(RawOrigin::Signed(caller.clone()).into(), 0)?;
+	Ok(())
+}
+
 fn setup_pot_account<T: Config<I>, I: 'static>() {
 	let pot_account = Treasury::<T, I>::account_id();
 	let value = T::Currency::minimum_balance().saturating_mul(1_000_000_000u32.into());
@@ -64,7 +139,100 @@ benchmarks_instance_pallet! {
 		// Whitelist caller account from further DB operations.
 		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
 		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
-	}: _(RawOrigin::Signed(caller), value, beneficiary_lookup, 1)
+		let description = Some(T::Hash::default());
+	}: _(
+		RawOrigin::Signed(caller),
+		value,
+		beneficiary_lookup,
+		chunks,
+		Zero::zero(),
+		None,
+		description
+	)
+
+	propose_spend_waiting {
+		let (caller, value, beneficiary_lookup, chunks) = setup_proposal::<T, _>(SEED);
+		// Every block is outside the allowed proposal period.
+		<AllowedProposalPeriodOverride<T, I>>::put(T::BlockNumber::zero());
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
+		let description = Some(T::Hash::default());
+	}: propose_spend(
+		RawOrigin::Signed(caller),
+		value,
+		beneficiary_lookup,
+		chunks,
+		Zero::zero(),
+		None,
+		description
+	)
+	verify {
+		ensure!(Treasury::<T, I>::waiting_proposal_count() == 1, "Proposal not queued");
+	}
+
+	propose_spend_scheduled {
+		let s in 1 .. T::MaxOccurrences::get();
+		let (caller, _, beneficiary_lookup, _) = setup_proposal::<T, _>(SEED);
+		let amounts: BoundedVec<_, T::MaxOccurrences> =
+			vec![T::Currency::minimum_balance(); s as usize].try_into().unwrap();
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
+	}: _(RawOrigin::Signed(caller), amounts, beneficiary_lookup)
+	verify {
+		ensure!(Treasury::<T, I>::proposal_count() == 1, "Proposal not created");
+	}
+
+	propose_spend_split {
+		let b in 1 .. T::MaxBeneficiaries::get();
+		let (caller, value, _, chunks) = setup_proposal::<T, _>(SEED);
+		let share = Permill::from_rational(1u32, b);
+		let beneficiaries: BoundedVec<_, T::MaxBeneficiaries> = (0..b)
+			.map(|i| {
+				let who = account("beneficiary", i, SEED);
+				let beneficiary = T::NativePaymaster::beneficiary_of(who);
+				(T::BeneficiaryLookup::unlookup(beneficiary), share)
+			})
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap();
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
+	}: _(RawOrigin::Signed(caller), value, beneficiaries, chunks)
+	verify {
+		ensure!(Treasury::<T, I>::proposal_count() == 1, "Proposal not created");
+	}
+
+	set_metadata {
+		let (caller, value, beneficiary_lookup, chunks) = setup_proposal::<T, _>(SEED);
+		Treasury::<T, _>::propose_spend(
+			RawOrigin::Signed(caller.clone()).into(),
+			value,
+			beneficiary_lookup,
+			chunks,
+			Zero::zero(),
+			None,
+			None,
+		)?;
+		let proposal_id = Treasury::<T, _>::proposal_count() - 1;
+		let metadata = vec![0u8; T::MaxMetadataLen::get() as usize];
+	}: _(RawOrigin::Signed(caller), proposal_id, metadata)
+	verify {
+		ensure!(
+			Treasury::<T, I>::proposal_metadata(proposal_id).is_some(),
+			"Metadata not set"
+		);
+	}
+
+	propose_spend_exempt {
+		let origin = T::BondExemptOrigin::successful_origin();
+		let (_, value, beneficiary_lookup, chunks) = setup_proposal::<T, _>(SEED);
+	}: _<T::Origin>(origin, value, beneficiary_lookup, chunks, Zero::zero(), None)
+	verify {
+		ensure!(Treasury::<T, I>::proposal_count() == 1, "Proposal not created");
+	}
 
 	reject_proposal {
 		let (caller, value, beneficiary_lookup, chunks) = setup_proposal::<T, _>(SEED);
@@ -72,11 +240,53 @@ benchmarks_instance_pallet! {
 			RawOrigin::Signed(caller).into(),
 			value,
 			beneficiary_lookup,
-			1
+			chunks,
+			Zero::zero(),
+			None,
+			None
 		)?;
 		let proposal_id = Treasury::<T, _>::proposal_count() - 1;
 	}: _(RawOrigin::Root, proposal_id)
 
+	reject_proposal_with_reason {
+		let r in 0 .. T::MaxReasonLength::get();
+		let (caller, value, beneficiary_lookup, _) = setup_proposal::<T, _>(SEED);
+		Treasury::<T, _>::propose_spend(
+			RawOrigin::Signed(caller).into(),
+			value,
+			beneficiary_lookup,
+			1,
+			Zero::zero(),
+			None,
+			None
+		)?;
+		let proposal_id = Treasury::<T, _>::proposal_count() - 1;
+		let reason = vec![0u8; r as usize];
+	}: _(RawOrigin::Root, proposal_id, reason)
+
+	reject_proposals {
+		let p in 1 .. T::MaxApprovals::get();
+		let mut proposal_ids = Vec::new();
+		for i in 0..p {
+			let (caller, value, beneficiary_lookup, chunks) = setup_proposal::<T, _>(i);
+			Treasury::<T, _>::propose_spend(
+				RawOrigin::Signed(caller).into(),
+				value,
+				beneficiary_lookup,
+				chunks,
+				Zero::zero(),
+				None,
+				None
+			)?;
+			proposal_ids.push(Treasury::<T, _>::proposal_count() - 1);
+		}
+		let proposal_ids: BoundedVec<_, _> = proposal_ids.try_into().unwrap();
+	}: _(RawOrigin::Root, proposal_ids)
+
+	reject_waiting_proposal {
+		create_waiting_proposals::<T, _>(1)?;
+	}: _(RawOrigin::Root, 0)
+
 	approve_proposal {
 		let p in 0 .. T::MaxApprovals::get() - 1;
 		create_approved_proposals::<T, _>(p)?;
@@ -85,17 +295,398 @@ benchmarks_instance_pallet! {
 			RawOrigin::Signed(caller).into(),
 			value,
 			beneficiary_lookup,
-			1
+			chunks,
+			Zero::zero(),
+			None,
+			None
+		)?;
+		let proposal_id = Treasury::<T, _>::proposal_count() - 1;
+	}: _(RawOrigin::Root, proposal_id)
+
+	approve_proposals {
+		let p in 1 .. T::MaxApprovals::get();
+		let mut proposal_ids = Vec::new();
+		for i in 0..p {
+			let (caller, value, beneficiary_lookup, chunks) = setup_proposal::<T, _>(i);
+			Treasury::<T, _>::propose_spend(
+				RawOrigin::Signed(caller).into(),
+				value,
+				beneficiary_lookup,
+				chunks,
+				Zero::zero(),
+				None,
+				None
+			)?;
+			proposal_ids.push(Treasury::<T, _>::proposal_count() - 1);
+		}
+		let proposal_ids: BoundedVec<_, _> = proposal_ids.try_into().unwrap();
+	}: _(RawOrigin::Root, proposal_ids)
+	verify {
+		ensure!(<Approvals<T, I>>::get().len() == p as usize, "Not all approved");
+	}
+
+	amend_proposal {
+		let (caller, value, beneficiary_lookup, chunks) = setup_proposal::<T, _>(SEED);
+		Treasury::<T, _>::propose_spend(
+			RawOrigin::Signed(caller).into(),
+			value,
+			beneficiary_lookup.clone(),
+			chunks,
+			Zero::zero(),
+			None,
+			None
+		)?;
+		let proposal_id = Treasury::<T, _>::proposal_count() - 1;
+		let new_value = value / 2u32.into();
+	}: _(RawOrigin::Root, proposal_id, new_value, beneficiary_lookup, chunks)
+
+	update_beneficiary {
+		let (caller, value, beneficiary_lookup, _) = setup_proposal::<T, _>(SEED);
+		Treasury::<T, _>::propose_spend(
+			RawOrigin::Signed(caller.clone()).into(),
+			value,
+			beneficiary_lookup,
+			1,
+			Zero::zero(),
+			None,
+			None
 		)?;
 		let proposal_id = Treasury::<T, _>::proposal_count() - 1;
+		let new_beneficiary =
+			T::NativePaymaster::beneficiary_of(account("new_beneficiary", 0, SEED));
+		let new_beneficiary_lookup = T::BeneficiaryLookup::unlookup(new_beneficiary.clone());
+	}: _(RawOrigin::Signed(caller), proposal_id, new_beneficiary_lookup)
+	verify {
+		ensure!(
+			Treasury::<T, I>::proposals(proposal_id).unwrap().beneficiary == new_beneficiary,
+			"Beneficiary not updated"
+		);
+	}
+
+	remove_approval {
+		let p in 1 .. T::MaxApprovals::get();
+		create_approved_proposals::<T, _>(p)?;
+		let proposal_id = Treasury::<T, _>::proposal_count() - 1;
+	}: _(RawOrigin::Root, proposal_id)
+
+	reprioritize_approval {
+		let p in 1 .. T::MaxApprovals::get();
+		// Leave room to move the last approval ahead of the others approved in this block.
+		frame_system::Pallet::<T>::set_block_number(One::one());
+		create_approved_proposals::<T, _>(p)?;
+		let proposal_id = Treasury::<T, _>::proposal_count() - 1;
+	}: _(RawOrigin::Root, proposal_id, 0)
+	verify {
+		ensure!(Treasury::<T, I>::approvals()[0] == proposal_id, "Approval not moved");
+	}
+
+	set_priority {
+		let p in 1 .. T::MaxApprovals::get();
+		create_approved_proposals::<T, _>(p)?;
+		let proposal_id = Treasury::<T, _>::proposal_count() - 1;
+	}: _(RawOrigin::Root, proposal_id, u8::MAX)
+	verify {
+		ensure!(
+			Treasury::<T, I>::approval_priority(proposal_id) == u8::MAX,
+			"Priority not set"
+		);
+	}
+
+	sort_approvals {
+		let p in 1 .. T::MaxApprovals::get();
+		create_approved_proposals::<T, _>(p)?;
+		let mut approvals = Treasury::<T, I>::approvals();
+		// Start from the reverse of the order in which they are paid.
+		Treasury::<T, I>::sort_for_payout(&mut approvals);
+		approvals.as_mut().reverse();
+	}: {
+		Treasury::<T, I>::sort_for_payout(&mut approvals);
+	}
+
+	veto_approved {
+		let p in 1 .. T::MaxApprovals::get();
+		create_approved_proposals::<T, _>(p)?;
+		let proposal_id = Treasury::<T, _>::proposal_count() - 1;
+	}: _(RawOrigin::Root, proposal_id, true)
+	verify {
+		ensure!(Treasury::<T, I>::proposals(proposal_id).is_none(), "Proposal not removed");
+	}
+
+	pause_proposal {
+		create_approved_proposals::<T, _>(1)?;
+		let proposal_id = Treasury::<T, _>::proposal_count() - 1;
 	}: _(RawOrigin::Root, proposal_id)
+	verify {
+		ensure!(Treasury::<T, I>::proposals(proposal_id).unwrap().paused, "Proposal not paused");
+	}
+
+	resume_proposal {
+		create_approved_proposals::<T, _>(1)?;
+		let proposal_id = Treasury::<T, _>::proposal_count() - 1;
+		Treasury::<T, I>::pause_proposal(RawOrigin::Root.into(), proposal_id)?;
+	}: _(RawOrigin::Root, proposal_id)
+	verify {
+		ensure!(!Treasury::<T, I>::proposals(proposal_id).unwrap().paused, "Proposal not resumed");
+	}
+
+	set_expiry {
+		create_approved_proposals::<T, _>(1)?;
+		let proposal_id = Treasury::<T, _>::proposal_count() - 1;
+		let expires_at = Some(T::SpendPeriod::get());
+	}: _(RawOrigin::Root, proposal_id, expires_at)
+	verify {
+		ensure!(
+			Treasury::<T, I>::proposals(proposal_id).unwrap().expires_at == expires_at,
+			"Expiry not set"
+		);
+	}
+
+	set_milestone_gated {
+		create_approved_proposals::<T, _>(1)?;
+		let proposal_id = Treasury::<T, _>::proposal_count() - 1;
+	}: _(RawOrigin::Root, proposal_id, true)
+	verify {
+		ensure!(
+			Treasury::<T, I>::proposals(proposal_id).unwrap().milestone_gated,
+			"Milestone gating not set"
+		);
+	}
+
+	confirm_milestone {
+		create_approved_proposals::<T, _>(1)?;
+		let proposal_id = Treasury::<T, _>::proposal_count() - 1;
+		Treasury::<T, I>::set_milestone_gated(RawOrigin::Root.into(), proposal_id, true)?;
+		let beneficiary = account("beneficiary", 0, SEED);
+	}: _(RawOrigin::Signed(beneficiary), proposal_id)
+	verify {
+		ensure!(
+			Treasury::<T, I>::proposals(proposal_id).unwrap().milestone_confirmed,
+			"Milestone not confirmed"
+		);
+	}
+
+	extend_proposal {
+		let (caller, value, beneficiary_lookup, chunks) = setup_proposal::<T, _>(SEED);
+		Treasury::<T, _>::propose_spend(
+			RawOrigin::Signed(caller.clone()).into(),
+			value,
+			beneficiary_lookup,
+			chunks,
+			Zero::zero(),
+			None,
+			None
+		)?;
+		let proposal_id = Treasury::<T, _>::proposal_count() - 1;
+		let _ = T::Currency::make_free_balance_be(&caller, value);
+		let additional_occurs = T::MaxOccurrences::get() - chunks;
+		let additional_value = T::Currency::minimum_balance() * additional_occurs.into();
+	}: _(RawOrigin::Root, proposal_id, additional_value, additional_occurs)
+	verify {
+		ensure!(
+			Treasury::<T, I>::proposals(proposal_id).unwrap().occurs == T::MaxOccurrences::get(),
+			"Proposal not extended"
+		);
+	}
+
+	fund {
+		let (caller, value, _, _) = setup_proposal::<T, _>(SEED);
+		let amount = value / 2u32.into();
+	}: _(RawOrigin::Signed(caller), amount)
+	verify {
+		ensure!(Treasury::<T, I>::pot() >= amount, "Pot not funded");
+	}
+
+	set_burn {
+		let burn = Some(Permill::from_percent(10));
+	}: _(RawOrigin::Root, burn)
+	verify {
+		ensure!(Treasury::<T, I>::burn_override() == burn, "Burn not set");
+	}
+
+	set_burn_destinations {
+		let d in 0 .. T::MaxBurnDestinations::get();
+		let destinations: BoundedVec<_, _> = (0 .. d)
+			.map(|i| (account("destination", i, SEED), Permill::from_parts(1)))
+			.collect::<Vec<(T::AccountId, Permill)>>()
+			.try_into()
+			.map_err(|_| "Too many destinations")?;
+	}: _(RawOrigin::Root, destinations)
+	verify {
+		ensure!(Treasury::<T, I>::burn_destinations().len() == d as usize, "Destinations not set");
+	}
+
+	set_allowed_proposal_period {
+		let blocks = Some(T::SpendPeriod::get());
+	}: _(RawOrigin::Root, blocks)
+	verify {
+		ensure!(
+			Treasury::<T, I>::allowed_proposal_period_override() == blocks,
+			"Period not set"
+		);
+	}
+
+	add_proposer {
+		let who: T::AccountId = account("proposer", 0, SEED);
+		let who_lookup = T::Lookup::unlookup(who.clone());
+	}: _(RawOrigin::Root, who_lookup)
+	verify {
+		ensure!(Treasury::<T, I>::approved_proposers(&who).is_some(), "Proposer not added");
+	}
+
+	remove_proposer {
+		let who: T::AccountId = account("proposer", 0, SEED);
+		let who_lookup = T::Lookup::unlookup(who.clone());
+		Treasury::<T, I>::add_proposer(RawOrigin::Root.into(), who_lookup.clone())?;
+	}: _(RawOrigin::Root, who_lookup)
+	verify {
+		ensure!(Treasury::<T, I>::approved_proposers(&who).is_none(), "Proposer not removed");
+	}
+
+	ban_proposer {
+		let who: T::AccountId = account("proposer", 0, SEED);
+		let who_lookup = T::Lookup::unlookup(who.clone());
+	}: _(RawOrigin::Root, who_lookup, None)
+	verify {
+		ensure!(Treasury::<T, I>::banned_until(&who) == Some(None), "Proposer not banned");
+	}
+
+	unban_proposer {
+		let who: T::AccountId = account("proposer", 0, SEED);
+		let who_lookup = T::Lookup::unlookup(who.clone());
+		Treasury::<T, I>::ban_proposer(RawOrigin::Root.into(), who_lookup.clone(), None)?;
+	}: _(RawOrigin::Root, who_lookup)
+	verify {
+		ensure!(Treasury::<T, I>::banned_until(&who).is_none(), "Proposer not unbanned");
+	}
+
+	withdraw_proposal {
+		let (caller, value, beneficiary_lookup, chunks) = setup_proposal::<T, _>(SEED);
+		Treasury::<T, _>::propose_spend(
+			RawOrigin::Signed(caller.clone()).into(),
+			value,
+			beneficiary_lookup,
+			chunks,
+			Zero::zero(),
+			None,
+			None
+		)?;
+		let proposal_id = Treasury::<T, _>::proposal_count() - 1;
+	}: _(RawOrigin::Signed(caller), proposal_id)
+
+	cancel_waiting {
+		create_waiting_proposals::<T, _>(1)?;
+		let caller: T::AccountId = account("caller", 0, SEED);
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		ensure!(Treasury::<T, I>::waiting_proposal_count() == 0, "Proposal not cancelled");
+	}
+
+	claim_payout {
+		setup_pot_account::<T, _>();
+		let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+		let value = T::Currency::minimum_balance() * 100u32.into();
+		PendingPayouts::<T, I>::insert(&beneficiary, value);
+		TotalPendingPayouts::<T, I>::put(value);
+	}: _(RawOrigin::Signed(beneficiary.clone()))
+	verify {
+		ensure!(Treasury::<T, I>::pending_payouts(&beneficiary).is_zero(), "Payout not claimed");
+	}
+
+	spend {
+		let origin = T::SpendOrigin::successful_origin();
+		let (_, value, beneficiary_lookup, _) = setup_proposal::<T, _>(SEED);
+	}: _<T::Origin>(origin, value, beneficiary_lookup)
+	verify {
+		ensure!(<Approvals<T, I>>::get().len() == 1, "Spend not approved");
+	}
+
+	spend_asset {
+		let origin = T::SpendAssetOrigin::successful_origin();
+		let (asset_kind, amount, beneficiary) = setup_asset_spend::<T, _>()?;
+	}: _<T::Origin>(origin, asset_kind, amount, beneficiary)
+	verify {
+		ensure!(Treasury::<T, I>::asset_spends(0).is_some(), "Asset spend not approved");
+	}
+
+	payout_asset {
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset_kind, amount, beneficiary) = setup_asset_spend::<T, _>()?;
+		Treasury::<T, I>::spend_asset(
+			T::SpendAssetOrigin::successful_origin(),
+			asset_kind,
+			amount,
+			beneficiary,
+		)?;
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		let status = Treasury::<T, I>::asset_spends(0).map(|spend| spend.status);
+		ensure!(matches!(status, Some(PaymentState::Attempted { .. })), "Asset spend not paid");
+	}
+
+	check_status {
+		let caller: T::AccountId = whitelisted_caller();
+		create_paid_asset_spend::<T, _>(&caller)?;
+		if let Some(PaymentState::Attempted { id }) =
+			Treasury::<T, I>::asset_spends(0).map(|spend| spend.status)
+		{
+			T::Paymaster::ensure_concluded(id);
+		}
+	}: _(RawOrigin::Signed(caller), 0)
+
+	set_vesting_period {
+		create_approved_proposals::<T, _>(1)?;
+		let proposal_id = Treasury::<T, _>::proposal_count() - 1;
+		let vesting_period = Some(T::SpendPeriod::get());
+	}: _(RawOrigin::Root, proposal_id, vesting_period)
+	verify {
+		ensure!(
+			Treasury::<T, I>::proposals(proposal_id).unwrap().vesting_period == vesting_period,
+			"Vesting period not set"
+		);
+	}
+
+	report_proposal {
+		let (caller, value, beneficiary_lookup, chunks) = setup_proposal::<T, _>(SEED);
+		Treasury::<T, _>::propose_spend(
+			RawOrigin::Signed(caller).into(),
+			value,
+			beneficiary_lookup,
+			chunks,
+			Zero::zero(),
+			None,
+			None,
+		)?;
+		let proposal_id = Treasury::<T, _>::proposal_count() - 1;
+		let reporter: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(reporter.clone()), proposal_id)
+	verify {
+		ensure!(Treasury::<T, I>::reporter(proposal_id) == Some(reporter), "Not reported");
+	}
 
 	on_initialize_proposals {
 		let p in 0 .. T::MaxApprovals::get();
+		let w in 0 .. T::MaxWaitingProposals::get();
 		setup_pot_account::<T, _>();
 		create_approved_proposals::<T, _>(p)?;
+		create_waiting_proposals::<T, _>(w)?;
+		// The waiting proposals were queued before the last spend block, so they are overdue.
+		<WaitingPromotionCutoff<T, I>>::put(w);
+	}: {
+		Treasury::<T, _>::on_initialize(T::BlockNumber::zero());
+	} verify {
+		ensure!(Treasury::<T, I>::waiting_proposal_count() == 0, "Not all promoted");
+	}
+
+	on_initialize_waiting {
+		let p in 0 .. T::MaxWaitingProposals::get();
+		setup_pot_account::<T, _>();
+		create_waiting_proposals::<T, _>(p)?;
+		// None of them were promoted in `on_idle`, so the spend block promotes them all.
+		<WaitingPromotionCutoff<T, I>>::put(p);
 	}: {
 		Treasury::<T, _>::on_initialize(T::BlockNumber::zero());
+	} verify {
+		ensure!(Treasury::<T, I>::waiting_proposal_count() == 0, "Not all promoted");
 	}
 }
 
@@ -36,8 +36,8 @@
 //! ### Terminology
 //!
 //! - **Proposal:** A suggestion to allocate funds from the pot to a beneficiary.
-//! - **Beneficiary:** An account who will receive the funds from a proposal iff the proposal is
-//!   approved.
+//! - **Beneficiary:** Who will receive the funds from a proposal iff the proposal is approved.
+//!   Usually an account, but may be e.g. a location on another chain.
 //! - **Deposit:** Funds that a proposer must lock when making a proposal. The deposit will be
 //!   returned or slashed if the proposal is approved or rejected respectively.
 //! - **Pot:** Unspent funds accumulated by the treasury pallet.
@@ -48,40 +48,98 @@
 //!
 //! General spending/proposal protocol:
 //! - `propose_spend` - Make a spending proposal and stake the required deposit.
+//! - `propose_spend_scheduled` - Make a spending proposal paid out on a custom schedule.
+//! - `propose_spend_exempt` - Make a spending proposal without a deposit, from a privileged
+//!   origin.
 //! - `reject_proposal` - Reject a proposal, slashing the deposit.
+//! - `reject_proposal_with_reason` - Reject a proposal with a reason, slashing the deposit.
+//! - `reject_proposals` - Reject several proposals at once, slashing their deposits.
+//! - `reject_waiting_proposal` - Reject a proposal that is still waiting, slashing the deposit.
 //! - `approve_proposal` - Accept the proposal, returning the deposit.
+//! - `approve_proposals` - Accept several proposals at once.
+//! - `amend_proposal` - Change the value, beneficiary or chunks of an unapproved proposal.
+//! - `extend_proposal` - Add further occurrences to a live proposal, topping up the deposit.
+//! - `update_beneficiary` - Point a pending proposal at a new beneficiary.
+//! - `remove_approval` - Take an approved proposal back out of the spending queue.
+//! - `reprioritize_approval` - Move an approved proposal within the spending queue.
+//! - `pause_proposal` - Put the payouts of a proposal on hold.
+//! - `resume_proposal` - Resume the payouts of a paused proposal.
+//! - `set_expiry` - Set or clear the block from which a proposal is no longer paid.
+//! - `set_milestone_gated` - Require each occurrence of a proposal to be acknowledged first.
+//! - `confirm_milestone` - Acknowledge the next milestone of a gated proposal.
+//! - `veto_approved` - Veto an approved proposal before it is paid, slashing or returning the
+//!   remaining bond.
+//! - `spend` - Approve a spend from the pot directly, without a proposal or deposit.
+//! - `fund` - Donate funds into the pot.
+//! - `force_spend` - Run a spend round immediately.
+//! - `set_burn` - Override the percentage of spare funds burnt per spend period.
+//! - `set_burn_destinations` - Share each burn out between several accounts.
+//! - `set_allowed_proposal_period` - Override the window in which proposals are accepted
+//!   directly.
+//! - `add_proposer` - Allow an account to make proposals, restricting proposals to the allowlist.
+//! - `remove_proposer` - Remove an account from the proposer allowlist.
+//! - `withdraw_proposal` - Withdraw an undecided proposal, recovering the deposit minus a fee.
+//! - `cancel_waiting` - Cancel a waiting proposal, recovering the whole deposit.
+//! - `claim_payout` - Claim awards left pending under `PayoutMode::Pull`.
+//! - `spend_asset` - Approve a spend of some kind of asset, paid through `Config::Paymaster`.
+//! - `payout_asset` - Pay out an approved asset spend, or retry a failed payment.
+//! - `check_status` - Settle the payment of an asset spend.
+//! - `set_vesting_period` - Set or clear the period over which the awards of a proposal vest.
+//! - `propose_spend_split` - Make a spending proposal shared between several beneficiaries.
+//! - `set_metadata` - Set the hash or URI describing a proposal.
+//! - `report_proposal` - Report a proposal as one to reject, for a share of the slash.
+//! - `ban_proposer` - Stop an account from making proposals, for a while or for good.
+//! - `unban_proposer` - Lift the ban on an account.
 //!
 //! ## GenesisConfig
 //!
-//! The Treasury pallet depends on the [`GenesisConfig`].
+//! The Treasury pallet depends on the [`GenesisConfig`]. Its `pot` is put in the treasury
+//! account at genesis, on top of the existential deposit.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-// mod benchmarking; TODO: fix benchamrks for frame changes
+mod benchmarking;
+pub mod migrations;
+mod pay;
+mod preimages;
 #[cfg(test)]
 mod tests;
 pub mod weights;
 
 use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 
 use sp_runtime::{
-	traits::{AccountIdConversion, Saturating, StaticLookup, Zero},
+	traits::{
+		AccountIdConversion, AtLeast32BitUnsigned, CheckedSub, One, Saturating, StaticLookup, Zero,
+	},
 	Permill, RuntimeDebug,
 };
-use sp_std::prelude::*;
+use sp_std::{
+	cmp::Reverse, collections::btree_map::BTreeMap, convert::TryInto, fmt::Debug,
+	marker::PhantomData, prelude::*,
+};
 
 use frame_support::{
+	dispatch::{DispatchError, DispatchResult},
 	print,
 	traits::{
 		Currency, ExistenceRequirement::KeepAlive, Get, Imbalance, OnUnbalanced,
 		ReservableCurrency, WithdrawReasons,
 	},
+	transactional,
 	weights::Weight,
-	PalletId,
+	BoundedVec, CloneNoBound, PalletId, PartialEqNoBound, RuntimeDebugNoBound,
 };
 
 pub use pallet::*;
+pub use pay::{
+	CurrencyPaymaster, Pay, PayFromAccount, Paymaster, PaymentStatus, VestingHandler,
+	VestingScheduleHandler,
+};
+pub use preimages::QueryPreimage;
 pub use weights::WeightInfo;
 
 pub type BalanceOf<T, I = ()> =
@@ -92,6 +150,22 @@ pub type PositiveImbalanceOf<T, I = ()> = <<T as Config<I>>::Currency as Currenc
 pub type NegativeImbalanceOf<T, I = ()> = <<T as Config<I>>::Currency as Currency<
 	<T as frame_system::Config>::AccountId,
 >>::NegativeImbalance;
+pub type ProposalOf<T, I = ()> = Proposal<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T, I>,
+	<T as frame_system::Config>::BlockNumber,
+	<T as Config<I>>::MaxOccurrences,
+	<T as Config<I>>::MaxBeneficiaries,
+	<T as Config<I>>::Beneficiary,
+>;
+pub type BeneficiaryLookupOf<T, I = ()> =
+	<<T as Config<I>>::BeneficiaryLookup as StaticLookup>::Source;
+pub type PayBalanceOf<T, I = ()> = <<T as Config<I>>::Paymaster as Pay>::Balance;
+pub type PayBeneficiaryOf<T, I = ()> = <<T as Config<I>>::Paymaster as Pay>::Beneficiary;
+pub type AssetKindOf<T, I = ()> = <<T as Config<I>>::Paymaster as Pay>::AssetKind;
+pub type PaymentIdOf<T, I = ()> = <<T as Config<I>>::Paymaster as Pay>::Id;
+pub type AssetSpendOf<T, I = ()> =
+	AssetSpend<AssetKindOf<T, I>, PayBalanceOf<T, I>, PayBeneficiaryOf<T, I>, PaymentIdOf<T, I>>;
 
 /// A trait to allow the Treasury Pallet to spend it's funds for other purposes.
 /// There is an expectation that the implementer of this trait will correctly manage
@@ -104,7 +178,12 @@ pub type NegativeImbalanceOf<T, I = ()> = <<T as Config<I>>::Currency as Currenc
 ///   value.
 /// * `missed_any`: If there were items that you want to spend on, but there were not enough funds,
 ///   mark this value as `true`. This will prevent the treasury from burning the excess funds.
-#[impl_trait_for_tuples::impl_for_tuples(30)]
+///
+/// Implementors that need to know more about the spend period, see `SpendContext`, can override
+/// `spend_funds_with_context`, which is what the treasury calls.
+///
+/// When implemented for a tuple, the members are called in order until `total_weight` exceeds
+/// `SpendContext::weight_limit`. The rest are skipped and `SpendFundsTruncated` is emitted.
 pub trait SpendFunds<T: Config<I>, I: 'static = ()> {
 	fn spend_funds(
 		budget_remaining: &mut BalanceOf<T, I>,
@@ -112,27 +191,557 @@ pub trait SpendFunds<T: Config<I>, I: 'static = ()> {
 		total_weight: &mut Weight,
 		missed_any: &mut bool,
 	);
+
+	/// As `spend_funds`, given the `context` of the spend period. Calls `spend_funds` by
+	/// default.
+	fn spend_funds_with_context(
+		context: &SpendContext<BalanceOf<T, I>, T::BlockNumber>,
+		budget_remaining: &mut BalanceOf<T, I>,
+		imbalance: &mut PositiveImbalanceOf<T, I>,
+		total_weight: &mut Weight,
+		missed_any: &mut bool,
+	) {
+		let _ = context;
+		Self::spend_funds(budget_remaining, imbalance, total_weight, missed_any)
+	}
+}
+
+impl<T: Config<I>, I: 'static> SpendFunds<T, I> for () {
+	fn spend_funds(
+		_budget_remaining: &mut BalanceOf<T, I>,
+		_imbalance: &mut PositiveImbalanceOf<T, I>,
+		_total_weight: &mut Weight,
+		_missed_any: &mut bool,
+	) {
+	}
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(1, 30)]
+impl<T: Config<I>, I: 'static> SpendFunds<T, I> for Tuple {
+	fn spend_funds(
+		budget_remaining: &mut BalanceOf<T, I>,
+		imbalance: &mut PositiveImbalanceOf<T, I>,
+		total_weight: &mut Weight,
+		missed_any: &mut bool,
+	) {
+		for_tuples!(
+			#( Tuple::spend_funds(budget_remaining, imbalance, total_weight, missed_any); )*
+		)
+	}
+
+	fn spend_funds_with_context(
+		context: &SpendContext<BalanceOf<T, I>, T::BlockNumber>,
+		budget_remaining: &mut BalanceOf<T, I>,
+		imbalance: &mut PositiveImbalanceOf<T, I>,
+		total_weight: &mut Weight,
+		missed_any: &mut bool,
+	) {
+		let mut skipped = 0u32;
+		for_tuples!(
+			#(
+				if *total_weight > context.weight_limit {
+					skipped += 1;
+				} else {
+					Tuple::spend_funds_with_context(
+						context,
+						budget_remaining,
+						imbalance,
+						total_weight,
+						missed_any,
+					);
+				}
+			)*
+		);
+		if skipped > 0 {
+			log::warn!(
+				target: "runtime::treasury",
+				"SpendFunds exceeded its weight limit; skipping the remaining {} implementors.",
+				skipped,
+			);
+			Pallet::<T, I>::deposit_event(Event::SpendFundsTruncated(skipped));
+		}
+	}
+}
+
+/// Lets other pallets react to the decisions taken on proposals in `Proposals`. Each function
+/// does nothing by default.
+///
+/// During a spend round the calls are made once `Approvals` has been updated, so they may queue
+/// further spends. When implemented for a tuple, the members are called in order.
+pub trait OnProposalLifecycle<T: Config<I>, I: 'static = ()> {
+	/// `proposal` at `index` was approved, including by `spend`.
+	fn on_approved(index: ProposalIndex, proposal: &ProposalOf<T, I>) {
+		let _ = (index, proposal);
+	}
+
+	/// `proposal` at `index` was rejected or vetoed, or expired or lapsed in a spend round.
+	fn on_rejected(index: ProposalIndex, proposal: &ProposalOf<T, I>) {
+		let _ = (index, proposal);
+	}
+
+	/// `amount` was awarded for the proposal at `index`, completing its `occurrence`th payout.
+	fn on_awarded(index: ProposalIndex, amount: BalanceOf<T, I>, occurrence: u32) {
+		let _ = (index, amount, occurrence);
+	}
+
+	/// The proposal at `index` was paid in full and removed.
+	fn on_completed(index: ProposalIndex) {
+		let _ = index;
+	}
+}
+
+impl<T: Config<I>, I: 'static> OnProposalLifecycle<T, I> for () {}
+
+#[impl_trait_for_tuples::impl_for_tuples(1, 30)]
+impl<T: Config<I>, I: 'static> OnProposalLifecycle<T, I> for Tuple {
+	fn on_approved(index: ProposalIndex, proposal: &ProposalOf<T, I>) {
+		for_tuples!( #( Tuple::on_approved(index, proposal); )* )
+	}
+
+	fn on_rejected(index: ProposalIndex, proposal: &ProposalOf<T, I>) {
+		for_tuples!( #( Tuple::on_rejected(index, proposal); )* )
+	}
+
+	fn on_awarded(index: ProposalIndex, amount: BalanceOf<T, I>, occurrence: u32) {
+		for_tuples!( #( Tuple::on_awarded(index, amount, occurrence); )* )
+	}
+
+	fn on_completed(index: ProposalIndex) {
+		for_tuples!( #( Tuple::on_completed(index); )* )
+	}
+}
+
+/// A call to `T::OnProposalLifecycle` made during a spend round. The calls are held back until
+/// `Approvals` has been written, so that handlers may queue spends of their own.
+enum LifecycleCall<T: Config<I>, I: 'static> {
+	Rejected(ProposalIndex, ProposalOf<T, I>),
+	Awarded(ProposalIndex, BalanceOf<T, I>, u32),
+	Completed(ProposalIndex),
+}
+
+impl<T: Config<I>, I: 'static> LifecycleCall<T, I> {
+	fn make(self) {
+		match self {
+			LifecycleCall::Rejected(index, proposal) =>
+				T::OnProposalLifecycle::on_rejected(index, &proposal),
+			LifecycleCall::Awarded(index, amount, occurrence) =>
+				T::OnProposalLifecycle::on_awarded(index, amount, occurrence),
+			LifecycleCall::Completed(index) => T::OnProposalLifecycle::on_completed(index),
+		}
+	}
+}
+
+/// Lets other pallets pay from the pot without a bond, under the same budget and with the same
+/// events as the extrinsics.
+pub trait TreasurySpender {
+	/// The balance that is spent.
+	type Balance;
+	/// Who is paid.
+	type Beneficiary;
+
+	/// Queue a spend of `value` to `beneficiary` for the next spend period, as `spend` does.
+	/// Returns the index of the approved proposal.
+	fn queue_spend(
+		value: Self::Balance,
+		beneficiary: Self::Beneficiary,
+	) -> Result<ProposalIndex, DispatchError>;
+
+	/// Pay `value` to `beneficiary` from the pot now. Only funds that are not committed to
+	/// approved proposals may be spent, and the pot account is kept alive. The spend counts
+	/// against `T::MaxSpendPerPeriod` like the awards of the current spend period.
+	fn spend_now(value: Self::Balance, beneficiary: Self::Beneficiary) -> DispatchResult;
+}
+
+/// Read-only access to the state of the treasury, for other pallets.
+pub trait TreasuryInspect {
+	/// The balance held in the pot.
+	type Balance;
+
+	/// The funds in the pot that can be spent, see `Pallet::pot`.
+	fn pot() -> Self::Balance;
+
+	/// The funds committed to approved proposals.
+	fn committed() -> Self::Balance;
+
+	/// Whether there is a proposal at `index` in `Proposals`.
+	fn proposal_exists(index: ProposalIndex) -> bool;
+}
+
+/// What the treasury tells `SpendFunds` implementors about the spend period in progress.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SpendContext<Balance, BlockNumber> {
+	/// The block at which the funds are spent.
+	pub now: BlockNumber,
+	/// The index of the spend period, see `Pallet::spend_period_index`.
+	pub period: BlockNumber,
+	/// The amount awarded to approved proposals in this spend period so far.
+	pub awarded: Balance,
+	/// The number of approvals that could not be paid for want of funds.
+	pub missed_approvals: u32,
+	/// The `total_weight` past which the remaining members of a `SpendFunds` tuple are skipped,
+	/// leaving `T::SpendFundsWeightLimit` for the implementors.
+	pub weight_limit: Weight,
 }
 
 /// An index of a proposal. Just a `u32`.
 pub type ProposalIndex = u32;
 
 /// A spending proposal.
-#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
-pub struct Proposal<AccountId, Balance> {
-	/// The account proposing it.
-	proposer: AccountId,
-	/// The (total) amount that should be paid if the proposal is accepted.
-	value: Balance,
-	/// The account to whom the payment should be made if the proposal is accepted.
-	beneficiary: AccountId,
+#[derive(CloneNoBound, Eq, PartialEqNoBound, RuntimeDebugNoBound)]
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[codec(mel_bound(
+	AccountId: MaxEncodedLen,
+	Balance: MaxEncodedLen,
+	BlockNumber: MaxEncodedLen,
+	Beneficiary: MaxEncodedLen,
+))]
+#[scale_info(skip_type_params(MaxOccurrences, MaxBeneficiaries))]
+pub struct Proposal<
+	AccountId,
+	Balance,
+	BlockNumber,
+	MaxOccurrences,
+	MaxBeneficiaries,
+	Beneficiary = AccountId,
+> where
+	AccountId: Clone + PartialEq + Debug,
+	Beneficiary: Clone + PartialEq + Debug,
+	Balance: Clone + PartialEq + Debug,
+	BlockNumber: Clone + PartialEq + Debug,
+	MaxOccurrences: Get<u32>,
+	MaxBeneficiaries: Get<u32>,
+{
+	/// The account proposing it, or `None` for a spend made by `T::SpendOrigin`.
+	proposer: Option<AccountId>,
+	/// The total amount that should be paid over all occurrences if the proposal is accepted.
+	total_value: Balance,
+	/// Who the payment should be made to if the proposal is accepted.
+	beneficiary: Beneficiary,
 	/// The amount held on deposit (reserved) for making this proposal.
 	bond: Balance,
+	/// The part of the bond that is still reserved. It is released pro rata as occurrences are
+	/// paid.
+	remaining_bond: Balance,
 	/// How many times should this be repeated.
 	occurs: u32,
 	/// How many times left to be repeated.
 	remaining_occurs: u32,
+	/// The minimum number of blocks between two payouts. Zero pays every spend period.
+	interval: BlockNumber,
+	/// The block at which the last payout was made, if any.
+	last_paid_at: Option<BlockNumber>,
+	/// Whether payouts are on hold. A paused proposal keeps its remaining occurrences.
+	paused: bool,
+	/// The amount paid per occurrence, if it was given explicitly rather than split in equal
+	/// chunks. It always sums to `total_value`.
+	schedule: Option<BoundedVec<Balance, MaxOccurrences>>,
+	/// The block from which the proposal is no longer paid, if any.
+	expires_at: Option<BlockNumber>,
+	/// Whether each occurrence must be acknowledged with `confirm_milestone` before it is paid.
+	milestone_gated: bool,
+	/// Whether a milestone has been confirmed and not yet consumed by a payout.
+	milestone_confirmed: bool,
+	/// The part of the next occurrence already paid out of a pot that could not cover all of
+	/// it, under `T::AllowPartialPayouts`.
+	partially_paid: Balance,
+	/// The number of blocks over which each award is released from a lock, if it is vested.
+	vesting_period: Option<BlockNumber>,
+	/// The other beneficiaries of a proposal split with `propose_spend_split`, with their shares
+	/// of each award. `beneficiary` receives whatever they are not given.
+	splits: BoundedVec<(Beneficiary, Permill), MaxBeneficiaries>,
+	/// The block at which it was proposed, including to `WaitingProposals`.
+	proposed_at: BlockNumber,
+	/// The block at which it was approved, while it is in `Approvals`.
+	approved_at: Option<BlockNumber>,
+}
+
+impl<AccountId, Balance, BlockNumber, MaxOccurrences, MaxBeneficiaries, Beneficiary>
+	Proposal<AccountId, Balance, BlockNumber, MaxOccurrences, MaxBeneficiaries, Beneficiary>
+where
+	AccountId: Clone + PartialEq + Debug,
+	Beneficiary: Clone + PartialEq + Debug,
+	Balance: Debug + AtLeast32BitUnsigned + Copy,
+	BlockNumber: Debug + AtLeast32BitUnsigned + Copy,
+	MaxOccurrences: Get<u32>,
+	MaxBeneficiaries: Get<u32>,
+{
+	/// The amount to pay for the next occurrence.
+	fn next_award(&self) -> Balance {
+		self.award_at(self.occurs.saturating_sub(self.remaining_occurs))
+	}
+
+	/// The amount to pay for the zero based `occurrence`. The part of `total_value` that does not
+	/// divide evenly between the occurrences is paid with the final one.
+	fn award_at(&self, occurrence: u32) -> Balance {
+		if let Some(schedule) = &self.schedule {
+			return schedule.get(occurrence as usize).copied().unwrap_or_else(Zero::zero)
+		}
+		let chunk = self.total_value / self.occurs.max(1).into();
+		if occurrence.saturating_add(1) >= self.occurs {
+			let paid_before = chunk.saturating_mul(self.occurs.saturating_sub(1).into());
+			self.total_value.saturating_sub(paid_before)
+		} else {
+			chunk
+		}
+	}
+
+	/// The part of the next award that has not been paid yet.
+	fn outstanding_award(&self) -> Balance {
+		self.next_award().saturating_sub(self.partially_paid)
+	}
+
+	/// The amount still to be paid over the remaining occurrences.
+	fn remaining_value(&self) -> Balance {
+		(self.occurs.saturating_sub(self.remaining_occurs)..self.occurs)
+			.fold(Zero::zero(), |total, occurrence| total.saturating_add(self.award_at(occurrence)))
+			.saturating_sub(self.partially_paid)
+	}
+
+	/// The part of the bond to return for the next occurrence. Whatever is still held is returned
+	/// with the final one.
+	fn next_bond_release(&self) -> Balance {
+		if self.remaining_occurs <= 1 {
+			self.remaining_bond
+		} else {
+			(self.bond / self.occurs.into()).min(self.remaining_bond)
+		}
+	}
+
+	/// Whether the proposal has expired at block `now`.
+	fn is_expired(&self, now: BlockNumber) -> bool {
+		self.expires_at.map_or(false, |expires_at| now >= expires_at)
+	}
+
+	/// Whether at least `interval` blocks have passed since the last payout at block `now`.
+	fn is_due(&self, now: BlockNumber) -> bool {
+		self.last_paid_at.map_or(true, |last| now.saturating_sub(last) >= self.interval)
+	}
+
+	/// Split `amount` between the beneficiaries, `beneficiary` first. Shares are rounded, and
+	/// the rounding dust goes to `beneficiary` along with the part no split claims.
+	fn shares_of(&self, amount: Balance) -> Vec<(Beneficiary, Balance)> {
+		let mut shares: Vec<(Beneficiary, Balance)> =
+			sp_std::iter::once((self.beneficiary.clone(), Zero::zero()))
+				.chain(self.splits.iter().map(|(who, share)| (who.clone(), *share * amount)))
+				.collect();
+		let shared = shares
+			.iter()
+			.fold(Zero::zero(), |total: Balance, (_, part)| total.saturating_add(*part));
+		shares[0].1 = amount.saturating_sub(shared);
+		shares
+	}
+}
+
+/// A proposal as exposed to the outside world, e.g. through `TreasuryApi`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ProposalDetails<AccountId, Balance, BlockNumber, Beneficiary = AccountId> {
+	/// The account proposing it, or `None` for a spend made by `T::SpendOrigin`.
+	pub proposer: Option<AccountId>,
+	/// Who the payments are made to.
+	pub beneficiary: Beneficiary,
+	/// The total amount paid over all occurrences.
+	pub total_value: Balance,
+	/// The amount paid for the next occurrence.
+	pub next_award: Balance,
+	/// The amount held on deposit for making the proposal.
+	pub bond: Balance,
+	/// The part of the bond that is still reserved.
+	pub remaining_bond: Balance,
+	/// The number of occurrences.
+	pub occurs: u32,
+	/// The number of occurrences still to be paid.
+	pub remaining_occurs: u32,
+	/// The minimum number of blocks between two payouts.
+	pub interval: BlockNumber,
+	/// The block at which the last payout was made, if any.
+	pub last_paid_at: Option<BlockNumber>,
+	/// Whether payouts are on hold.
+	pub paused: bool,
+	/// The amount paid per occurrence, if it was given explicitly.
+	pub schedule: Option<Vec<Balance>>,
+	/// The block from which the proposal is no longer paid, if any.
+	pub expires_at: Option<BlockNumber>,
+	/// Whether each occurrence must be acknowledged before it is paid.
+	pub milestone_gated: bool,
+	/// Whether the next milestone is confirmed.
+	pub milestone_confirmed: bool,
+	/// The number of blocks over which each award vests, if any.
+	pub vesting_period: Option<BlockNumber>,
+	/// The other beneficiaries and their shares of each award.
+	pub splits: Vec<(Beneficiary, Permill)>,
+	/// The block at which it was proposed.
+	pub proposed_at: BlockNumber,
+	/// The block at which it was approved, if it is approved.
+	pub approved_at: Option<BlockNumber>,
+}
+
+impl<AccountId, Balance, BlockNumber, MaxOccurrences, MaxBeneficiaries, Beneficiary>
+	From<Proposal<AccountId, Balance, BlockNumber, MaxOccurrences, MaxBeneficiaries, Beneficiary>>
+	for ProposalDetails<AccountId, Balance, BlockNumber, Beneficiary>
+where
+	AccountId: Clone + PartialEq + Debug,
+	Beneficiary: Clone + PartialEq + Debug,
+	Balance: Debug + AtLeast32BitUnsigned + Copy,
+	BlockNumber: Debug + AtLeast32BitUnsigned + Copy,
+	MaxOccurrences: Get<u32>,
+	MaxBeneficiaries: Get<u32>,
+{
+	fn from(
+		proposal: Proposal<
+			AccountId,
+			Balance,
+			BlockNumber,
+			MaxOccurrences,
+			MaxBeneficiaries,
+			Beneficiary,
+		>,
+	) -> Self {
+		Self {
+			next_award: proposal.next_award(),
+			proposer: proposal.proposer,
+			beneficiary: proposal.beneficiary,
+			total_value: proposal.total_value,
+			bond: proposal.bond,
+			remaining_bond: proposal.remaining_bond,
+			occurs: proposal.occurs,
+			remaining_occurs: proposal.remaining_occurs,
+			interval: proposal.interval,
+			last_paid_at: proposal.last_paid_at,
+			paused: proposal.paused,
+			schedule: proposal.schedule.map(|schedule| schedule.into_inner()),
+			expires_at: proposal.expires_at,
+			milestone_gated: proposal.milestone_gated,
+			milestone_confirmed: proposal.milestone_confirmed,
+			vesting_period: proposal.vesting_period,
+			splits: proposal.splits.into_inner(),
+			proposed_at: proposal.proposed_at,
+			approved_at: proposal.approved_at,
+		}
+	}
+}
+
+/// How awards are delivered to the beneficiaries of approved proposals.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum PayoutMode {
+	/// Awards are paid through `Config::NativePaymaster` during `spend_funds`.
+	Push,
+	/// Awards are recorded in `PendingPayouts` and stay in the pot until the beneficiary calls
+	/// `claim_payout`. Beneficiaries without a local account are paid as under `Push`.
+	Pull,
+}
+
+/// The order in which `spend_funds` works through the approvals of the same priority.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum PayoutOrdering {
+	/// Approvals are paid in the order they were approved.
+	Fifo,
+	/// Approvals are paid smallest next award first, to pay as many as the pot allows. An
+	/// approval whose award exceeds the whole pot does not hold back the burn.
+	SmallestFirst,
+}
+
+/// An index of an asset spend. Just a `u32`.
+pub type AssetSpendIndex = u32;
+
+/// Where the payment of an asset spend stands.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum PaymentState<Id> {
+	/// The spend has not been paid out yet.
+	Pending,
+	/// The payment `id` was made and has not concluded yet.
+	Attempted { id: Id },
+	/// The payment failed. The spend may be paid out again.
+	Failed,
+}
+
+/// A spend of some kind of asset, paid out through `Config::Paymaster`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct AssetSpend<AssetKind, Balance, Beneficiary, PaymentId> {
+	/// The kind of asset to be paid.
+	asset_kind: AssetKind,
+	/// The amount to be paid.
+	amount: Balance,
+	/// Who is to be paid.
+	beneficiary: Beneficiary,
+	/// Where the payment stands.
+	status: PaymentState<PaymentId>,
+}
+
+/// Where a proposal stands in its lifecycle.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum ProposalStatus {
+	/// Queued in `WaitingProposals` until the next allowed proposal period.
+	Waiting,
+	/// In `Proposals`, but not approved.
+	Proposed,
+	/// Approved, with nothing paid yet.
+	Approved,
+	/// Approved, with some of its occurrences paid.
+	PartiallyPaid {
+		/// The number of occurrences paid so far.
+		paid_occurrences: u32,
+	},
+	/// Paid in full and removed.
+	Completed,
+	/// Removed before it was paid in full: rejected, vetoed, expired or lapsed.
+	Rejected,
+	/// Withdrawn or cancelled by its proposer before it was approved.
+	Withdrawn,
+}
+
+/// Where a deposit into the treasury came from, as reported by the `Deposit` event.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum DepositSource {
+	/// Deposited through the `OnUnbalanced` impl, without saying where from.
+	Unattributed,
+	/// Transaction fees and tips.
+	Fees,
+	/// A slash.
+	Slash,
+	/// The proceeds of an auction.
+	Auction,
+	/// Any other source, tagged as the runtime sees fit.
+	Other(u8),
+}
+
+impl Default for DepositSource {
+	fn default() -> Self {
+		DepositSource::Unattributed
+	}
+}
+
+/// How much of the surplus is burnt at the end of each spend period.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum BurnMode {
+	/// Burn `Pallet::burn` of the surplus.
+	Static,
+	/// Burn `factor` of what the pot grew by since the end of the last spend period, but no
+	/// more than `max` of the surplus. Nothing is burnt if the pot shrank.
+	Adaptive {
+		/// The share of the growth that is burnt.
+		factor: Permill,
+		/// The largest share of the surplus that is burnt.
+		max: Permill,
+	},
+}
+
+impl Default for BurnMode {
+	fn default() -> Self {
+		BurnMode::Static
+	}
+}
+
+/// What happens to the bond still held for an approved proposal that expires.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum ExpiredBondPolicy {
+	/// The bond is returned to the proposer.
+	Return,
+	/// The bond is slashed.
+	Slash,
 }
 
 #[frame_support::pallet]
@@ -141,9 +750,13 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
 	#[pallet::generate_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
 	#[pallet::config]
@@ -151,6 +764,20 @@ pub mod pallet {
 		/// The staking balance.
 		type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
 
+		/// Who the awards of proposals are paid to, usually `Self::AccountId`.
+		type Beneficiary: Parameter + MaxEncodedLen + Ord + MaybeSerializeDeserialize;
+
+		/// Converting the beneficiaries given in calls into `Self::Beneficiary`.
+		type BeneficiaryLookup: StaticLookup<Target = Self::Beneficiary>;
+
+		/// Delivers the awards of proposals to their beneficiaries. `CurrencyPaymaster` deposits
+		/// them into local accounts.
+		type NativePaymaster: Paymaster<Self, I>;
+
+		/// Locks the awards of proposals with a vesting period, releasing them over that period.
+		/// `()` does not support vesting.
+		type VestingHandler: VestingHandler<Self::AccountId, BalanceOf<Self, I>, Self::BlockNumber>;
+
 		/// Origin from which approvals must come.
 		type ApproveOrigin: EnsureOrigin<Self::Origin>;
 
@@ -172,7 +799,19 @@ pub mod pallet {
 		#[pallet::constant]
 		type ProposalBondMinimum: Get<BalanceOf<Self, I>>;
 
-		/// Period that proposals will enter, after that they go in WaitingProposals
+		/// Maximum amount of funds that should be placed in a deposit for making a proposal.
+		#[pallet::constant]
+		type ProposalBondMaximum: Get<Option<BalanceOf<Self, I>>>;
+
+		/// Maximum total value of a proposal, as a fraction of the pot at the time it is made.
+		/// Proposals are not checked again when the pot changes later on.
+		#[pallet::constant]
+		type MaxProposalValue: Get<Option<Permill>>;
+
+		/// Period that proposals will enter, after that they go in WaitingProposals. Can be
+		/// overridden through `set_allowed_proposal_period`. Waiting proposals are promoted in
+		/// `on_idle` during this period, or at the following spend block if `on_idle` does not
+		/// get to them.
 		#[pallet::constant]
 		type AllowedProposalPeriod: Get<Self::BlockNumber>;
 
@@ -180,7 +819,30 @@ pub mod pallet {
 		#[pallet::constant]
 		type SpendPeriod: Get<Self::BlockNumber>;
 
-		/// Percentage of spare funds (if any) that are burnt per spend period.
+		/// Shifts the spend blocks within the spend period: funds are spent at blocks `n` where
+		/// `(n + SpendPeriodOffset) % SpendPeriod` is zero. Lets several treasuries avoid spending
+		/// in the same block.
+		#[pallet::constant]
+		type SpendPeriodOffset: Get<Self::BlockNumber>;
+
+		/// Maximum amount a single beneficiary is awarded per spend period. Further payouts to
+		/// the beneficiary stay queued for the next spend period.
+		#[pallet::constant]
+		type MaxPerBeneficiaryPerPeriod: Get<Option<BalanceOf<Self, I>>>;
+
+		/// Maximum amount awarded to proposals, or paid through `TreasurySpender::spend_now`, per
+		/// spend period. Once it is reached, further approvals stay queued for the next spend
+		/// period.
+		#[pallet::constant]
+		type MaxSpendPerPeriod: Get<Option<BalanceOf<Self, I>>>;
+
+		/// The number of spend periods after which an approval that still cannot be paid lapses.
+		/// The proposal is removed and its bond returned. `None` keeps such approvals forever.
+		#[pallet::constant]
+		type ApprovalTtlPeriods: Get<Option<u32>>;
+
+		/// Percentage of spare funds (if any) that are burnt per spend period under
+		/// `BurnMode::Static`, unless overridden through `set_burn`.
 		#[pallet::constant]
 		type Burn: Get<Permill>;
 
@@ -197,25 +859,183 @@ pub mod pallet {
 		/// Runtime hooks to external pallet using treasury to compute spend funds.
 		type SpendFunds: SpendFunds<Self, I>;
 
+		/// The weight the `SpendFunds` implementors may use in a spend period, beyond which the
+		/// remaining members of a tuple are skipped.
+		#[pallet::constant]
+		type SpendFundsWeightLimit: Get<Weight>;
+
 		/// The maximum number of approvals that can wait in the spending queue.
 		#[pallet::constant]
 		type MaxApprovals: Get<u32>;
+
+		/// Fraction of the held bond that is slashed when a proposer withdraws their proposal.
+		#[pallet::constant]
+		type ProposalWithdrawalFee: Get<Permill>;
+
+		/// The origin required for approving spends from the treasury outside of the proposal
+		/// process. The `Success` value is the maximum amount that this origin is allowed to
+		/// spend at a time.
+		type SpendOrigin: EnsureOrigin<Self::Origin, Success = BalanceOf<Self, I>>;
+
+		/// Pays out the spends made with `spend_asset`. `()` disables them.
+		type Paymaster: Pay;
+
+		/// The origin required for `spend_asset`. The `Success` value is the maximum amount
+		/// that this origin is allowed to spend at a time, of any kind of asset.
+		type SpendAssetOrigin: EnsureOrigin<Self::Origin, Success = PayBalanceOf<Self, I>>;
+
+		/// Origin from which the treasury parameters can be changed at runtime.
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Maximum length of the reason given when rejecting a proposal.
+		#[pallet::constant]
+		type MaxReasonLength: Get<u32>;
+
+		/// Whether awards are deposited directly or left for the beneficiary to claim.
+		#[pallet::constant]
+		type PayoutMode: Get<PayoutMode>;
+
+		/// The order in which approvals of the same priority are paid.
+		#[pallet::constant]
+		type PayoutOrdering: Get<PayoutOrdering>;
+
+		/// Whether the bond still held for an expired proposal is returned or slashed.
+		#[pallet::constant]
+		type ExpiredBondPolicy: Get<ExpiredBondPolicy>;
+
+		/// Whether the burn is a fixed share of the surplus or follows the growth of the pot.
+		#[pallet::constant]
+		type BurnMode: Get<BurnMode>;
+
+		/// Whether proposals that pay their own proposer are flagged with `SelfProposal`.
+		#[pallet::constant]
+		type FlagSelfProposals: Get<bool>;
+
+		/// Whether a proposal can only be approved if its next award fits in the uncommitted pot.
+		#[pallet::constant]
+		type EnsureAffordable: Get<bool>;
+
+		/// Whether an approval the pot cannot cover is paid whatever is left, carrying the rest
+		/// over to later spend periods, rather than being missed.
+		#[pallet::constant]
+		type AllowPartialPayouts: Get<bool>;
+
+		/// Origin from which accounts can be added to or removed from `ApprovedProposers`.
+		type ProposerManagementOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Origin that can make proposals without placing a deposit. The `Success` value is the
+		/// account recorded as the proposer.
+		type BondExemptOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+
+		/// The maximum number of accounts in `ApprovedProposers`.
+		#[pallet::constant]
+		type MaxProposers: Get<u32>;
+
+		/// The maximum number of proposals that can wait in `WaitingProposals`. Gaps left in the
+		/// waiting queue by removed proposals count towards it until the next spend period.
+		#[pallet::constant]
+		type MaxWaitingProposals: Get<u32>;
+
+		/// The maximum number of open or waiting proposals a single account may have.
+		#[pallet::constant]
+		type MaxPendingPerAccount: Get<u32>;
+
+		/// The number of blocks an account must wait after making a proposal before making
+		/// another. Rejecting or withdrawing the proposal does not shorten the wait.
+		#[pallet::constant]
+		type ProposalCooldown: Get<Self::BlockNumber>;
+
+		/// The maximum number of occurrences a proposal may be paid out in.
+		#[pallet::constant]
+		type MaxOccurrences: Get<u32>;
+
+		/// Fraction of the held bond that is slashed when a proposal is rejected. The rest is
+		/// returned to the proposer.
+		#[pallet::constant]
+		type RejectionSlashRatio: Get<Permill>;
+
+		/// Share of the bond slashed on rejection that is paid to whoever reported the proposal
+		/// with `report_proposal`. The rest goes to `T::OnSlash` as usual.
+		#[pallet::constant]
+		type ReporterReward: Get<Permill>;
+
+		/// The surplus burn never takes the remaining budget below this amount.
+		#[pallet::constant]
+		type BurnFloor: Get<BalanceOf<Self, I>>;
+
+		/// The surplus burn only applies to the part of the remaining budget above this amount,
+		/// so nothing is burnt while the pot holds no more than it.
+		#[pallet::constant]
+		type BurnThreshold: Get<BalanceOf<Self, I>>;
+
+		/// The maximum number of accounts the burn can be shared with.
+		#[pallet::constant]
+		type MaxBurnDestinations: Get<u32>;
+
+		/// The maximum number of beneficiaries a proposal can be split between.
+		#[pallet::constant]
+		type MaxBeneficiaries: Get<u32>;
+
+		/// Maximum length of the metadata of a proposal, usually a hash or a URI.
+		#[pallet::constant]
+		type MaxMetadataLen: Get<u32>;
+
+		/// The amount held on deposit by a proposer for setting the metadata of a proposal.
+		#[pallet::constant]
+		type MetadataDepositBase: Get<BalanceOf<Self, I>>;
+
+		/// The amount held on deposit per byte of metadata.
+		#[pallet::constant]
+		type MetadataDepositPerByte: Get<BalanceOf<Self, I>>;
+
+		/// Keeps the full descriptions of proposals made with a description hash available for
+		/// as long as the proposals exist. `()` keeps none.
+		type Preimages: QueryPreimage<Self::Hash>;
+
+		/// Told when proposals are approved, rejected, awarded and completed. `()` ignores it.
+		type OnProposalLifecycle: OnProposalLifecycle<Self, I>;
 	}
 
-	/// Number of waiting proposals that have been made.
+	/// Number of asset spends that have been made.
+	#[pallet::storage]
+	#[pallet::getter(fn asset_spend_count)]
+	pub(crate) type AssetSpendCount<T, I = ()> = StorageValue<_, AssetSpendIndex, ValueQuery>;
+
+	/// Asset spends that have not been paid out, or whose payment has not concluded.
+	#[pallet::storage]
+	#[pallet::getter(fn asset_spends)]
+	pub type AssetSpends<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, AssetSpendIndex, AssetSpendOf<T, I>, OptionQuery>;
+
+	/// Position of the oldest entry in the waiting proposal queue.
+	#[pallet::storage]
+	#[pallet::getter(fn waiting_queue_head)]
+	pub(crate) type WaitingQueueHead<T, I = ()> = StorageValue<_, ProposalIndex, ValueQuery>;
+
+	/// Position at which the next waiting proposal is queued.
+	#[pallet::storage]
+	#[pallet::getter(fn waiting_queue_tail)]
+	pub(crate) type WaitingQueueTail<T, I = ()> = StorageValue<_, ProposalIndex, ValueQuery>;
+
+	/// Waiting queue positions below this one were queued before the last spend block. Any of
+	/// them that `on_idle` has not promoted by the next spend block are promoted there.
 	#[pallet::storage]
-	#[pallet::getter(fn waiting_proposal_count)]
-	pub(crate) type WaitingProposalCount<T, I = ()> = StorageValue<_, ProposalIndex, ValueQuery>;
+	#[pallet::getter(fn waiting_promotion_cutoff)]
+	pub(crate) type WaitingPromotionCutoff<T, I = ()> = StorageValue<_, ProposalIndex, ValueQuery>;
 
-	/// Proposals that are waitning to be made.
+	/// Proposals that are waiting to be made, keyed by their position in the waiting queue.
+	/// Positions between `WaitingQueueHead` and `WaitingQueueTail` with no entry were removed
+	/// and are skipped on promotion.
 	#[pallet::storage]
 	#[pallet::getter(fn waiting_proposals)]
 	pub type WaitingProposals<T: Config<I>, I: 'static = ()> = StorageMap<
 		_,
 		Twox64Concat,
 		ProposalIndex,
-		Proposal<T::AccountId, BalanceOf<T, I>>,
+		ProposalOf<T, I>,
 		OptionQuery,
+		GetDefault,
+		T::MaxWaitingProposals,
 	>;
 
 	/// Number of proposals that have been made.
@@ -226,330 +1046,3148 @@ pub mod pallet {
 	/// Proposals that have been made.
 	#[pallet::storage]
 	#[pallet::getter(fn proposals)]
-	pub type Proposals<T: Config<I>, I: 'static = ()> = StorageMap<
-		_,
-		Twox64Concat,
-		ProposalIndex,
-		Proposal<T::AccountId, BalanceOf<T, I>>,
-		OptionQuery,
-	>;
+	pub type Proposals<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, ProposalIndex, ProposalOf<T, I>, OptionQuery>;
 
-	/// Proposal indices that have been approved but not yet awarded.
+	/// Proposal indices that have been approved but not yet awarded. Recurring proposals stay in
+	/// here until every occurrence has been paid.
+	///
+	/// Approvals are paid in descending `ApprovalPriority`, then in the order they were approved,
+	/// as recorded in `ApprovedAt`, with the lower proposal index first among those approved in
+	/// the same block.
 	#[pallet::storage]
 	#[pallet::getter(fn approvals)]
 	pub type Approvals<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, BoundedVec<ProposalIndex, T::MaxApprovals>, ValueQuery>;
 
-	#[pallet::genesis_config]
-	pub struct GenesisConfig;
+	/// The block at which each proposal in `Approvals` was approved. This orders the spending
+	/// queue, and is adjusted when an approval is moved with `reprioritize_approval`.
+	#[pallet::storage]
+	#[pallet::getter(fn approved_at)]
+	pub type ApprovedAt<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, ProposalIndex, T::BlockNumber, OptionQuery>;
 
-	#[cfg(feature = "std")]
-	impl Default for GenesisConfig {
-		fn default() -> Self {
-			Self
-		}
-	}
+	/// The priority class of each proposal in `Approvals`, if not zero. Higher classes are paid
+	/// first.
+	#[pallet::storage]
+	#[pallet::getter(fn approval_priority)]
+	pub type ApprovalPriority<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, ProposalIndex, u8, ValueQuery>;
 
-	#[cfg(feature = "std")]
-	impl GenesisConfig {
-		/// Direct implementation of `GenesisBuild::assimilate_storage`.
-		#[deprecated(
-			note = "use `<GensisConfig<T, I> as GenesisBuild<T, I>>::assimilate_storage` instead"
-		)]
-		pub fn assimilate_storage<T: Config<I>, I: 'static>(
-			&self,
-			storage: &mut sp_runtime::Storage,
-		) -> Result<(), String> {
-			<Self as GenesisBuild<T, I>>::assimilate_storage(self, storage)
-		}
-	}
+	/// Percentage of spare funds burnt per spend period, overriding `T::Burn` when set.
+	#[pallet::storage]
+	#[pallet::getter(fn burn_override)]
+	pub type BurnOverride<T: Config<I>, I: 'static = ()> = StorageValue<_, Permill, OptionQuery>;
 
-	#[pallet::genesis_build]
-	impl<T: Config<I>, I: 'static> GenesisBuild<T, I> for GenesisConfig {
-		fn build(&self) {
-			// Create Treasury account
-			let account_id = <Pallet<T, I>>::account_id();
-			let min = T::Currency::minimum_balance();
-			if T::Currency::free_balance(&account_id) < min {
-				let _ = T::Currency::make_free_balance_be(&account_id, min);
-			}
-		}
-	}
+	/// Period that proposals will enter, overriding `T::AllowedProposalPeriod` when set.
+	#[pallet::storage]
+	#[pallet::getter(fn allowed_proposal_period_override)]
+	pub type AllowedProposalPeriodOverride<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, T::BlockNumber, OptionQuery>;
 
-	#[pallet::event]
-	#[pallet::generate_deposit(pub(super) fn deposit_event)]
-	pub enum Event<T: Config<I>, I: 'static = ()> {
-		/// New proposal. \[proposal_index\]
-		Proposed(ProposalIndex),
-		/// New waiting proposal. \[proposal_index\]
-		WaitingProposed(ProposalIndex),
-		/// Move Proposal from Waiting to Proposed
-		WaitingProposalTransfered(ProposalIndex),
-		/// We have ended a spend period and will now allocate funds. \[budget_remaining\]
-		Spending(BalanceOf<T, I>),
-		/// Some funds have been allocated. \[proposal_index, award, beneficiary\]
-		Awarded(ProposalIndex, BalanceOf<T, I>, T::AccountId),
-		/// A proposal was rejected; funds were slashed. \[proposal_index, slashed\]
-		Rejected(ProposalIndex, BalanceOf<T, I>),
-		/// Some of our funds have been burnt. \[burn\]
-		Burnt(BalanceOf<T, I>),
-		/// Spending has finished; this is the amount that rolls over until next spend.
-		/// \[budget_remaining\]
-		Rollover(BalanceOf<T, I>),
-		/// Some funds have been deposited. \[deposit\]
-		Deposit(BalanceOf<T, I>),
-	}
+	/// Awards that have been granted but not yet claimed, by beneficiary account. Only used with
+	/// `PayoutMode::Pull`.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_payouts)]
+	pub type PendingPayouts<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T, I>, ValueQuery>;
 
-	/// Old name generated by `decl_event`.
-	#[deprecated(note = "use `Event` instead")]
-	pub type RawEvent<T, I = ()> = Event<T, I>;
+	/// The sum of all `PendingPayouts`. These funds are kept out of the spendable pot.
+	#[pallet::storage]
+	#[pallet::getter(fn total_pending_payouts)]
+	pub type TotalPendingPayouts<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BalanceOf<T, I>, ValueQuery>;
 
-	/// Error for the treasury pallet.
-	#[pallet::error]
-	pub enum Error<T, I = ()> {
-		/// Proposer's balance is too low.
-		InsufficientProposersBalance,
-		/// No proposal or bounty at that index.
-		InvalidIndex,
-		/// Too many approvals in the queue.
-		TooManyApprovals,
-	}
+	/// Accounts allowed to make proposals. While empty, any signed account may propose.
+	#[pallet::storage]
+	#[pallet::getter(fn approved_proposers)]
+	pub type ApprovedProposers<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::AccountId, (), OptionQuery>;
+
+	/// Number of accounts in `ApprovedProposers`.
+	#[pallet::storage]
+	#[pallet::getter(fn approved_proposer_count)]
+	pub type ApprovedProposerCount<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, u32, ValueQuery>;
+
+	/// Accounts that may not make proposals, until the given block or, if `None`, for good.
+	/// Expired bans are removed when the account next proposes.
+	#[pallet::storage]
+	#[pallet::getter(fn banned_until)]
+	pub type BannedProposers<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::AccountId, Option<T::BlockNumber>, OptionQuery>;
+
+	/// The block at which each account last made a proposal, waiting or not, from which
+	/// `T::ProposalCooldown` is counted. Kept when the proposal is rejected or withdrawn.
+	#[pallet::storage]
+	#[pallet::getter(fn last_proposal_block)]
+	pub type LastProposalBlock<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::AccountId, T::BlockNumber, OptionQuery>;
+
+	/// Number of entries in `Proposals` and `WaitingProposals` made by each proposer.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_by_proposer)]
+	pub type PendingByProposer<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	/// The indices of the entries in `Proposals` made by each proposer.
+	#[pallet::storage]
+	#[pallet::getter(fn proposals_by_proposer)]
+	pub type ProposalsByProposer<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		BoundedVec<ProposalIndex, T::MaxPendingPerAccount>,
+		ValueQuery,
+	>;
+
+	/// The indices of the entries in `WaitingProposals` made by each proposer.
+	#[pallet::storage]
+	#[pallet::getter(fn waiting_proposals_by_proposer)]
+	pub type WaitingProposalsByProposer<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		BoundedVec<ProposalIndex, T::MaxPendingPerAccount>,
+		ValueQuery,
+	>;
+
+	/// Accounts that receive a share of each burn, in order. Whatever is not shared out goes to
+	/// `T::BurnDestination`.
+	#[pallet::storage]
+	#[pallet::getter(fn burn_destinations)]
+	pub type BurnDestinations<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BoundedVec<(T::AccountId, Permill), T::MaxBurnDestinations>, ValueQuery>;
+
+	/// The amount awarded to proposals in the current spend period, together with what was paid
+	/// through `TreasurySpender::spend_now` since. Reset at the start of every spend round.
+	#[pallet::storage]
+	#[pallet::getter(fn spent_this_period)]
+	pub type SpentThisPeriod<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BalanceOf<T, I>, ValueQuery>;
+
+	/// The block of the last scheduled spend round. A round is run as soon as a full
+	/// `T::SpendPeriod` has passed since, even if the spend block itself was skipped.
+	#[pallet::storage]
+	#[pallet::getter(fn last_spend_block)]
+	pub type LastSpendBlock<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// The amount still to be paid to the proposals in `Approvals`, over all their remaining
+	/// occurrences.
+	#[pallet::storage]
+	#[pallet::getter(fn committed)]
+	pub type Committed<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BalanceOf<T, I>, ValueQuery>;
+
+	/// The sum of all awards ever granted to proposal beneficiaries.
+	#[pallet::storage]
+	#[pallet::getter(fn total_awarded)]
+	pub type TotalAwarded<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BalanceOf<T, I>, ValueQuery>;
+
+	/// The sum of all funds ever burnt from the pot.
+	#[pallet::storage]
+	#[pallet::getter(fn total_burnt)]
+	pub type TotalBurnt<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BalanceOf<T, I>, ValueQuery>;
+
+	/// The pot at the end of the last spend period, against which `BurnMode::Adaptive`
+	/// measures its growth.
+	#[pallet::storage]
+	#[pallet::getter(fn last_period_pot)]
+	pub type LastPeriodPot<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BalanceOf<T, I>, OptionQuery>;
+
+	/// The sum of all imbalances ever deposited into the pot.
+	#[pallet::storage]
+	#[pallet::getter(fn total_deposited)]
+	pub type TotalDeposited<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BalanceOf<T, I>, ValueQuery>;
+
+	/// The amount of the issuance deactivated on behalf of the pot. Follows the pot as it grows
+	/// and shrinks, since funds in the pot are not circulating.
+	#[pallet::storage]
+	#[pallet::getter(fn deactivated)]
+	pub type Deactivated<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BalanceOf<T, I>, ValueQuery>;
+
+	/// The metadata of proposals in `Proposals`, usually the hash or URI of a description held
+	/// off chain. It is removed along with the proposal.
+	#[pallet::storage]
+	#[pallet::getter(fn proposal_metadata)]
+	pub type ProposalMetadata<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, ProposalIndex, BoundedVec<u8, T::MaxMetadataLen>, OptionQuery>;
+
+	/// The account holding the deposit for the metadata of a proposal, and the amount held.
+	#[pallet::storage]
+	#[pallet::getter(fn metadata_deposit)]
+	pub type MetadataDeposits<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, ProposalIndex, (T::AccountId, BalanceOf<T, I>), OptionQuery>;
+
+	/// The hash of the full description of proposals in `Proposals`, whose preimage is requested
+	/// from `T::Preimages` until the proposal is removed.
+	#[pallet::storage]
+	#[pallet::getter(fn proposal_description)]
+	pub type ProposalDescriptions<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, ProposalIndex, T::Hash, OptionQuery>;
+
+	/// The hash of the full description of proposals in `WaitingProposals`. It moves to
+	/// `ProposalDescriptions` when the proposal is promoted.
+	#[pallet::storage]
+	#[pallet::getter(fn waiting_proposal_description)]
+	pub type WaitingProposalDescriptions<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, ProposalIndex, T::Hash, OptionQuery>;
+
+	/// The status of each proposal index that has been used.
+	#[pallet::storage]
+	#[pallet::getter(fn status)]
+	pub type Statuses<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, ProposalIndex, ProposalStatus, OptionQuery>;
+
+	/// The status of each waiting proposal index, until the proposal is promoted.
+	///
+	/// A rejected or withdrawn waiting proposal keeps its `Rejected` or `Withdrawn` status
+	/// permanently, like the entries of `Statuses`. Waiting positions are never given out twice,
+	/// so the entry is never overwritten: there is one per waiting proposal ever removed.
+	#[pallet::storage]
+	#[pallet::getter(fn waiting_status)]
+	pub type WaitingStatuses<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, ProposalIndex, ProposalStatus, OptionQuery>;
+
+	/// The account that reported a proposal in `Proposals` as one to reject, and which is paid
+	/// `T::ReporterReward` of the slash if it is.
+	#[pallet::storage]
+	#[pallet::getter(fn reporter)]
+	pub type Reporters<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, ProposalIndex, T::AccountId, OptionQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
+		/// The amount put in the pot on top of the existential deposit of the treasury account.
+		pub pot: BalanceOf<T, I>,
+		/// Proposals on the books from the start, as the proposer, the value, the beneficiary and
+		/// the number of chunks. Their bonds are reserved from the proposers.
+		pub proposals: Vec<(T::AccountId, BalanceOf<T, I>, T::Beneficiary, u32)>,
+		/// The indices of the proposals in `proposals` that start out approved, in the order
+		/// they are paid. At most `T::MaxApprovals` are allowed.
+		pub approvals: Vec<ProposalIndex>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config<I>, I: 'static> Default for GenesisConfig<T, I> {
+		fn default() -> Self {
+			Self { pot: Zero::zero(), proposals: Default::default(), approvals: Default::default() }
+		}
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config<I>, I: 'static> GenesisConfig<T, I> {
+		/// Direct implementation of `GenesisBuild::assimilate_storage`.
+		#[deprecated(
+			note = "use `<GensisConfig<T, I> as GenesisBuild<T, I>>::assimilate_storage` instead"
+		)]
+		pub fn assimilate_storage(&self, storage: &mut sp_runtime::Storage) -> Result<(), String> {
+			<Self as GenesisBuild<T, I>>::assimilate_storage(self, storage)
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config<I>, I: 'static> GenesisBuild<T, I> for GenesisConfig<T, I> {
+		fn build(&self) {
+			// Create Treasury account, holding `pot` on top of the existential deposit.
+			let account_id = <Pallet<T, I>>::account_id();
+			let balance = T::Currency::minimum_balance().saturating_add(self.pot);
+			if T::Currency::free_balance(&account_id) < balance {
+				let _ = T::Currency::make_free_balance_be(&account_id, balance);
+			}
+			<Pallet<T, I>>::update_deactivated();
+
+			for (proposer, value, beneficiary, chunks) in self.proposals.iter().cloned() {
+				let index = <Pallet<T, I>>::proposal_count();
+				let chunks = chunks.max(1);
+				assert!(
+					value >= T::Currency::minimum_balance() &&
+						chunks <= T::MaxOccurrences::get() &&
+						<Pallet<T, I>>::ensure_chunk_size(value, chunks).is_ok(),
+					"genesis proposal {} has too small a value or too many chunks",
+					index,
+				);
+				let bond = <Pallet<T, I>>::calculate_bond(value);
+				assert!(
+					T::Currency::reserve(&proposer, bond).is_ok(),
+					"the proposer of genesis proposal {} cannot cover its bond",
+					index,
+				);
+				<Pallet<T, I>>::note_pending(&proposer);
+				<Pallet<T, I>>::index_proposal(&proposer, index, false);
+				let proposal = Proposal {
+					proposer,
+					total_value: value,
+					beneficiary,
+					bond,
+					remaining_bond: bond,
+					occurs: chunks,
+					remaining_occurs: chunks,
+					interval: Zero::zero(),
+					last_paid_at: None,
+					paused: false,
+					schedule: None,
+					expires_at: None,
+					milestone_gated: false,
+					milestone_confirmed: false,
+					partially_paid: Zero::zero(),
+					vesting_period: None,
+					splits: Default::default(),
+					proposed_at: Zero::zero(),
+					approved_at: None,
+				};
+				<Proposals<T, I>>::insert(index, proposal);
+				<Statuses<T, I>>::insert(index, ProposalStatus::Proposed);
+				<ProposalCount<T, I>>::put(index + 1);
+			}
+
+			for &index in self.approvals.iter() {
+				let proposal = <Pallet<T, I>>::proposals(index).unwrap_or_else(|| {
+					panic!("genesis approval {} is not a genesis proposal", index)
+				});
+				assert!(
+					!<Pallet<T, I>>::approvals().contains(&index),
+					"genesis approval {} is given twice",
+					index,
+				);
+				assert!(
+					Approvals::<T, I>::try_append(index).is_ok(),
+					"more genesis approvals than `T::MaxApprovals`",
+				);
+				<Pallet<T, I>>::note_approved(index, Zero::zero());
+				<Statuses<T, I>>::insert(index, ProposalStatus::Approved);
+				<Pallet<T, I>>::note_committed(proposal.remaining_value());
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// New proposal, or a waiting proposal that was promoted. Describes the proposal: the
+		/// proposer, the beneficiary, the total value, the amount paid for the first occurrence,
+		/// the number of occurrences and the bond reserved.
+		Proposed {
+			proposal_index: ProposalIndex,
+			proposer: Option<T::AccountId>,
+			beneficiary: T::Beneficiary,
+			value: BalanceOf<T, I>,
+			chunk: BalanceOf<T, I>,
+			occurs: u32,
+			bond: BalanceOf<T, I>,
+		},
+		/// The proposal announced by the preceding `Proposed` or `WaitingProposed` event was made
+		/// without a deposit. \[proposal_index\]
+		BondExempt(ProposalIndex),
+		/// The proposal announced by the preceding `Proposed` or `WaitingProposed` event pays its
+		/// own proposer. Only emitted if `T::FlagSelfProposals` is set. \[proposal_index\]
+		SelfProposal(ProposalIndex),
+		/// A proposal was approved and queued for payment. \[proposal_index\]
+		Approved(ProposalIndex),
+		/// New waiting proposal, with the same description as `Proposed`.
+		WaitingProposed {
+			proposal_index: ProposalIndex,
+			proposer: Option<T::AccountId>,
+			beneficiary: T::Beneficiary,
+			value: BalanceOf<T, I>,
+			chunk: BalanceOf<T, I>,
+			occurs: u32,
+			bond: BalanceOf<T, I>,
+		},
+		/// The waiting proposal at `waiting_index` was promoted to `new_proposal_index`, with the
+		/// same description as `Proposed`.
+		WaitingProposalTransfered {
+			waiting_index: ProposalIndex,
+			new_proposal_index: ProposalIndex,
+			proposer: Option<T::AccountId>,
+			beneficiary: T::Beneficiary,
+			value: BalanceOf<T, I>,
+			chunk: BalanceOf<T, I>,
+			occurs: u32,
+			bond: BalanceOf<T, I>,
+		},
+		/// We have ended a spend period and will now allocate funds. \[budget_remaining\]
+		Spending(BalanceOf<T, I>),
+		/// Some funds have been allocated. `occurrence` counts from 1, and `remaining_occurs` is
+		/// the number of occurrences still to be paid after this one.
+		/// \[proposal_index, award, beneficiary, occurrence, remaining_occurs\]
+		Awarded(ProposalIndex, BalanceOf<T, I>, T::Beneficiary, u32, u32),
+		/// Part of the next award of a proposal was paid with what was left in the pot.
+		/// \[proposal_index, paid, outstanding\]
+		PartiallyAwarded(ProposalIndex, BalanceOf<T, I>, BalanceOf<T, I>),
+		/// The final occurrence of a recurring proposal was paid and the proposal removed.
+		/// \[proposal_index\]
+		RecurringCompleted(ProposalIndex),
+		/// A proposal was rejected; part of the bond was slashed and the rest refunded.
+		/// \[proposal_index, slashed, refunded, reason\]
+		Rejected(
+			ProposalIndex,
+			BalanceOf<T, I>,
+			BalanceOf<T, I>,
+			BoundedVec<u8, T::MaxReasonLength>,
+		),
+		/// A batch of proposals was rejected. Indices without a proposal were skipped.
+		/// \[rejected, skipped\]
+		ProposalsRejected(u32, Vec<ProposalIndex>),
+		/// A payout was deferred to the next spend period because the beneficiary reached
+		/// `T::MaxPerBeneficiaryPerPeriod`. \[proposal_index, beneficiary\]
+		PayoutDeferred(ProposalIndex, T::Beneficiary),
+		/// An approved proposal was not paid because the remaining budget could not cover it. It
+		/// stays queued. \[proposal_index, required, available\]
+		ProposalMissed(ProposalIndex, BalanceOf<T, I>, BalanceOf<T, I>),
+		/// `T::MaxSpendPerPeriod` was reached and the remaining approvals were left for the next
+		/// spend period. \[spent, max_spend\]
+		PeriodBudgetExhausted(BalanceOf<T, I>, BalanceOf<T, I>),
+		/// The `SpendFunds` implementors used up `T::SpendFundsWeightLimit` and the remaining
+		/// ones were skipped. \[skipped\]
+		SpendFundsTruncated(u32),
+		/// Some of our funds have been burnt. \[burn\]
+		Burnt(BalanceOf<T, I>),
+		/// Part of the burn was paid to a burn destination. \[destination, amount\]
+		BurnShared(T::AccountId, BalanceOf<T, I>),
+		/// The burn destinations were replaced.
+		BurnDestinationsSet,
+		/// Spending has finished; this is the amount that rolls over until next spend.
+		/// \[budget_remaining\]
+		Rollover(BalanceOf<T, I>),
+		/// Some funds have been deposited. \[deposit, source\]
+		Deposit(BalanceOf<T, I>, DepositSource),
+		/// The pot changed outside of the treasury and the deactivated issuance was brought back
+		/// in line with it. \[reactivated, deactivated\]
+		UpdatedInactive(BalanceOf<T, I>, BalanceOf<T, I>),
+		/// A proposal was removed from the approvals queue. \[proposal_index\]
+		ApprovalRemoved(ProposalIndex),
+		/// The beneficiary of a proposal was changed.
+		/// \[proposal_index, old_beneficiary, new_beneficiary\]
+		BeneficiaryUpdated(ProposalIndex, T::Beneficiary, T::Beneficiary),
+		/// A beneficiary claimed some of their pending payouts. \[beneficiary, amount\]
+		PayoutClaimed(T::AccountId, BalanceOf<T, I>),
+		/// An account was allowed to make proposals. \[who\]
+		ProposerAdded(T::AccountId),
+		/// An account is no longer allowed to make proposals. \[who\]
+		ProposerRemoved(T::AccountId),
+		/// An account was banned from making proposals, until `until` if set.
+		/// \[who, until\]
+		ProposerBanned(T::AccountId, Option<T::BlockNumber>),
+		/// The ban on an account was lifted. \[who\]
+		ProposerUnbanned(T::AccountId),
+		/// An approved proposal was moved within the spending queue.
+		/// \[proposal_index, old_position, new_position\]
+		ApprovalReprioritized(ProposalIndex, u32, u32),
+		/// The priority class of an approved proposal was set. \[proposal_index, priority\]
+		ApprovalPrioritySet(ProposalIndex, u8),
+		/// An approved proposal was vetoed before being fully paid. The held bond was slashed if
+		/// `slashed` is set, and returned otherwise. \[proposal_index, bond, slashed\]
+		Vetoed(ProposalIndex, BalanceOf<T, I>, bool),
+		/// Payouts of a proposal were put on hold. \[proposal_index\]
+		Paused(ProposalIndex),
+		/// Payouts of a paused proposal were resumed. \[proposal_index\]
+		Resumed(ProposalIndex),
+		/// The expiry block of a proposal was changed. \[proposal_index, expires_at\]
+		ExpirySet(ProposalIndex, Option<T::BlockNumber>),
+		/// An approved proposal expired before being fully paid and was removed.
+		/// \[proposal_index, unpaid_occurrences\]
+		ProposalExpired(ProposalIndex, u32),
+		/// An approved proposal could not be paid for `T::ApprovalTtlPeriods` spend periods and
+		/// was removed. \[proposal_index\]
+		ApprovalLapsed(ProposalIndex),
+		/// Whether the occurrences of a proposal must be acknowledged before being paid was
+		/// changed. \[proposal_index, gated\]
+		MilestoneGatingSet(ProposalIndex, bool),
+		/// The next milestone of a proposal was confirmed. \[proposal_index\]
+		MilestoneConfirmed(ProposalIndex),
+		/// The payout of a proposal was skipped because its milestone was not confirmed.
+		/// \[proposal_index\]
+		AwaitingMilestone(ProposalIndex),
+		/// Part of the bond of a proposal was returned to the proposer. `shortfall` is the part
+		/// that could not be unreserved. \[proposal_index, proposer, amount, shortfall\]
+		BondReturned(ProposalIndex, T::AccountId, BalanceOf<T, I>, BalanceOf<T, I>),
+		/// A proposal was withdrawn by its proposer. \[proposal_index, refunded\]
+		Withdrawn(ProposalIndex, BalanceOf<T, I>),
+		/// A waiting proposal was cancelled by its proposer. \[proposal_index, refunded\]
+		WaitingProposalCancelled(ProposalIndex, BalanceOf<T, I>),
+		/// A proposal was amended. \[proposal_index, old_value, new_value\]
+		Amended(ProposalIndex, BalanceOf<T, I>, BalanceOf<T, I>),
+		/// A proposal was extended with further occurrences.
+		/// \[proposal_index, additional_value, additional_occurs\]
+		Extended(ProposalIndex, BalanceOf<T, I>, u32),
+		/// A new spend proposal has been approved. \[proposal_index, amount, beneficiary\]
+		SpendApproved(ProposalIndex, BalanceOf<T, I>, T::Beneficiary),
+		/// Another pallet paid from the pot through `TreasurySpender::spend_now`.
+		/// \[amount, beneficiary\]
+		SpentNow(BalanceOf<T, I>, T::Beneficiary),
+		/// Some funds have been donated to the pot. \[contributor, amount\]
+		Funded(T::AccountId, BalanceOf<T, I>),
+		/// The burn percentage was overridden, or reset to `T::Burn` if `None`. \[burn\]
+		BurnSet(Option<Permill>),
+		/// The allowed proposal period was overridden, or reset to `T::AllowedProposalPeriod`
+		/// if `None`. \[blocks\]
+		AllowedProposalPeriodSet(Option<T::BlockNumber>),
+		/// An asset spend has been approved.
+		/// \[index, asset_kind, amount, beneficiary\]
+		AssetSpendApproved(
+			AssetSpendIndex,
+			AssetKindOf<T, I>,
+			PayBalanceOf<T, I>,
+			PayBeneficiaryOf<T, I>,
+		),
+		/// The payment of an asset spend was made. \[index, payment_id\]
+		AssetPaid(AssetSpendIndex, PaymentIdOf<T, I>),
+		/// The payment of an asset spend failed. The spend may be paid out again.
+		/// \[index, payment_id\]
+		AssetPaymentFailed(AssetSpendIndex, PaymentIdOf<T, I>),
+		/// The payment of an asset spend succeeded and the spend was removed. \[index\]
+		AssetSpendProcessed(AssetSpendIndex),
+		/// The vesting period of a proposal was changed. \[proposal_index, vesting_period\]
+		VestingPeriodSet(ProposalIndex, Option<T::BlockNumber>),
+		/// An award was locked in the account it was paid to, releasing linearly over `period`
+		/// blocks from `starting_block`. \[proposal_index, who, locked, starting_block, period\]
+		AwardVested(ProposalIndex, T::AccountId, BalanceOf<T, I>, T::BlockNumber, T::BlockNumber),
+		/// An award was paid, but could not be vested and is not locked. \[proposal_index\]
+		VestingFailed(ProposalIndex),
+		/// The metadata of a proposal was set. \[proposal_index\]
+		MetadataSet(ProposalIndex),
+		/// The metadata of a proposal was removed, and its deposit returned. \[proposal_index\]
+		MetadataCleared(ProposalIndex),
+		/// The preimage of the description of a proposal was requested. The index is that of
+		/// the preceding `Proposed` or `WaitingProposed` event. \[proposal_index, hash\]
+		DescriptionRequested(ProposalIndex, T::Hash),
+		/// The proposal was removed and the preimage of its description is no longer requested.
+		/// \[proposal_index, hash\]
+		DescriptionDropped(ProposalIndex, T::Hash),
+		/// A proposal was reported as one to reject. \[proposal_index, reporter\]
+		ProposalReported(ProposalIndex, T::AccountId),
+		/// The slash of a rejected proposal was shared with its reporter, and the remainder went
+		/// to `T::OnSlash`. \[proposal_index, reporter, reward, remainder\]
+		ReporterRewarded(ProposalIndex, T::AccountId, BalanceOf<T, I>, BalanceOf<T, I>),
+	}
+
+	/// Old name generated by `decl_event`.
+	#[deprecated(note = "use `Event` instead")]
+	pub type RawEvent<T, I = ()> = Event<T, I>;
+
+	/// Error for the treasury pallet.
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// Proposer's balance is too low.
+		InsufficientProposersBalance,
+		/// Deprecated: no longer returned, see `ProposalNotFound` and `NotInApprovalQueue`. Kept
+		/// so that the indices of the other errors do not change.
+		InvalidIndex,
+		/// Too many approvals in the queue.
+		TooManyApprovals,
+		/// The proposal is already in the approvals queue.
+		AlreadyApproved,
+		/// The value paid per occurrence is below the existential deposit.
+		ChunkTooSmall,
+		/// The proposal is not in the approvals queue.
+		NotInApprovalQueue,
+		/// The caller is not the proposer of the proposal.
+		NotProposer,
+		/// The spend origin is valid but the amount it is allowed to spend is lower than the
+		/// amount to be spent.
+		InsufficientPermission,
+		/// The amount is zero.
+		ZeroAmount,
+		/// The allowed proposal period is longer than the spend period.
+		InvalidProposalPeriod,
+		/// The rejection reason is longer than `T::MaxReasonLength`.
+		ReasonTooLong,
+		/// There is no pending payout to claim.
+		NoPendingPayout,
+		/// The treasury cannot currently pay out any of the pending payout.
+		InsufficientPot,
+		/// The signer is not in `ApprovedProposers`.
+		NotAllowedToPropose,
+		/// The account is already an approved proposer.
+		AlreadyApprovedProposer,
+		/// The account is not an approved proposer.
+		NotApprovedProposer,
+		/// There are already `T::MaxProposers` approved proposers.
+		TooManyProposers,
+		/// There are already `T::MaxWaitingProposals` waiting proposals.
+		TooManyWaitingProposals,
+		/// The proposer already has `T::MaxPendingPerAccount` open proposals.
+		TooManyPendingProposals,
+		/// The proposal value exceeds `T::MaxProposalValue` of the pot.
+		ProposalTooLarge,
+		/// The burn destination shares add up to more than 100%.
+		InvalidBurnShares,
+		/// The proposal is already paused.
+		AlreadyPaused,
+		/// The proposal is not paused.
+		NotPaused,
+		/// The proposal would be paid out in more than `T::MaxOccurrences` occurrences.
+		TooManyOccurrences,
+		/// A payout schedule must have at least one occurrence.
+		EmptySchedule,
+		/// The proposal does not require milestone confirmations.
+		NotMilestoneGated,
+		/// The next milestone of the proposal is already confirmed.
+		MilestoneAlreadyConfirmed,
+		/// The origin is neither the beneficiary of the proposal nor `T::ApproveOrigin`.
+		NotBeneficiary,
+		/// The proposal value is zero or below the existential deposit.
+		InvalidValue,
+		/// The treasury account cannot be the beneficiary of a proposal.
+		InvalidBeneficiary,
+		/// There is no proposal at that index.
+		ProposalNotFound,
+		/// Deprecated: no longer returned. Proposal and waiting proposal indices are counted
+		/// separately, so a proposal index never refers to a waiting proposal.
+		StillWaiting,
+		/// The next award of the proposal exceeds the uncommitted pot.
+		InsufficientFunds,
+		/// The approval cannot be moved there: both neighbours were approved in the same block,
+		/// and are paid in index order.
+		CannotReprioritize,
+		/// There is no asset spend at that index.
+		AssetSpendNotFound,
+		/// The asset spend was already paid out and its payment has not failed.
+		AlreadyAttempted,
+		/// The asset spend has not been paid out yet.
+		NotAttempted,
+		/// The `T::Paymaster` could not make the payment.
+		PayoutError,
+		/// The payment has not concluded yet.
+		Inconclusive,
+		/// The vesting period is zero blocks.
+		ZeroVestingPeriod,
+		/// A split proposal needs at least one beneficiary.
+		NoBeneficiaries,
+		/// The shares of a split proposal add up to more than 100%.
+		InvalidSplitShares,
+		/// The metadata is longer than `T::MaxMetadataLen`.
+		MetadataTooLong,
+		/// Proposers may not report their own proposals.
+		ReporterIsProposer,
+		/// The proposal has already been reported.
+		AlreadyReported,
+		/// The proposer is banned from making proposals.
+		ProposerBanned,
+		/// The account is not banned.
+		NotBanned,
+		/// The ban would already have expired.
+		BanAlreadyExpired,
+		/// The proposer made a proposal less than `T::ProposalCooldown` blocks ago.
+		CooldownActive,
+		/// The proposal has already been paid in part.
+		PartlyPaid,
+		/// The spend would take the amount spent in this spend period over
+		/// `T::MaxSpendPerPeriod`.
+		OverPeriodBudget,
+	}
 
 	#[pallet::hooks]
 	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
 		/// # <weight>
-		/// - Complexity: `O(A)` where `A` is the number of approvals
-		/// - Db reads and writes: `Approvals`, `pot account data`
+		/// - Complexity: `O(A + W)` where `A` is the number of approvals and `W` the number of
+		///   waiting proposals
+		/// - Db reads and writes: `LastSpendBlock`, `Deactivated`, `Approvals`, `WaitingQueueHead`,
+		///   `WaitingQueueTail`, `pot account data`
 		/// - Db reads and writes per approval: `Proposals`, `proposer account data`, `beneficiary
 		///   account data`
+		/// - Db reads and writes per waiting proposal: `WaitingProposals`, `ProposalCount`,
+		///   `Proposals`
 		/// - The weight is overestimated if some approvals got missed.
 		/// # </weight>
 		fn on_initialize(n: T::BlockNumber) -> Weight {
 			// Check to see if we should spend some funds!
-			if (n % T::SpendPeriod::get()).is_zero() {
-				Self::spend_funds()
+			let weight = if Self::is_spend_due(n) {
+				let weight = Self::spend_funds(n);
+				<LastSpendBlock<T, I>>::put(n);
+				weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+			} else {
+				T::DbWeight::get().reads(1)
+			};
+
+			// Funds may have reached or left the pot without going through the treasury.
+			if let Some((reactivated, deactivated)) = Self::update_deactivated() {
+				Self::deposit_event(Event::UpdatedInactive(reactivated, deactivated));
+			}
+			weight.saturating_add(T::DbWeight::get().reads_writes(3, 2))
+		}
+
+		/// Promote as many waiting proposals as fit in `remaining_weight`, during the allowed
+		/// proposal period only.
+		///
+		/// # <weight>
+		/// - Complexity: `O(W)` where `W` is the number of waiting proposals promoted
+		/// - Db reads and writes: `WaitingQueueHead`, `WaitingQueueTail`
+		/// - Db reads and writes per waiting proposal: `WaitingProposals`, `ProposalCount`,
+		///   `Proposals`
+		/// # </weight>
+		fn on_idle(n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			if Self::spend_period_phase(n) >= Self::allowed_proposal_period() {
+				return 0
+			}
+			let base = T::WeightInfo::on_initialize_waiting(0);
+			if remaining_weight < base {
+				return 0
+			}
+			let per_proposal = T::WeightInfo::on_initialize_waiting(1).saturating_sub(base);
+			let fit = (remaining_weight - base)
+				.checked_div(per_proposal)
+				.unwrap_or(Weight::MAX)
+				.min(ProposalIndex::MAX as Weight) as ProposalIndex;
+			let promoted = Self::promote_waiting(Self::waiting_queue_tail(), fit);
+			T::WeightInfo::on_initialize_waiting(promoted)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Put forward a suggestion for spending. A deposit proportional to the value
+		/// is reserved and slashed if the proposal is rejected. It is returned pro rata as the
+		/// proposal is awarded.
+		///
+		/// The value is paid out in `chunks` equal parts, one per spend period, with any remainder
+		/// of the division added to the final part. A `chunks` of zero is treated as a single
+		/// payout, and at most `T::MaxOccurrences` parts are allowed. Each part must be at least
+		/// the existential deposit.
+		///
+		/// A part is only paid if at least `interval` blocks have passed since the previous one;
+		/// otherwise it waits for a later spend period. An `interval` of zero pays a part every
+		/// spend period.
+		///
+		/// If `expires_at` is given, the proposal is no longer paid from that block on. Once an
+		/// approved proposal expires it is removed, and the bond still held is returned or slashed
+		/// according to `T::ExpiredBondPolicy`.
+		///
+		/// If a `description` hash is given, its preimage is requested from `T::Preimages` for as
+		/// long as the proposal exists.
+		///
+		/// # <weight>
+		/// - Complexity: O(1)
+		/// - DbReads: `ProposalCount`, `origin account`
+		/// - DbWrites: `ProposalCount`, `Proposals`, `ProposalDescriptions`, `origin account`
+		/// - Outside the allowed proposal period, `WaitingQueueTail`, `WaitingProposals` and
+		///   `WaitingProposalDescriptions` are written instead. Only the weight of the path taken
+		///   is charged.
+		/// # </weight>
+		#[pallet::weight(
+			T::WeightInfo::propose_spend().max(T::WeightInfo::propose_spend_waiting())
+		)]
+		#[transactional]
+		pub fn propose_spend(
+			origin: OriginFor<T>,
+			#[pallet::compact] value: BalanceOf<T, I>,
+			beneficiary: BeneficiaryLookupOf<T, I>,
+			chunks: u32,
+			interval: T::BlockNumber,
+			expires_at: Option<T::BlockNumber>,
+			description: Option<T::Hash>,
+		) -> DispatchResultWithPostInfo {
+			let proposer = ensure_signed(origin)?;
+			Self::ensure_allowed_proposer(&proposer)?;
+			Self::ensure_not_banned(&proposer)?;
+			Self::note_proposal_made(&proposer)?;
+			let beneficiary = T::BeneficiaryLookup::lookup(beneficiary)?;
+
+			let waiting = Self::do_propose_spend(
+				proposer,
+				value,
+				beneficiary,
+				Default::default(),
+				chunks,
+				None,
+				interval,
+				expires_at,
+				description,
+				false,
+			)?;
+			let weight = if waiting {
+				T::WeightInfo::propose_spend_waiting()
 			} else {
-				0
+				T::WeightInfo::propose_spend()
+			};
+			Ok(Some(weight).into())
+		}
+
+		/// Put forward a suggestion for spending without placing a deposit. Otherwise behaves
+		/// like `propose_spend`, and the proposal is rejected and awarded in the same way.
+		///
+		/// May only be called from `T::BondExemptOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(1)
+		/// - DbReads: `ProposalCount`
+		/// - DbWrites: `ProposalCount`, `Proposals`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::propose_spend_exempt())]
+		#[transactional]
+		pub fn propose_spend_exempt(
+			origin: OriginFor<T>,
+			#[pallet::compact] value: BalanceOf<T, I>,
+			beneficiary: BeneficiaryLookupOf<T, I>,
+			chunks: u32,
+			interval: T::BlockNumber,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			let proposer = T::BondExemptOrigin::ensure_origin(origin)?;
+			let beneficiary = T::BeneficiaryLookup::lookup(beneficiary)?;
+
+			Self::do_propose_spend(
+				proposer,
+				value,
+				beneficiary,
+				Default::default(),
+				chunks,
+				None,
+				interval,
+				expires_at,
+				None,
+				true,
+			)?;
+			Ok(())
+		}
+
+		/// Put forward a suggestion for spending that is paid out according to `amounts`, one
+		/// element per spend period, rather than in equal chunks. The deposit is computed on the
+		/// sum of the schedule and returned pro rata as the proposal is awarded.
+		///
+		/// Each amount must be at least the existential deposit.
+		///
+		/// # <weight>
+		/// - Complexity: O(S) where `S` is the length of `amounts`, at most `T::MaxOccurrences`.
+		/// - DbReads: `ProposalCount`, `origin account`
+		/// - DbWrites: `ProposalCount`, `Proposals`, `origin account`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::propose_spend_scheduled(amounts.len() as u32))]
+		#[transactional]
+		pub fn propose_spend_scheduled(
+			origin: OriginFor<T>,
+			amounts: BoundedVec<BalanceOf<T, I>, T::MaxOccurrences>,
+			beneficiary: BeneficiaryLookupOf<T, I>,
+		) -> DispatchResult {
+			let proposer = ensure_signed(origin)?;
+			Self::ensure_allowed_proposer(&proposer)?;
+			Self::ensure_not_banned(&proposer)?;
+			Self::note_proposal_made(&proposer)?;
+			let beneficiary = T::BeneficiaryLookup::lookup(beneficiary)?;
+
+			let value = amounts
+				.iter()
+				.fold(Zero::zero(), |total: BalanceOf<T, I>, amount| total.saturating_add(*amount));
+			Self::do_propose_spend(
+				proposer,
+				value,
+				beneficiary,
+				Default::default(),
+				0,
+				Some(amounts),
+				Zero::zero(),
+				None,
+				None,
+				false,
+			)?;
+			Ok(())
+		}
+
+		/// Reject a proposed spend. The part of the deposit that is still held will be slashed,
+		/// sharing `T::ReporterReward` of it with the reporter if the proposal was reported.
+		///
+		/// May only be called from `T::RejectOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(A) where `A` is the number of approvals.
+		/// - DbReads: `Proposals`, `Approvals`, `Reporters`, `rejected proposer account`
+		/// - DbWrites: `Proposals`, `Approvals`, `Reporters`, `rejected proposer account`
+		/// # </weight>
+		#[pallet::weight((T::WeightInfo::reject_proposal(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn reject_proposal(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: ProposalIndex,
+		) -> DispatchResult {
+			T::RejectOrigin::ensure_origin(origin)?;
+
+			Self::do_reject_proposal(proposal_id, Default::default())?;
+			Ok(())
+		}
+
+		/// Reject a proposed spend, giving a reason that is included in the `Rejected` event. The
+		/// part of the deposit that is still held will be slashed.
+		///
+		/// May only be called from `T::RejectOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(R) where `R` is the length of `reason`, at most `T::MaxReasonLength`.
+		/// - DbReads: `Proposals`, `Approvals`, `rejected proposer account`
+		/// - DbWrites: `Proposals`, `Approvals`, `rejected proposer account`
+		/// # </weight>
+		#[pallet::weight((
+			T::WeightInfo::reject_proposal_with_reason(reason.len() as u32),
+			DispatchClass::Operational
+		))]
+		#[transactional]
+		pub fn reject_proposal_with_reason(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: ProposalIndex,
+			reason: Vec<u8>,
+		) -> DispatchResult {
+			T::RejectOrigin::ensure_origin(origin)?;
+			let reason: BoundedVec<_, _> =
+				reason.try_into().map_err(|_| Error::<T, I>::ReasonTooLong)?;
+
+			Self::do_reject_proposal(proposal_id, reason)?;
+			Ok(())
+		}
+
+		/// Reject several proposed spends at once, slashing each original deposit. Indices
+		/// without a proposal are skipped and reported in the `ProposalsRejected` event.
+		///
+		/// May only be called from `T::RejectOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(P) where `P` is the number of proposals to reject.
+		/// - DbReads: `Proposals`, `Approvals`, `rejected proposer account` (per proposal)
+		/// - DbWrites: `Proposals`, `Approvals`, `rejected proposer account` (per proposal)
+		/// # </weight>
+		#[pallet::weight((
+			T::WeightInfo::reject_proposals(proposal_ids.len() as u32),
+			DispatchClass::Operational
+		))]
+		#[transactional]
+		pub fn reject_proposals(
+			origin: OriginFor<T>,
+			proposal_ids: BoundedVec<ProposalIndex, T::MaxApprovals>,
+		) -> DispatchResult {
+			T::RejectOrigin::ensure_origin(origin)?;
+
+			let mut rejected = 0u32;
+			let mut skipped = Vec::new();
+			for proposal_id in proposal_ids {
+				match Self::do_reject_proposal(proposal_id, Default::default()) {
+					Ok(_) => rejected += 1,
+					Err(_) => skipped.push(proposal_id),
+				}
+			}
+
+			Self::deposit_event(Event::<T, I>::ProposalsRejected(rejected, skipped));
+			Ok(())
+		}
+
+		/// Reject a proposal that is still waiting to be promoted. The original deposit will be
+		/// slashed.
+		///
+		/// May only be called from `T::RejectOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(1)
+		/// - DbReads: `WaitingProposals`, `WaitingQueueHead`, `rejected proposer account`
+		/// - DbWrites: `WaitingProposals`, `WaitingQueueHead`, `rejected proposer account`
+		/// # </weight>
+		#[pallet::weight((T::WeightInfo::reject_waiting_proposal(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn reject_waiting_proposal(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: ProposalIndex,
+		) -> DispatchResult {
+			T::RejectOrigin::ensure_origin(origin)?;
+
+			let proposal =
+				Self::remove_waiting_proposal(proposal_id).ok_or(Error::<T, I>::ProposalNotFound)?;
+			<WaitingStatuses<T, I>>::insert(proposal_id, ProposalStatus::Rejected);
+			let (slashed, refunded) = Self::slash_rejected_bond(proposal_id, &proposal, None);
+
+			Self::deposit_event(Event::<T, I>::Rejected(
+				proposal_id,
+				slashed,
+				refunded,
+				Default::default(),
+			));
+			Ok(())
+		}
+
+		/// Approve a proposal. At a later time, the proposal will be allocated to the beneficiary
+		/// and the original deposit will be returned.
+		///
+		/// If `T::EnsureAffordable` is set, the next award of the proposal must fit in the
+		/// uncommitted pot.
+		///
+		/// May only be called from `T::ApproveOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(A) where `A` is the number of approvals.
+		/// - DbReads: `Proposals`, `Approvals`
+		/// - DbWrite: `Approvals`
+		/// # </weight>
+		#[pallet::weight((T::WeightInfo::approve_proposal(T::MaxApprovals::get()), DispatchClass::Operational))]
+		#[transactional]
+		pub fn approve_proposal(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: ProposalIndex,
+		) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+
+			let proposal = Self::proposals(proposal_id).ok_or(Error::<T, I>::ProposalNotFound)?;
+			ensure!(!Self::approvals().contains(&proposal_id), Error::<T, I>::AlreadyApproved);
+			Self::ensure_affordable(&proposal, Self::uncommitted_pot())?;
+			Approvals::<T, I>::try_append(proposal_id)
+				.map_err(|_| Error::<T, I>::TooManyApprovals)?;
+			<Statuses<T, I>>::insert(proposal_id, Self::approved_status(&proposal));
+			Self::note_committed(proposal.remaining_value());
+			let now = frame_system::Pallet::<T>::block_number();
+			if let Some(proposal) = Self::note_approved(proposal_id, now) {
+				T::OnProposalLifecycle::on_approved(proposal_id, &proposal);
+			}
+
+			Self::deposit_event(Event::<T, I>::Approved(proposal_id));
+			Ok(())
+		}
+
+		/// Approve several proposals at once. Either all of them are queued or, if any of them
+		/// cannot be approved, none are.
+		///
+		/// May only be called from `T::ApproveOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(P * A) where `P` is the number of proposals to approve and `A` the
+		///   number of approvals.
+		/// - DbReads: `Proposals` (per proposal), `Approvals`
+		/// - DbWrite: `Approvals`
+		/// # </weight>
+		#[pallet::weight((
+			T::WeightInfo::approve_proposals(proposal_ids.len() as u32),
+			DispatchClass::Operational
+		))]
+		#[transactional]
+		pub fn approve_proposals(
+			origin: OriginFor<T>,
+			proposal_ids: BoundedVec<ProposalIndex, T::MaxApprovals>,
+		) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+
+			let uncommitted = Self::uncommitted_pot();
+			let committed = Approvals::<T, I>::try_mutate(|approvals| {
+				let mut committed = <BalanceOf<T, I>>::zero();
+				for &proposal_id in proposal_ids.iter() {
+					let proposal =
+						Self::proposals(proposal_id).ok_or(Error::<T, I>::ProposalNotFound)?;
+					ensure!(!approvals.contains(&proposal_id), Error::<T, I>::AlreadyApproved);
+					Self::ensure_affordable(&proposal, uncommitted.saturating_sub(committed))?;
+					approvals.try_push(proposal_id).map_err(|_| Error::<T, I>::TooManyApprovals)?;
+					<Statuses<T, I>>::insert(proposal_id, Self::approved_status(&proposal));
+					committed = committed.saturating_add(proposal.remaining_value());
+				}
+				Ok::<_, Error<T, I>>(committed)
+			})?;
+			Self::note_committed(committed);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			for proposal_id in proposal_ids {
+				if let Some(proposal) = Self::note_approved(proposal_id, now) {
+					T::OnProposalLifecycle::on_approved(proposal_id, &proposal);
+				}
+				Self::deposit_event(Event::<T, I>::Approved(proposal_id));
+			}
+			Ok(())
+		}
+
+		/// Amend the value, beneficiary and chunks of a proposal that has not been approved nor
+		/// paid in part. The proposer's deposit is adjusted to the new value. A proposal without a
+		/// deposit stays without one. A proposal with a custom schedule is turned into one paid in
+		/// equal chunks.
+		///
+		/// May only be called from `T::ApproveOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(A) where `A` is the number of approvals.
+		/// - DbReads: `Proposals`, `Approvals`, `proposer account`
+		/// - DbWrites: `Proposals`, `proposer account`
+		/// # </weight>
+		#[pallet::weight((T::WeightInfo::amend_proposal(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn amend_proposal(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: ProposalIndex,
+			#[pallet::compact] new_value: BalanceOf<T, I>,
+			new_beneficiary: BeneficiaryLookupOf<T, I>,
+			new_chunks: u32,
+		) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			let new_beneficiary = T::BeneficiaryLookup::lookup(new_beneficiary)?;
+			ensure!(!Self::is_pot(&new_beneficiary), Error::<T, I>::InvalidBeneficiary);
+			let new_chunks = new_chunks.max(1);
+			ensure!(new_chunks <= T::MaxOccurrences::get(), Error::<T, I>::TooManyOccurrences);
+
+			ensure!(!Self::approvals().contains(&proposal_id), Error::<T, I>::AlreadyApproved);
+			Self::ensure_chunk_size(new_value, new_chunks)?;
+			if let Some(max) = T::MaxProposalValue::get() {
+				ensure!(new_value <= max * Self::pot(), Error::<T, I>::ProposalTooLarge);
+			}
+
+			let old_value = Proposals::<T, I>::try_mutate(proposal_id, |maybe_proposal| {
+				let proposal = maybe_proposal.as_mut().ok_or(Error::<T, I>::ProposalNotFound)?;
+				// Amending resets the occurrences, which would pay the paid ones again.
+				ensure!(
+					proposal.remaining_occurs == proposal.occurs &&
+						proposal.partially_paid.is_zero(),
+					Error::<T, I>::PartlyPaid
+				);
+				let old_value = proposal.total_value;
+
+				let bond = if proposal.bond.is_zero() {
+					Zero::zero()
+				} else {
+					Self::calculate_bond(new_value)
+				};
+				if bond > proposal.remaining_bond {
+					Self::reserve_bond(&proposal, bond - proposal.remaining_bond)?;
+				} else {
+					Self::return_bond(
+						proposal_id,
+						proposal.proposer.as_ref(),
+						proposal.remaining_bond - bond,
+					);
+				}
+
+				proposal.total_value = new_value;
+				proposal.beneficiary = new_beneficiary;
+				proposal.bond = bond;
+				proposal.remaining_bond = bond;
+				proposal.occurs = new_chunks;
+				proposal.remaining_occurs = new_chunks;
+				proposal.schedule = None;
+				Ok::<_, Error<T, I>>(old_value)
+			})?;
+
+			Self::deposit_event(Event::<T, I>::Amended(proposal_id, old_value, new_value));
+			Ok(())
+		}
+
+		/// Extend a live proposal with `additional_occurs` further occurrences, paying
+		/// `additional_value` in equal chunks after the occurrences it already has. A zero
+		/// `additional_occurs` is treated as a single occurrence.
+		///
+		/// The proposer tops up the deposit to match the new total value. A proposal without a
+		/// deposit stays without one.
+		///
+		/// May only be called from `T::ApproveOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(M) where `M` is `T::MaxOccurrences`.
+		/// - DbReads: `Proposals`, `proposer account`
+		/// - DbWrites: `Proposals`, `proposer account`
+		/// # </weight>
+		#[pallet::weight((T::WeightInfo::extend_proposal(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn extend_proposal(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: ProposalIndex,
+			#[pallet::compact] additional_value: BalanceOf<T, I>,
+			additional_occurs: u32,
+		) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			let additional_occurs = additional_occurs.max(1);
+			Self::ensure_chunk_size(additional_value, additional_occurs)?;
+
+			Proposals::<T, I>::try_mutate(proposal_id, |maybe_proposal| {
+				let proposal = maybe_proposal.as_mut().ok_or(Error::<T, I>::ProposalNotFound)?;
+				let total_value = proposal.total_value.saturating_add(additional_value);
+				if let Some(max) = T::MaxProposalValue::get() {
+					ensure!(total_value <= max * Self::pot(), Error::<T, I>::ProposalTooLarge);
+				}
+
+				// Spell out every occurrence so that those already paid keep their amounts.
+				let chunk = additional_value / additional_occurs.into();
+				let last = additional_value.saturating_sub(chunk * (additional_occurs - 1).into());
+				let schedule: BoundedVec<_, T::MaxOccurrences> = (0..proposal.occurs)
+					.map(|occurrence| proposal.award_at(occurrence))
+					.chain((1..additional_occurs).map(|_| chunk))
+					.chain(sp_std::iter::once(last))
+					.collect::<Vec<_>>()
+					.try_into()
+					.map_err(|_| Error::<T, I>::TooManyOccurrences)?;
+
+				let extra_bond = if proposal.bond.is_zero() {
+					Zero::zero()
+				} else {
+					Self::calculate_bond(total_value).saturating_sub(proposal.bond)
+				};
+				Self::reserve_bond(&proposal, extra_bond)?;
+
+				proposal.total_value = total_value;
+				proposal.bond = proposal.bond.saturating_add(extra_bond);
+				proposal.remaining_bond = proposal.remaining_bond.saturating_add(extra_bond);
+				proposal.occurs = proposal.occurs.saturating_add(additional_occurs);
+				proposal.remaining_occurs =
+					proposal.remaining_occurs.saturating_add(additional_occurs);
+				proposal.schedule = Some(schedule);
+				Ok::<_, Error<T, I>>(())
+			})?;
+			if Self::approvals().contains(&proposal_id) {
+				Self::note_committed(additional_value);
+			}
+
+			Self::deposit_event(Event::<T, I>::Extended(
+				proposal_id,
+				additional_value,
+				additional_occurs,
+			));
+			Ok(())
+		}
+
+		/// Point a pending proposal at a new beneficiary.
+		///
+		/// The proposer may do so until the proposal is approved. `T::ApproveOrigin` may do so
+		/// at any time before the proposal is fully paid out, which also redirects the remaining
+		/// occurrences of a recurring proposal.
+		///
+		/// # <weight>
+		/// - Complexity: O(A) where `A` is the number of approvals.
+		/// - DbReads: `Proposals`, `Approvals`
+		/// - DbWrites: `Proposals`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::update_beneficiary())]
+		#[transactional]
+		pub fn update_beneficiary(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: ProposalIndex,
+			new_beneficiary: BeneficiaryLookupOf<T, I>,
+		) -> DispatchResult {
+			let maybe_proposer = T::ApproveOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some))?;
+			let new_beneficiary = T::BeneficiaryLookup::lookup(new_beneficiary)?;
+			ensure!(!Self::is_pot(&new_beneficiary), Error::<T, I>::InvalidBeneficiary);
+
+			let old_beneficiary = Proposals::<T, I>::try_mutate(proposal_id, |maybe_proposal| {
+				let proposal = maybe_proposal.as_mut().ok_or(Error::<T, I>::ProposalNotFound)?;
+				if let Some(who) = maybe_proposer {
+					ensure!(proposal.proposer.as_ref() == Some(&who), Error::<T, I>::NotProposer);
+					ensure!(
+						!Self::approvals().contains(&proposal_id),
+						Error::<T, I>::AlreadyApproved
+					);
+				}
+				Ok::<_, Error<T, I>>(sp_std::mem::replace(
+					&mut proposal.beneficiary,
+					new_beneficiary.clone(),
+				))
+			})?;
+
+			Self::deposit_event(Event::<T, I>::BeneficiaryUpdated(
+				proposal_id,
+				old_beneficiary,
+				new_beneficiary,
+			));
+			Ok(())
+		}
+
+		/// Remove an approved proposal from the spending queue. The proposal itself is kept, so it
+		/// can be approved again or rejected later.
+		///
+		/// May only be called from `T::RejectOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(A) where `A` is the number of approvals.
+		/// - DbReads: `Approvals`
+		/// - DbWrite: `Approvals`
+		/// # </weight>
+		#[pallet::weight((T::WeightInfo::remove_approval(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn remove_approval(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: ProposalIndex,
+		) -> DispatchResult {
+			T::RejectOrigin::ensure_origin(origin)?;
+
+			Approvals::<T, I>::try_mutate(|v| -> DispatchResult {
+				let position = v
+					.iter()
+					.position(|&index| index == proposal_id)
+					.ok_or(Error::<T, I>::NotInApprovalQueue)?;
+				v.remove(position);
+				Ok(())
+			})?;
+			Self::forget_approval(proposal_id);
+			if let Some(mut proposal) = Self::proposals(proposal_id) {
+				<Statuses<T, I>>::insert(proposal_id, ProposalStatus::Proposed);
+				Self::note_uncommitted(proposal.remaining_value());
+				proposal.approved_at = None;
+				<Proposals<T, I>>::insert(proposal_id, proposal);
+			}
+
+			Self::deposit_event(Event::<T, I>::ApprovalRemoved(proposal_id));
+			Ok(())
+		}
+
+		/// Move an approved proposal to `new_position` in the spending queue, which is paid in
+		/// order. Positions past the end of the queue move the proposal to the back.
+		///
+		/// Under `PayoutOrdering::SmallestFirst` the position only orders approvals with equal
+		/// awards.
+		///
+		/// The queue is ordered by priority and then by approval block, so the proposal's
+		/// `ApprovedAt` is adjusted to place it between its new neighbours. This also changes when
+		/// the approval lapses under `T::ApprovalTtlPeriods`. Fails with `CannotReprioritize` if
+		/// the new position is in another priority class, or if both neighbours were approved in
+		/// the same block and the proposal index does not fall between theirs.
+		///
+		/// May only be called from `T::ApproveOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(A) where `A` is the number of approvals.
+		/// - DbReads: `Approvals`, `ApprovedAt`
+		/// - DbWrite: `Approvals`, `ApprovedAt`
+		/// # </weight>
+		#[pallet::weight((
+			T::WeightInfo::reprioritize_approval(T::MaxApprovals::get()),
+			DispatchClass::Operational
+		))]
+		#[transactional]
+		pub fn reprioritize_approval(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: ProposalIndex,
+			#[pallet::compact] new_position: u32,
+		) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+
+			let (old_position, new_position) =
+				Approvals::<T, I>::try_mutate(|v| -> Result<_, DispatchError> {
+					Self::sort_approvals(v);
+					let old_position = v
+						.iter()
+						.position(|&index| index == proposal_id)
+						.ok_or(Error::<T, I>::NotInApprovalQueue)?;
+					v.remove(old_position);
+					let new_position = (new_position as usize).min(v.len());
+					let before = new_position.checked_sub(1).map(|i| Self::approval_key(v[i]));
+					let after = v.get(new_position).map(|&index| Self::approval_key(index));
+					let (class, current, _) = Self::approval_key(proposal_id);
+					let fits = |block: T::BlockNumber| {
+						before.map_or(true, |key| (class, block, proposal_id) > key) &&
+							after.map_or(true, |key| (class, block, proposal_id) < key)
+					};
+					let same_class = |key: &(_, _, _)| key.0 == class;
+					let block = if fits(current) {
+						current
+					} else {
+						match (before.filter(same_class), after.filter(same_class)) {
+							// The earliest block that keeps it behind the one before.
+							(Some((_, block, index)), _) if proposal_id > index => block,
+							(Some((_, block, _)), _) => block.saturating_add(One::one()),
+							// The latest block that keeps it ahead of the one after.
+							(None, Some((_, block, index))) if proposal_id < index => block,
+							(None, Some((_, block, _))) =>
+								block.checked_sub(&One::one()).unwrap_or(current),
+							(None, None) => current,
+						}
+					};
+					ensure!(fits(block), Error::<T, I>::CannotReprioritize);
+					v.try_insert(new_position, proposal_id)
+						.map_err(|_| Error::<T, I>::TooManyApprovals)?;
+					ApprovedAt::<T, I>::insert(proposal_id, block);
+					Ok((old_position as u32, new_position as u32))
+				})?;
+
+			Self::deposit_event(Event::<T, I>::ApprovalReprioritized(
+				proposal_id,
+				old_position,
+				new_position,
+			));
+			Ok(())
+		}
+
+		/// Set the priority class of an approved proposal. Approvals are paid in descending
+		/// priority, and in order of approval within a priority. The default priority is zero.
+		///
+		/// May only be called from `T::ApproveOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(A) where `A` is the number of approvals.
+		/// - DbReads: `Approvals`
+		/// - DbWrite: `ApprovalPriority`
+		/// # </weight>
+		#[pallet::weight((
+			T::WeightInfo::set_priority(T::MaxApprovals::get()),
+			DispatchClass::Operational
+		))]
+		pub fn set_priority(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: ProposalIndex,
+			priority: u8,
+		) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			ensure!(Self::approvals().contains(&proposal_id), Error::<T, I>::NotInApprovalQueue);
+
+			if priority.is_zero() {
+				<ApprovalPriority<T, I>>::remove(proposal_id);
+			} else {
+				<ApprovalPriority<T, I>>::insert(proposal_id, priority);
+			}
+
+			Self::deposit_event(Event::<T, I>::ApprovalPrioritySet(proposal_id, priority));
+			Ok(())
+		}
+
+		/// Veto an approved proposal before it is fully paid out, removing it from the spending
+		/// queue and from `Proposals`.
+		///
+		/// The bond still held for the proposal is slashed if `slash_bond` is set, and returned to
+		/// the proposer otherwise. For recurring proposals this is only the part of the bond that
+		/// has not yet been released by earlier payouts.
+		///
+		/// May only be called from `T::RejectOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(A) where `A` is the number of approvals.
+		/// - DbReads: `Approvals`, `Proposals`, `proposer account`
+		/// - DbWrites: `Approvals`, `Proposals`, `proposer account`
+		/// # </weight>
+		#[pallet::weight((
+			T::WeightInfo::veto_approved(T::MaxApprovals::get()),
+			DispatchClass::Operational
+		))]
+		#[transactional]
+		pub fn veto_approved(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: ProposalIndex,
+			slash_bond: bool,
+		) -> DispatchResult {
+			T::RejectOrigin::ensure_origin(origin)?;
+
+			Approvals::<T, I>::try_mutate(|v| -> DispatchResult {
+				let position = v
+					.iter()
+					.position(|&index| index == proposal_id)
+					.ok_or(Error::<T, I>::NotInApprovalQueue)?;
+				v.remove(position);
+				Ok(())
+			})?;
+			Self::forget_approval(proposal_id);
+			let proposal =
+				<Proposals<T, I>>::take(&proposal_id).ok_or(Error::<T, I>::ProposalNotFound)?;
+			<Reporters<T, I>>::remove(proposal_id);
+			Self::untrack_proposer(&proposal, proposal_id, false);
+			Self::clear_metadata(proposal_id);
+			Self::drop_description(proposal_id, false);
+			<Statuses<T, I>>::insert(proposal_id, ProposalStatus::Rejected);
+			Self::note_uncommitted(proposal.remaining_value());
+			T::OnProposalLifecycle::on_rejected(proposal_id, &proposal);
+
+			let bond = proposal.remaining_bond;
+			if slash_bond {
+				let imbalance = Self::slash_bond(&proposal, bond);
+				T::OnSlash::on_unbalanced(imbalance);
+			} else {
+				Self::return_bond(proposal_id, proposal.proposer.as_ref(), bond);
+			}
+
+			Self::deposit_event(Event::<T, I>::Vetoed(proposal_id, bond, slash_bond));
+			Ok(())
+		}
+
+		/// Put the payouts of a proposal on hold. A paused proposal stays in the spending queue
+		/// but is skipped by `spend_funds`, keeping its remaining occurrences for later.
+		///
+		/// May only be called from `T::ApproveOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `Proposals`
+		/// - DbWrites: `Proposals`
+		/// # </weight>
+		#[pallet::weight((T::WeightInfo::pause_proposal(), DispatchClass::Operational))]
+		pub fn pause_proposal(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: ProposalIndex,
+		) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+
+			Self::set_paused(proposal_id, true)?;
+
+			Self::deposit_event(Event::<T, I>::Paused(proposal_id));
+			Ok(())
+		}
+
+		/// Resume the payouts of a paused proposal from the next spend period.
+		///
+		/// May only be called from `T::ApproveOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `Proposals`
+		/// - DbWrites: `Proposals`
+		/// # </weight>
+		#[pallet::weight((T::WeightInfo::resume_proposal(), DispatchClass::Operational))]
+		pub fn resume_proposal(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: ProposalIndex,
+		) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+
+			Self::set_paused(proposal_id, false)?;
+
+			Self::deposit_event(Event::<T, I>::Resumed(proposal_id));
+			Ok(())
+		}
+
+		/// Set or clear the block from which a proposal is no longer paid.
+		///
+		/// May only be called from `T::ApproveOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `Proposals`
+		/// - DbWrites: `Proposals`
+		/// # </weight>
+		#[pallet::weight((T::WeightInfo::set_expiry(), DispatchClass::Operational))]
+		pub fn set_expiry(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: ProposalIndex,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+
+			Proposals::<T, I>::try_mutate(proposal_id, |maybe_proposal| {
+				let proposal = maybe_proposal.as_mut().ok_or(Error::<T, I>::ProposalNotFound)?;
+				proposal.expires_at = expires_at;
+				Ok::<_, Error<T, I>>(())
+			})?;
+
+			Self::deposit_event(Event::<T, I>::ExpirySet(proposal_id, expires_at));
+			Ok(())
+		}
+
+		/// Set whether each occurrence of a proposal must be acknowledged with
+		/// `confirm_milestone` before it is paid. Clearing it also discards any pending
+		/// confirmation.
+		///
+		/// May only be called from `T::ApproveOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `Proposals`
+		/// - DbWrites: `Proposals`
+		/// # </weight>
+		#[pallet::weight((T::WeightInfo::set_milestone_gated(), DispatchClass::Operational))]
+		pub fn set_milestone_gated(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: ProposalIndex,
+			gated: bool,
+		) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+
+			Proposals::<T, I>::try_mutate(proposal_id, |maybe_proposal| {
+				let proposal = maybe_proposal.as_mut().ok_or(Error::<T, I>::ProposalNotFound)?;
+				proposal.milestone_gated = gated;
+				proposal.milestone_confirmed = false;
+				Ok::<_, Error<T, I>>(())
+			})?;
+
+			Self::deposit_event(Event::<T, I>::MilestoneGatingSet(proposal_id, gated));
+			Ok(())
+		}
+
+		/// Confirm delivery of the next milestone of a gated proposal, allowing its next
+		/// occurrence to be paid.
+		///
+		/// May be called by the beneficiary of the proposal or from `T::ApproveOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `Proposals`
+		/// - DbWrites: `Proposals`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::confirm_milestone())]
+		pub fn confirm_milestone(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: ProposalIndex,
+		) -> DispatchResult {
+			let maybe_beneficiary = T::ApproveOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some))?;
+
+			Proposals::<T, I>::try_mutate(proposal_id, |maybe_proposal| {
+				let proposal = maybe_proposal.as_mut().ok_or(Error::<T, I>::ProposalNotFound)?;
+				if let Some(who) = maybe_beneficiary {
+					ensure!(
+						T::NativePaymaster::account_of(&proposal.beneficiary) == Some(who),
+						Error::<T, I>::NotBeneficiary
+					);
+				}
+				ensure!(proposal.milestone_gated, Error::<T, I>::NotMilestoneGated);
+				ensure!(!proposal.milestone_confirmed, Error::<T, I>::MilestoneAlreadyConfirmed);
+				proposal.milestone_confirmed = true;
+				Ok::<_, Error<T, I>>(())
+			})?;
+
+			Self::deposit_event(Event::<T, I>::MilestoneConfirmed(proposal_id));
+			Ok(())
+		}
+
+		/// Propose and approve a spend of treasury funds in one go. No deposit is taken.
+		///
+		/// The spend is queued in `Approvals` and paid to the beneficiary at the next spend
+		/// period, like any other approved proposal.
+		/// It has no proposer, so it does not count towards anybody's `T::MaxPendingPerAccount`
+		/// open proposals and cannot be withdrawn.
+		///
+		/// May only be called from `T::SpendOrigin`, for at most the amount it permits.
+		///
+		/// # <weight>
+		/// - Complexity: O(1)
+		/// - DbReads: `ProposalCount`, `Approvals`
+		/// - DbWrites: `ProposalCount`, `Approvals`, `Proposals`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::spend())]
+		#[transactional]
+		pub fn spend(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: BalanceOf<T, I>,
+			beneficiary: BeneficiaryLookupOf<T, I>,
+		) -> DispatchResult {
+			let max_amount = T::SpendOrigin::ensure_origin(origin)?;
+			let beneficiary = T::BeneficiaryLookup::lookup(beneficiary)?;
+
+			ensure!(amount <= max_amount, Error::<T, I>::InsufficientPermission);
+
+			Self::do_spend(amount, beneficiary)?;
+			Ok(())
+		}
+
+		/// Donate `amount` from the caller into the pot. The caller's account is kept alive.
+		///
+		/// # <weight>
+		/// - Complexity: O(1)
+		/// - DbReads: `origin account`, `pot account`, `TotalPendingPayouts`, `Deactivated`
+		/// - DbWrites: `origin account`, `pot account`, `Deactivated`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::fund())]
+		#[transactional]
+		pub fn fund(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: BalanceOf<T, I>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T, I>::ZeroAmount);
+
+			T::Currency::transfer(&who, &Self::account_id(), amount, KeepAlive)?;
+			Self::update_deactivated();
+
+			Self::deposit_event(Event::<T, I>::Funded(who, amount));
+			Ok(())
+		}
+
+		/// Run a spend round immediately, outside of the regular `T::SpendPeriod` schedule.
+		///
+		/// Approvals are paid, overdue waiting proposals are promoted and the surplus is burnt
+		/// exactly as in a scheduled round. The next scheduled round is not affected.
+		///
+		/// May only be called from root.
+		///
+		/// # <weight>
+		/// - Same as `on_initialize` in a spend period; the actual weight is refunded.
+		/// # </weight>
+		#[pallet::weight((
+			T::WeightInfo::on_initialize_proposals(
+				T::MaxApprovals::get(),
+				T::MaxWaitingProposals::get()
+			),
+			DispatchClass::Operational
+		))]
+		#[transactional]
+		pub fn force_spend(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			let weight = Self::spend_funds(frame_system::Pallet::<T>::block_number());
+			Ok(Some(weight).into())
+		}
+
+		/// Override the percentage of spare funds burnt per spend period. Passing `None` restores
+		/// `T::Burn`. Takes effect from the next spend period.
+		///
+		/// May only be called from `T::AdminOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(1)
+		/// - DbWrites: `BurnOverride`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::set_burn())]
+		pub fn set_burn(origin: OriginFor<T>, burn: Option<Permill>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			<BurnOverride<T, I>>::set(burn);
+
+			Self::deposit_event(Event::<T, I>::BurnSet(burn));
+			Ok(())
+		}
+
+		/// Replace the accounts that receive a share of each burn. Shares are fractions of the
+		/// burnt amount and may add up to at most 100%; whatever is left goes to
+		/// `T::BurnDestination`. If the shares add up to exactly 100%, rounding dust goes to the
+		/// first destination.
+		///
+		/// May only be called from `T::AdminOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(D) where `D` is the number of destinations.
+		/// - DbWrites: `BurnDestinations`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::set_burn_destinations(destinations.len() as u32))]
+		pub fn set_burn_destinations(
+			origin: OriginFor<T>,
+			destinations: BoundedVec<(T::AccountId, Permill), T::MaxBurnDestinations>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				Self::total_burn_share(&destinations) <= Permill::one().deconstruct(),
+				Error::<T, I>::InvalidBurnShares
+			);
+
+			<BurnDestinations<T, I>>::put(destinations);
+
+			Self::deposit_event(Event::<T, I>::BurnDestinationsSet);
+			Ok(())
+		}
+
+		/// Override the number of blocks at the start of each spend period during which new
+		/// proposals go straight into `Proposals` rather than `WaitingProposals`. Passing `None`
+		/// restores `T::AllowedProposalPeriod`.
+		///
+		/// May only be called from `T::AdminOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(1)
+		/// - DbWrites: `AllowedProposalPeriodOverride`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::set_allowed_proposal_period())]
+		pub fn set_allowed_proposal_period(
+			origin: OriginFor<T>,
+			blocks: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			if let Some(blocks) = blocks {
+				ensure!(blocks <= T::SpendPeriod::get(), Error::<T, I>::InvalidProposalPeriod);
+			}
+
+			<AllowedProposalPeriodOverride<T, I>>::set(blocks);
+
+			Self::deposit_event(Event::<T, I>::AllowedProposalPeriodSet(blocks));
+			Ok(())
+		}
+
+		/// Allow `who` to make proposals. Once any account has been added, only approved
+		/// proposers may call `propose_spend`.
+		///
+		/// May only be called from `T::ProposerManagementOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(1)
+		/// - DbReads: `ApprovedProposers`, `ApprovedProposerCount`
+		/// - DbWrites: `ApprovedProposers`, `ApprovedProposerCount`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::add_proposer())]
+		pub fn add_proposer(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::ProposerManagementOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			ensure!(
+				!<ApprovedProposers<T, I>>::contains_key(&who),
+				Error::<T, I>::AlreadyApprovedProposer
+			);
+			let count = Self::approved_proposer_count();
+			ensure!(count < T::MaxProposers::get(), Error::<T, I>::TooManyProposers);
+
+			<ApprovedProposers<T, I>>::insert(&who, ());
+			<ApprovedProposerCount<T, I>>::put(count + 1);
+
+			Self::deposit_event(Event::<T, I>::ProposerAdded(who));
+			Ok(())
+		}
+
+		/// Stop `who` from making proposals. Removing the last approved proposer opens
+		/// `propose_spend` to every signed account again.
+		///
+		/// May only be called from `T::ProposerManagementOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(1)
+		/// - DbReads: `ApprovedProposers`, `ApprovedProposerCount`
+		/// - DbWrites: `ApprovedProposers`, `ApprovedProposerCount`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::remove_proposer())]
+		pub fn remove_proposer(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::ProposerManagementOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			<ApprovedProposers<T, I>>::take(&who).ok_or(Error::<T, I>::NotApprovedProposer)?;
+			<ApprovedProposerCount<T, I>>::mutate(|count| *count = count.saturating_sub(1));
+
+			Self::deposit_event(Event::<T, I>::ProposerRemoved(who));
+			Ok(())
+		}
+
+		/// Stop `who` from making proposals until block `until`, or for good if `None`. Any ban
+		/// already in place is replaced. Proposals already made are not affected.
+		///
+		/// May only be called from `T::RejectOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(1)
+		/// - DbReads: none
+		/// - DbWrites: `BannedProposers`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::ban_proposer())]
+		pub fn ban_proposer(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			until: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			T::RejectOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			if let Some(until) = until {
+				let now = frame_system::Pallet::<T>::block_number();
+				ensure!(until > now, Error::<T, I>::BanAlreadyExpired);
+			}
+
+			<BannedProposers<T, I>>::insert(&who, until);
+
+			Self::deposit_event(Event::<T, I>::ProposerBanned(who, until));
+			Ok(())
+		}
+
+		/// Lift the ban on `who`, expired or not.
+		///
+		/// May only be called from `T::RejectOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(1)
+		/// - DbReads: `BannedProposers`
+		/// - DbWrites: `BannedProposers`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::unban_proposer())]
+		pub fn unban_proposer(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::RejectOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			<BannedProposers<T, I>>::take(&who).ok_or(Error::<T, I>::NotBanned)?;
+
+			Self::deposit_event(Event::<T, I>::ProposerUnbanned(who));
+			Ok(())
+		}
+
+		/// Withdraw a proposal that has not been approved. The deposit is returned, minus the
+		/// `T::ProposalWithdrawalFee` share which is slashed.
+		///
+		/// May only be called by the proposer.
+		///
+		/// # <weight>
+		/// - Complexity: O(A) where `A` is the number of approvals.
+		/// - DbReads: `Proposals`, `Approvals`, `proposer account`
+		/// - DbWrites: `Proposals`, `proposer account`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::withdraw_proposal())]
+		#[transactional]
+		pub fn withdraw_proposal(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: ProposalIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let proposal = Self::proposals(proposal_id).ok_or(Error::<T, I>::ProposalNotFound)?;
+			ensure!(proposal.proposer.as_ref() == Some(&who), Error::<T, I>::NotProposer);
+			ensure!(!Self::approvals().contains(&proposal_id), Error::<T, I>::AlreadyApproved);
+
+			<Proposals<T, I>>::remove(proposal_id);
+			<Reporters<T, I>>::remove(proposal_id);
+			Self::untrack_proposer(&proposal, proposal_id, false);
+			Self::clear_metadata(proposal_id);
+			Self::drop_description(proposal_id, false);
+			<Statuses<T, I>>::insert(proposal_id, ProposalStatus::Withdrawn);
+			let refunded = Self::refund_withdrawn_bond(proposal_id, &proposal);
+
+			Self::deposit_event(Event::<T, I>::Withdrawn(proposal_id, refunded));
+			Ok(())
+		}
+
+		/// Cancel a proposal that is still waiting to be promoted. Nothing has been decided on it
+		/// yet, so the deposit is returned in full.
+		///
+		/// May only be called by the proposer.
+		///
+		/// # <weight>
+		/// - Complexity: O(1)
+		/// - DbReads: `WaitingProposals`, `WaitingQueueHead`, `proposer account`
+		/// - DbWrites: `WaitingProposals`, `WaitingQueueHead`, `proposer account`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::cancel_waiting())]
+		#[transactional]
+		pub fn cancel_waiting(
+			origin: OriginFor<T>,
+			#[pallet::compact] index: ProposalIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let proposal = Self::waiting_proposals(index).ok_or(Error::<T, I>::ProposalNotFound)?;
+			ensure!(proposal.proposer.as_ref() == Some(&who), Error::<T, I>::NotProposer);
+
+			Self::remove_waiting_proposal(index);
+			<WaitingStatuses<T, I>>::insert(index, ProposalStatus::Withdrawn);
+			Self::return_bond(index, proposal.proposer.as_ref(), proposal.remaining_bond);
+
+			Self::deposit_event(Event::<T, I>::WaitingProposalCancelled(
+				index,
+				proposal.remaining_bond,
+			));
+			Ok(())
+		}
+
+		/// Claim the awards that are pending for the caller. If the treasury cannot currently
+		/// cover all of them, as much as possible is paid and the rest stays pending.
+		///
+		/// # <weight>
+		/// - Complexity: O(1)
+		/// - DbReads: `PendingPayouts`, `TotalPendingPayouts`, `treasury account`, `caller account`
+		/// - DbWrites: `PendingPayouts`, `TotalPendingPayouts`, `treasury account`,
+		///   `caller account`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::claim_payout())]
+		#[transactional]
+		pub fn claim_payout(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let pending = Self::pending_payouts(&who);
+			ensure!(!pending.is_zero(), Error::<T, I>::NoPendingPayout);
+
+			let account_id = Self::account_id();
+			let available = T::Currency::free_balance(&account_id)
+				.saturating_sub(T::Currency::minimum_balance());
+			let amount = pending.min(available);
+			ensure!(!amount.is_zero(), Error::<T, I>::InsufficientPot);
+
+			T::Currency::transfer(&account_id, &who, amount, KeepAlive)?;
+
+			let remaining = pending.saturating_sub(amount);
+			if remaining.is_zero() {
+				<PendingPayouts<T, I>>::remove(&who);
+			} else {
+				<PendingPayouts<T, I>>::insert(&who, remaining);
+			}
+			<TotalPendingPayouts<T, I>>::mutate(|total| *total = total.saturating_sub(amount));
+
+			Self::deposit_event(Event::<T, I>::PayoutClaimed(who, amount));
+			Ok(())
+		}
+
+		/// Approve a spend of `amount` of `asset_kind` to `beneficiary`, to be paid out through
+		/// `T::Paymaster` with `payout_asset`. The native token proposals are not affected.
+		///
+		/// May only be called from `T::SpendAssetOrigin`, for at most the amount it permits.
+		///
+		/// # <weight>
+		/// - Complexity: O(1)
+		/// - DbReads: `AssetSpendCount`
+		/// - DbWrites: `AssetSpendCount`, `AssetSpends`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::spend_asset())]
+		#[transactional]
+		pub fn spend_asset(
+			origin: OriginFor<T>,
+			asset_kind: AssetKindOf<T, I>,
+			amount: PayBalanceOf<T, I>,
+			beneficiary: PayBeneficiaryOf<T, I>,
+		) -> DispatchResult {
+			let max_amount = T::SpendAssetOrigin::ensure_origin(origin)?;
+			ensure!(!amount.is_zero(), Error::<T, I>::ZeroAmount);
+			ensure!(amount <= max_amount, Error::<T, I>::InsufficientPermission);
+
+			let index = Self::asset_spend_count();
+			let spend = AssetSpend {
+				asset_kind: asset_kind.clone(),
+				amount,
+				beneficiary: beneficiary.clone(),
+				status: PaymentState::Pending,
+			};
+			<AssetSpends<T, I>>::insert(index, spend);
+			<AssetSpendCount<T, I>>::put(index + 1);
+
+			Self::deposit_event(Event::<T, I>::AssetSpendApproved(
+				index,
+				asset_kind,
+				amount,
+				beneficiary,
+			));
+			Ok(())
+		}
+
+		/// Pay out the asset spend at `index` through `T::Paymaster`, or pay it out again if
+		/// its payment failed. The outcome of the payment is settled with `check_status`.
+		///
+		/// May be called by any signed origin.
+		///
+		/// # <weight>
+		/// - Complexity: O(1)
+		/// - DbReads: `AssetSpends`
+		/// - DbWrites: `AssetSpends`
+		/// - Plus whatever `T::Paymaster` reads and writes to make the payment
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::payout_asset())]
+		#[transactional]
+		pub fn payout_asset(
+			origin: OriginFor<T>,
+			#[pallet::compact] index: AssetSpendIndex,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let id = <AssetSpends<T, I>>::try_mutate(index, |maybe_spend| {
+				let spend = maybe_spend.as_mut().ok_or(Error::<T, I>::AssetSpendNotFound)?;
+				ensure!(
+					!matches!(spend.status, PaymentState::Attempted { .. }),
+					Error::<T, I>::AlreadyAttempted
+				);
+				let id =
+					T::Paymaster::pay(&spend.beneficiary, spend.asset_kind.clone(), spend.amount)
+						.map_err(|_| Error::<T, I>::PayoutError)?;
+				spend.status = PaymentState::Attempted { id };
+				Ok::<_, Error<T, I>>(id)
+			})?;
+
+			Self::deposit_event(Event::<T, I>::AssetPaid(index, id));
+			Ok(())
+		}
+
+		/// Settle the payment of the asset spend at `index`. A successful payment removes the
+		/// spend; a failed one leaves it to be paid out again. The call is free if the payment
+		/// has concluded.
+		///
+		/// May be called by any signed origin.
+		///
+		/// # <weight>
+		/// - Complexity: O(1)
+		/// - DbReads: `AssetSpends`
+		/// - DbWrites: `AssetSpends`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::check_status())]
+		#[transactional]
+		pub fn check_status(
+			origin: OriginFor<T>,
+			#[pallet::compact] index: AssetSpendIndex,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let mut spend = Self::asset_spends(index).ok_or(Error::<T, I>::AssetSpendNotFound)?;
+			let id = match spend.status {
+				PaymentState::Attempted { id } => id,
+				PaymentState::Pending | PaymentState::Failed =>
+					return Err(Error::<T, I>::NotAttempted.into()),
+			};
+
+			match T::Paymaster::check_payment(id) {
+				PaymentStatus::Success => {
+					<AssetSpends<T, I>>::remove(index);
+					Self::deposit_event(Event::<T, I>::AssetSpendProcessed(index));
+				},
+				PaymentStatus::Failure => {
+					spend.status = PaymentState::Failed;
+					<AssetSpends<T, I>>::insert(index, spend);
+					Self::deposit_event(Event::<T, I>::AssetPaymentFailed(index, id));
+				},
+				PaymentStatus::InProgress | PaymentStatus::Unknown =>
+					return Err(Error::<T, I>::Inconclusive.into()),
+			}
+			Ok(Pays::No.into())
+		}
+
+		/// Set or clear the number of blocks over which each award of a proposal is released from
+		/// a lock. Awards are only vested when paid straight away into a local account.
+		///
+		/// May only be called from `T::ApproveOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `Proposals`
+		/// - DbWrites: `Proposals`
+		/// # </weight>
+		#[pallet::weight((T::WeightInfo::set_vesting_period(), DispatchClass::Operational))]
+		pub fn set_vesting_period(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: ProposalIndex,
+			vesting_period: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			ensure!(
+				vesting_period.map_or(true, |period| !period.is_zero()),
+				Error::<T, I>::ZeroVestingPeriod
+			);
+
+			Proposals::<T, I>::try_mutate(proposal_id, |maybe_proposal| {
+				let proposal = maybe_proposal.as_mut().ok_or(Error::<T, I>::ProposalNotFound)?;
+				proposal.vesting_period = vesting_period;
+				Ok::<_, Error<T, I>>(())
+			})?;
+
+			Self::deposit_event(Event::<T, I>::VestingPeriodSet(proposal_id, vesting_period));
+			Ok(())
+		}
+
+		/// Put forward a suggestion for spending that is shared between several beneficiaries,
+		/// each receiving their share of every award. Shares may add up to at most 100%; the
+		/// first beneficiary receives whatever the others are not given. Otherwise behaves like
+		/// `propose_spend`, and the deposit is computed on the whole value.
+		///
+		/// Changing the beneficiary of a split proposal only replaces the first one.
+		///
+		/// # <weight>
+		/// - Complexity: O(B) where `B` is the number of beneficiaries, at most
+		///   `T::MaxBeneficiaries`.
+		/// - DbReads: `ProposalCount`, `origin account`
+		/// - DbWrites: `ProposalCount`, `Proposals`, `origin account`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::propose_spend_split(beneficiaries.len() as u32))]
+		#[transactional]
+		pub fn propose_spend_split(
+			origin: OriginFor<T>,
+			#[pallet::compact] value: BalanceOf<T, I>,
+			beneficiaries: BoundedVec<(BeneficiaryLookupOf<T, I>, Permill), T::MaxBeneficiaries>,
+			chunks: u32,
+		) -> DispatchResult {
+			let proposer = ensure_signed(origin)?;
+			Self::ensure_allowed_proposer(&proposer)?;
+			Self::ensure_not_banned(&proposer)?;
+			Self::note_proposal_made(&proposer)?;
+			let total_share = beneficiaries
+				.iter()
+				.fold(0u32, |total, (_, share)| total.saturating_add(share.deconstruct()));
+			ensure!(total_share <= Permill::one().deconstruct(), Error::<T, I>::InvalidSplitShares);
+
+			let mut beneficiaries = beneficiaries
+				.into_iter()
+				.map(|(who, share)| Ok((T::BeneficiaryLookup::lookup(who)?, share)))
+				.collect::<Result<Vec<_>, DispatchError>>()?;
+			ensure!(!beneficiaries.is_empty(), Error::<T, I>::NoBeneficiaries);
+			let (beneficiary, _) = beneficiaries.remove(0);
+			// Always fits, as it holds one fewer than `beneficiaries`.
+			let splits = beneficiaries.try_into().unwrap_or_default();
+
+			Self::do_propose_spend(
+				proposer,
+				value,
+				beneficiary,
+				splits,
+				chunks,
+				None,
+				Zero::zero(),
+				None,
+				None,
+				false,
+			)?;
+			Ok(())
+		}
+
+		/// Set the metadata of a proposal, usually the hash or URI of its description. Empty
+		/// metadata removes it. Waiting proposals get metadata once they are promoted.
+		///
+		/// The proposer may do so until the proposal is approved, holding a deposit of
+		/// `T::MetadataDepositBase` plus `T::MetadataDepositPerByte` per byte. `T::ApproveOrigin`
+		/// may do so at any time without a deposit. Any deposit held for the previous metadata is
+		/// returned, as is the deposit once the proposal is removed.
+		///
+		/// # <weight>
+		/// - Complexity: O(A + M) where `A` is the number of approvals and `M` the length of
+		///   `metadata`, at most `T::MaxMetadataLen`.
+		/// - DbReads: `Proposals`, `Approvals`, `MetadataDeposits`, `origin account`
+		/// - DbWrites: `ProposalMetadata`, `MetadataDeposits`, `origin account`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::set_metadata())]
+		#[transactional]
+		pub fn set_metadata(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: ProposalIndex,
+			metadata: Vec<u8>,
+		) -> DispatchResult {
+			let maybe_proposer = T::ApproveOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some))?;
+			let metadata: BoundedVec<u8, T::MaxMetadataLen> =
+				metadata.try_into().map_err(|_| Error::<T, I>::MetadataTooLong)?;
+
+			let proposal = Self::proposals(proposal_id).ok_or(Error::<T, I>::ProposalNotFound)?;
+			if let Some(who) = &maybe_proposer {
+				ensure!(proposal.proposer.as_ref() == Some(who), Error::<T, I>::NotProposer);
+				ensure!(!Self::approvals().contains(&proposal_id), Error::<T, I>::AlreadyApproved);
+			}
+
+			let deposit = match maybe_proposer {
+				Some(who) if !metadata.is_empty() => {
+					let per_byte = T::MetadataDepositPerByte::get();
+					let deposit = T::MetadataDepositBase::get()
+						.saturating_add(per_byte.saturating_mul((metadata.len() as u32).into()));
+					T::Currency::reserve(&who, deposit)
+						.map_err(|_| Error::<T, I>::InsufficientProposersBalance)?;
+					Some((who, deposit))
+				},
+				_ => None,
+			};
+			Self::clear_metadata(proposal_id);
+			if !metadata.is_empty() {
+				<ProposalMetadata<T, I>>::insert(proposal_id, metadata);
+				if let Some(deposit) = deposit {
+					<MetadataDeposits<T, I>>::insert(proposal_id, deposit);
+				}
+				Self::deposit_event(Event::<T, I>::MetadataSet(proposal_id));
+			}
+			Ok(())
+		}
+
+		/// Report a proposal as one to reject. If `T::RejectOrigin` later rejects it with
+		/// `reject_proposal`, the reporter is paid `T::ReporterReward` of the slashed bond.
+		///
+		/// Only the first report of a proposal counts, and proposers may not report their own.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `Proposals`, `Reporters`
+		/// - DbWrites: `Reporters`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::report_proposal())]
+		pub fn report_proposal(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: ProposalIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let proposal = Self::proposals(proposal_id).ok_or(Error::<T, I>::ProposalNotFound)?;
+			ensure!(proposal.proposer.as_ref() != Some(&who), Error::<T, I>::ReporterIsProposer);
+			ensure!(!<Reporters<T, I>>::contains_key(proposal_id), Error::<T, I>::AlreadyReported);
+
+			<Reporters<T, I>>::insert(proposal_id, &who);
+			Self::deposit_event(Event::<T, I>::ProposalReported(proposal_id, who));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	// Add public immutables and private mutables.
+
+	/// The account ID of the treasury pot.
+	///
+	/// This actually does computation. If you need to keep using it, then make sure you cache the
+	/// value and only call this once.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	/// Whether `beneficiary` is the treasury account itself.
+	fn is_pot(beneficiary: &T::Beneficiary) -> bool {
+		T::NativePaymaster::account_of(beneficiary).map_or(false, |who| who == Self::account_id())
+	}
+
+	/// Create a proposal, either in `Proposals` or, outside of the allowed proposal period, in
+	/// `WaitingProposals`. No deposit is reserved if `bond_exempt` is set. The preimage of the
+	/// `description` hash, if any, is requested until the proposal is removed. Returns whether
+	/// the proposal is waiting.
+	///
+	/// The value is paid in `chunks` equal parts unless a `schedule` is given, in which case
+	/// `value` must be its sum and `chunks` is ignored. Each award is shared with the `splits`.
+	fn do_propose_spend(
+		proposer: T::AccountId,
+		value: BalanceOf<T, I>,
+		beneficiary: T::Beneficiary,
+		splits: BoundedVec<(T::Beneficiary, Permill), T::MaxBeneficiaries>,
+		chunks: u32,
+		schedule: Option<BoundedVec<BalanceOf<T, I>, T::MaxOccurrences>>,
+		interval: T::BlockNumber,
+		expires_at: Option<T::BlockNumber>,
+		description: Option<T::Hash>,
+		bond_exempt: bool,
+	) -> Result<bool, DispatchError> {
+		ensure!(
+			!Self::is_pot(&beneficiary) && !splits.iter().any(|(who, _)| Self::is_pot(who)),
+			Error::<T, I>::InvalidBeneficiary
+		);
+		if let Some(max) = T::MaxProposalValue::get() {
+			ensure!(value <= max * Self::pot(), Error::<T, I>::ProposalTooLarge);
+		}
+		let chunks = match &schedule {
+			Some(amounts) => {
+				ensure!(!amounts.is_empty(), Error::<T, I>::EmptySchedule);
+				let min = T::Currency::minimum_balance();
+				ensure!(
+					amounts.iter().all(|amount| !amount.is_zero() && *amount >= min),
+					Error::<T, I>::ChunkTooSmall
+				);
+				amounts.len() as u32
+			},
+			None => {
+				// A value below the existential deposit would be lost when paid to a new account.
+				ensure!(
+					!value.is_zero() && value >= T::Currency::minimum_balance(),
+					Error::<T, I>::InvalidValue
+				);
+				// A proposal is always paid at least once.
+				let chunks = chunks.max(1);
+				ensure!(chunks <= T::MaxOccurrences::get(), Error::<T, I>::TooManyOccurrences);
+				Self::ensure_chunk_size(value, chunks)?;
+				chunks
+			},
+		};
+
+		let current_block = <frame_system::Pallet<T>>::block_number();
+		let waiting = Self::spend_period_phase(current_block) >= Self::allowed_proposal_period();
+		ensure!(
+			Self::pending_by_proposer(&proposer) < T::MaxPendingPerAccount::get(),
+			Error::<T, I>::TooManyPendingProposals
+		);
+
+		let bond = if bond_exempt { Zero::zero() } else { Self::calculate_bond(value) };
+		if !bond_exempt {
+			T::Currency::reserve(&proposer, bond)
+				.map_err(|_| Error::<T, I>::InsufficientProposersBalance)?;
+		}
+		Self::note_pending(&proposer);
+
+		let self_proposal = T::FlagSelfProposals::get() &&
+			T::NativePaymaster::account_of(&beneficiary).as_ref() == Some(&proposer);
+		let proposal = Proposal {
+			proposer: Some(proposer.clone()),
+			total_value: value,
+			beneficiary,
+			bond,
+			remaining_bond: bond,
+			occurs: chunks,
+			remaining_occurs: chunks,
+			interval,
+			last_paid_at: None,
+			paused: false,
+			schedule,
+			expires_at,
+			milestone_gated: false,
+			milestone_confirmed: false,
+			partially_paid: Zero::zero(),
+			vesting_period: None,
+			splits,
+			proposed_at: current_block,
+			approved_at: None,
+		};
+
+		let index = if !waiting {
+			let c_proposals = Self::proposal_count();
+			<ProposalCount<T, I>>::put(c_proposals + 1);
+			Self::deposit_event(Event::Proposed {
+				proposal_index: c_proposals,
+				proposer: proposal.proposer.clone(),
+				beneficiary: proposal.beneficiary.clone(),
+				value: proposal.total_value,
+				chunk: proposal.next_award(),
+				occurs: proposal.occurs,
+				bond: proposal.bond,
+			});
+			Self::index_proposal(&proposer, c_proposals, false);
+			<Proposals<T, I>>::insert(c_proposals, proposal);
+			<Statuses<T, I>>::insert(c_proposals, ProposalStatus::Proposed);
+			c_proposals
+		} else {
+			// Callers are transactional: a full queue also releases the bond reserved above.
+			ensure!(
+				Self::waiting_proposal_count() < T::MaxWaitingProposals::get(),
+				Error::<T, I>::TooManyWaitingProposals
+			);
+			let position = Self::waiting_queue_tail();
+			<WaitingQueueTail<T, I>>::put(position + 1);
+			Self::deposit_event(Event::WaitingProposed {
+				proposal_index: position,
+				proposer: proposal.proposer.clone(),
+				beneficiary: proposal.beneficiary.clone(),
+				value: proposal.total_value,
+				chunk: proposal.next_award(),
+				occurs: proposal.occurs,
+				bond: proposal.bond,
+			});
+			Self::index_proposal(&proposer, position, true);
+			<WaitingProposals<T, I>>::insert(position, proposal);
+			<WaitingStatuses<T, I>>::insert(position, ProposalStatus::Waiting);
+			position
+		};
+		if bond_exempt {
+			Self::deposit_event(Event::BondExempt(index));
+		}
+		if self_proposal {
+			Self::deposit_event(Event::SelfProposal(index));
+		}
+		if let Some(hash) = description {
+			T::Preimages::request(&hash);
+			if waiting {
+				<WaitingProposalDescriptions<T, I>>::insert(index, hash);
+			} else {
+				<ProposalDescriptions<T, I>>::insert(index, hash);
+			}
+			Self::deposit_event(Event::DescriptionRequested(index, hash));
+		}
+		Ok(waiting)
+	}
+
+	/// Ensure that paying `value` in `chunks` equal parts pays at least the existential deposit
+	/// each time.
+	fn ensure_chunk_size(value: BalanceOf<T, I>, chunks: u32) -> Result<(), Error<T, I>> {
+		let chunk = value / chunks.into();
+		ensure!(chunk >= T::Currency::minimum_balance(), Error::<T, I>::ChunkTooSmall);
+		Ok(())
+	}
+
+	/// Ensure that `amount` may be spent to `beneficiary` without a proposal, as `spend` and
+	/// `TreasurySpender` do.
+	fn ensure_spendable(
+		amount: BalanceOf<T, I>,
+		beneficiary: &T::Beneficiary,
+	) -> Result<(), Error<T, I>> {
+		ensure!(!Self::is_pot(beneficiary), Error::<T, I>::InvalidBeneficiary);
+		Self::ensure_chunk_size(amount, 1)
+	}
+
+	/// Ensure that `who` may make proposals: anyone may while `ApprovedProposers` is empty,
+	/// otherwise only its members.
+	fn ensure_allowed_proposer(who: &T::AccountId) -> Result<(), Error<T, I>> {
+		ensure!(
+			Self::approved_proposer_count() == 0 || <ApprovedProposers<T, I>>::contains_key(who),
+			Error::<T, I>::NotAllowedToPropose
+		);
+		Ok(())
+	}
+
+	/// Ensure that `who` is not banned from making proposals, removing their ban if it has
+	/// expired.
+	fn ensure_not_banned(who: &T::AccountId) -> Result<(), Error<T, I>> {
+		match Self::banned_until(who) {
+			None => Ok(()),
+			Some(Some(until)) if until <= frame_system::Pallet::<T>::block_number() => {
+				<BannedProposers<T, I>>::remove(who);
+				Ok(())
+			},
+			Some(_) => Err(Error::<T, I>::ProposerBanned),
+		}
+	}
+
+	/// Ensure that `T::ProposalCooldown` has passed since `who` last made a proposal, and restart
+	/// it from now.
+	fn note_proposal_made(who: &T::AccountId) -> Result<(), Error<T, I>> {
+		let now = frame_system::Pallet::<T>::block_number();
+		if let Some(last) = Self::last_proposal_block(who) {
+			ensure!(
+				now >= last.saturating_add(T::ProposalCooldown::get()),
+				Error::<T, I>::CooldownActive
+			);
+		}
+		<LastProposalBlock<T, I>>::insert(who, now);
+		Ok(())
+	}
+
+	/// The needed bond for a proposal whose spend is `value`.
+	fn calculate_bond(value: BalanceOf<T, I>) -> BalanceOf<T, I> {
+		let mut r = T::ProposalBondMinimum::get().max(T::ProposalBond::get() * value);
+		if let Some(m) = T::ProposalBondMaximum::get() {
+			r = r.min(m);
+		}
+		r
+	}
+
+	/// Propose and approve a spend of `amount` to `beneficiary` without a bond. It has no
+	/// proposer, so it is not counted against anybody's pending proposals. Returns the index of
+	/// the proposal.
+	fn do_spend(
+		amount: BalanceOf<T, I>,
+		beneficiary: T::Beneficiary,
+	) -> Result<ProposalIndex, DispatchError> {
+		Self::ensure_spendable(amount, &beneficiary)?;
+
+		let proposal_index = Self::proposal_count();
+		Approvals::<T, I>::try_append(proposal_index)
+			.map_err(|_| Error::<T, I>::TooManyApprovals)?;
+		let now = frame_system::Pallet::<T>::block_number();
+		<ApprovedAt<T, I>>::insert(proposal_index, now);
+		let proposal = Proposal {
+			proposer: None,
+			total_value: amount,
+			beneficiary: beneficiary.clone(),
+			bond: Zero::zero(),
+			remaining_bond: Zero::zero(),
+			occurs: 1,
+			remaining_occurs: 1,
+			interval: Zero::zero(),
+			last_paid_at: None,
+			paused: false,
+			schedule: None,
+			expires_at: None,
+			milestone_gated: false,
+			milestone_confirmed: false,
+			partially_paid: Zero::zero(),
+			vesting_period: None,
+			splits: Default::default(),
+			proposed_at: now,
+			approved_at: Some(now),
+		};
+		Self::note_committed(amount);
+		T::OnProposalLifecycle::on_approved(proposal_index, &proposal);
+		<Proposals<T, I>>::insert(proposal_index, proposal);
+		<Statuses<T, I>>::insert(proposal_index, ProposalStatus::Approved);
+		<ProposalCount<T, I>>::put(proposal_index + 1);
+
+		Self::deposit_event(Event::<T, I>::SpendApproved(proposal_index, amount, beneficiary));
+		Ok(proposal_index)
+	}
+
+	/// Remove a proposal, taking it out of `Approvals` if it is there, and slash the part of its
+	/// deposit that is still held. Returns the slashed amount.
+	fn do_reject_proposal(
+		proposal_id: ProposalIndex,
+		reason: BoundedVec<u8, T::MaxReasonLength>,
+	) -> Result<BalanceOf<T, I>, Error<T, I>> {
+		let proposal =
+			<Proposals<T, I>>::take(&proposal_id).ok_or(Error::<T, I>::ProposalNotFound)?;
+		Self::untrack_proposer(&proposal, proposal_id, false);
+		Self::clear_metadata(proposal_id);
+		Self::drop_description(proposal_id, false);
+		<Statuses<T, I>>::insert(proposal_id, ProposalStatus::Rejected);
+		if let Some(position) = Self::approvals().iter().position(|&index| index == proposal_id) {
+			Approvals::<T, I>::mutate(|v| {
+				v.remove(position);
+			});
+			Self::forget_approval(proposal_id);
+			Self::note_uncommitted(proposal.remaining_value());
+		}
+		let reporter = <Reporters<T, I>>::take(proposal_id);
+		let (slashed, refunded) = Self::slash_rejected_bond(proposal_id, &proposal, reporter);
+		T::OnProposalLifecycle::on_rejected(proposal_id, &proposal);
+
+		Self::deposit_event(Event::<T, I>::Rejected(proposal_id, slashed, refunded, reason));
+		Ok(slashed)
+	}
+
+	/// Slash `T::RejectionSlashRatio` of the bond still held for a rejected proposal and return
+	/// the rest to the proposer. `T::ReporterReward` of the slash goes to `reporter`, if any.
+	/// Returns the slashed and refunded amounts.
+	fn slash_rejected_bond(
+		index: ProposalIndex,
+		proposal: &ProposalOf<T, I>,
+		reporter: Option<T::AccountId>,
+	) -> (BalanceOf<T, I>, BalanceOf<T, I>) {
+		let slashed = T::RejectionSlashRatio::get() * proposal.remaining_bond;
+		let mut imbalance = Self::slash_bond(proposal, slashed);
+		if let Some(reporter) = reporter {
+			let reward = T::ReporterReward::get() * imbalance.peek();
+			if !reward.is_zero() {
+				let (reward, remainder) = imbalance.split(reward);
+				let paid = reward.peek();
+				// A reporter whose account is gone forfeits the reward.
+				imbalance = match T::Currency::resolve_into_existing(&reporter, reward) {
+					Ok(()) => {
+						Self::deposit_event(Event::ReporterRewarded(
+							index,
+							reporter,
+							paid,
+							remainder.peek(),
+						));
+						remainder
+					},
+					Err(reward) => remainder.merge(reward),
+				};
 			}
 		}
+		T::OnSlash::on_unbalanced(imbalance);
+
+		let refunded = proposal.remaining_bond.saturating_sub(slashed);
+		Self::return_bond(index, proposal.proposer.as_ref(), refunded);
+		(slashed, refunded)
 	}
 
-	#[pallet::call]
-	impl<T: Config<I>, I: 'static> Pallet<T, I> {
-		/// Put forward a suggestion for spending. A deposit proportional to the value
-		/// is reserved and slashed if the proposal is rejected. It is returned once the
-		/// proposal is awarded.
-		///
-		/// # <weight>
-		/// - Complexity: O(1)
-		/// - DbReads: `ProposalCount`, `origin account`
-		/// - DbWrites: `ProposalCount`, `Proposals`, `origin account`
-		/// # </weight>
-		#[pallet::weight(T::WeightInfo::propose_spend())]
-		pub fn propose_spend(
-			origin: OriginFor<T>,
-			#[pallet::compact] value: BalanceOf<T, I>,
-			beneficiary: <T::Lookup as StaticLookup>::Source,
-			chunks: u32,
-		) -> DispatchResult {
-			let proposer = ensure_signed(origin)?;
-			let beneficiary = T::Lookup::lookup(beneficiary)?;
+	/// Reserve `amount` more of the proposer's balance for the bond of `proposal`. A spend has no
+	/// proposer and never needs a bond.
+	fn reserve_bond(
+		proposal: &ProposalOf<T, I>,
+		amount: BalanceOf<T, I>,
+	) -> Result<(), Error<T, I>> {
+		match &proposal.proposer {
+			Some(proposer) => T::Currency::reserve(proposer, amount)
+				.map_err(|_| Error::<T, I>::InsufficientProposersBalance),
+			None => Ok(()),
+		}
+	}
 
-			let current_block = <frame_system::Pallet<T>>::block_number();
+	/// Slash `amount` of the bond held for `proposal`.
+	fn slash_bond(
+		proposal: &ProposalOf<T, I>,
+		amount: BalanceOf<T, I>,
+	) -> NegativeImbalanceOf<T, I> {
+		match &proposal.proposer {
+			Some(proposer) => T::Currency::slash_reserved(proposer, amount).0,
+			None => Zero::zero(),
+		}
+	}
 
-			if (current_block % T::SpendPeriod::get()).lt(&T::AllowedProposalPeriod::get()) {
-				let chunk: <<T as Config<I>>::Currency as Currency<
-					<T as frame_system::Config>::AccountId,
-				>>::Balance;
-				if chunks.gt(&0) {
-					chunk = value / chunks.into();
-				} else {
-					chunk = value;
-				}
+	/// Count a new entry in `Proposals` or `WaitingProposals` for `who`.
+	fn note_pending(who: &T::AccountId) {
+		<PendingByProposer<T, I>>::mutate(who, |count| *count = count.saturating_add(1));
+	}
 
-				let bond = Self::calculate_bond(value);
-				T::Currency::reserve(&proposer, bond)
-					.map_err(|_| Error::<T, I>::InsufficientProposersBalance)?;
+	/// Stop counting an entry removed from `Proposals` or `WaitingProposals` for `who`.
+	fn note_not_pending(who: &T::AccountId) {
+		<PendingByProposer<T, I>>::mutate_exists(who, |maybe_count| {
+			*maybe_count = maybe_count
+				.map(|count| count.saturating_sub(1))
+				.filter(|count| !count.is_zero())
+		});
+	}
 
-				let c_proposals = Self::proposal_count();
-				<ProposalCount<T, I>>::put(c_proposals + 1);
-				<Proposals<T, I>>::insert(
-					c_proposals,
-					Proposal {
-						proposer: proposer.clone(),
-						value: chunk.clone(),
-						beneficiary: beneficiary.clone(),
-						bond,
-						occurs: chunks,
-						remaining_occurs: chunks,
-					},
-				);
+	/// If `T::EnsureAffordable` is set, ensure that the next award of `proposal` does not exceed
+	/// `uncommitted`.
+	fn ensure_affordable(
+		proposal: &ProposalOf<T, I>,
+		uncommitted: BalanceOf<T, I>,
+	) -> Result<(), Error<T, I>> {
+		ensure!(
+			!T::EnsureAffordable::get() || proposal.next_award() <= uncommitted,
+			Error::<T, I>::InsufficientFunds
+		);
+		Ok(())
+	}
 
-				Self::deposit_event(Event::Proposed(c_proposals));
-			} else {
-				let chunk: <<T as Config<I>>::Currency as Currency<
-					<T as frame_system::Config>::AccountId,
-				>>::Balance;
-				if chunks.gt(&0) {
-					chunk = value / chunks.into();
-				} else {
-					chunk = value;
-				}
-				let bond = Self::calculate_bond(value);
-				T::Currency::reserve(&proposer, bond)
-					.map_err(|_| Error::<T, I>::InsufficientProposersBalance)?;
-
-				let w_proposals = Self::waiting_proposal_count();
-				<WaitingProposalCount<T, I>>::put(w_proposals + 1);
-				<WaitingProposals<T, I>>::insert(
-					w_proposals,
-					Proposal {
-						proposer: proposer.clone(),
-						value: chunk.clone(),
-						beneficiary: beneficiary.clone(),
-						bond,
-						occurs: chunks,
-						remaining_occurs: chunks,
-					},
-				);
+	/// Add `amount` to the amount committed to approved proposals.
+	fn note_committed(amount: BalanceOf<T, I>) {
+		<Committed<T, I>>::mutate(|committed| *committed = committed.saturating_add(amount));
+	}
+
+	/// Remove `amount` from the amount committed to approved proposals.
+	fn note_uncommitted(amount: BalanceOf<T, I>) {
+		<Committed<T, I>>::mutate(|committed| *committed = committed.saturating_sub(amount));
+	}
+
+	/// List `index` of `Proposals`, or of `WaitingProposals` if `waiting`, for `who`.
+	fn index_proposal(who: &T::AccountId, index: ProposalIndex, waiting: bool) {
+		// Never full: `who` has fewer than `T::MaxPendingPerAccount` pending entries.
+		let push = |entries: &mut BoundedVec<ProposalIndex, T::MaxPendingPerAccount>| {
+			let _ = entries.try_push(index);
+		};
+		if waiting {
+			<WaitingProposalsByProposer<T, I>>::mutate(who, push)
+		} else {
+			<ProposalsByProposer<T, I>>::mutate(who, push)
+		}
+	}
 
-				Self::deposit_event(Event::WaitingProposed(w_proposals));
+	/// Stop listing `index` of `Proposals`, or of `WaitingProposals` if `waiting`, for `who`.
+	fn unindex_proposal(who: &T::AccountId, index: ProposalIndex, waiting: bool) {
+		let remove = |maybe_entries: &mut Option<BoundedVec<_, _>>| {
+			if let Some(entries) = maybe_entries {
+				entries.retain(|&entry| entry != index);
+				if entries.is_empty() {
+					*maybe_entries = None;
+				}
 			}
-			Ok(())
+		};
+		if waiting {
+			<WaitingProposalsByProposer<T, I>>::mutate_exists(who, remove)
+		} else {
+			<ProposalsByProposer<T, I>>::mutate_exists(who, remove)
 		}
+	}
 
-		/// Reject a proposed spend. The original deposit will be slashed.
-		///
-		/// May only be called from `T::RejectOrigin`.
-		///
-		/// # <weight>
-		/// - Complexity: O(1)
-		/// - DbReads: `Proposals`, `rejected proposer account`
-		/// - DbWrites: `Proposals`, `rejected proposer account`
-		/// # </weight>
-		#[pallet::weight((T::WeightInfo::reject_proposal(), DispatchClass::Operational))]
-		pub fn reject_proposal(
-			origin: OriginFor<T>,
-			#[pallet::compact] proposal_id: ProposalIndex,
-		) -> DispatchResult {
-			T::RejectOrigin::ensure_origin(origin)?;
+	/// Stop counting the entry at `index` of `Proposals`, or of `WaitingProposals` if `waiting`,
+	/// as pending for the proposer of `proposal` and stop listing it for them.
+	fn untrack_proposer(proposal: &ProposalOf<T, I>, index: ProposalIndex, waiting: bool) {
+		if let Some(proposer) = &proposal.proposer {
+			Self::note_not_pending(proposer);
+			Self::unindex_proposal(proposer, index, waiting);
+		}
+	}
 
-			let proposal =
-				<Proposals<T, I>>::take(&proposal_id).ok_or(Error::<T, I>::InvalidIndex)?;
-			let value = proposal.bond;
-			let imbalance = T::Currency::slash_reserved(&proposal.proposer, value).0;
-			T::OnSlash::on_unbalanced(imbalance);
+	/// Remove the metadata of a proposal, returning its deposit.
+	fn clear_metadata(index: ProposalIndex) {
+		if <ProposalMetadata<T, I>>::take(index).is_none() {
+			return
+		}
+		if let Some((who, deposit)) = <MetadataDeposits<T, I>>::take(index) {
+			T::Currency::unreserve(&who, deposit);
+		}
+		Self::deposit_event(Event::MetadataCleared(index));
+	}
 
-			Self::deposit_event(Event::<T, I>::Rejected(proposal_id, value));
-			Ok(())
+	/// Stop requesting the preimage of the description of a proposal that is being removed from
+	/// `Proposals`, or from `WaitingProposals` if `waiting`.
+	fn drop_description(index: ProposalIndex, waiting: bool) {
+		let hash = if waiting {
+			<WaitingProposalDescriptions<T, I>>::take(index)
+		} else {
+			<ProposalDescriptions<T, I>>::take(index)
+		};
+		if let Some(hash) = hash {
+			T::Preimages::unrequest(&hash);
+			Self::deposit_event(Event::DescriptionDropped(index, hash));
 		}
+	}
 
-		/// Approve a proposal. At a later time, the proposal will be allocated to the beneficiary
-		/// and the original deposit will be returned.
-		///
-		/// May only be called from `T::ApproveOrigin`.
-		///
-		/// # <weight>
-		/// - Complexity: O(1).
-		/// - DbReads: `Proposals`, `Approvals`
-		/// - DbWrite: `Approvals`
-		/// # </weight>
-		#[pallet::weight((T::WeightInfo::approve_proposal(T::MaxApprovals::get()), DispatchClass::Operational))]
-		pub fn approve_proposal(
-			origin: OriginFor<T>,
-			#[pallet::compact] proposal_id: ProposalIndex,
-		) -> DispatchResult {
-			T::ApproveOrigin::ensure_origin(origin)?;
+	/// Remove a waiting proposal. Removing the entry at the head of the waiting queue advances
+	/// the head; any other removal leaves a gap that is skipped on promotion. The tail never
+	/// moves back, so a waiting index is never given out twice.
+	fn remove_waiting_proposal(index: ProposalIndex) -> Option<ProposalOf<T, I>> {
+		let proposal = <WaitingProposals<T, I>>::take(index)?;
+		Self::untrack_proposer(&proposal, index, true);
+		Self::drop_description(index, true);
+		<WaitingStatuses<T, I>>::remove(index);
+		if index == Self::waiting_queue_head() {
+			<WaitingQueueHead<T, I>>::put(index.saturating_add(1));
+		}
+		Some(proposal)
+	}
 
-			ensure!(<Proposals<T, I>>::contains_key(proposal_id), Error::<T, I>::InvalidIndex);
-			Approvals::<T, I>::try_append(proposal_id)
-				.map_err(|_| Error::<T, I>::TooManyApprovals)?;
+	/// Return the bond still held for a withdrawn proposal, slashing the withdrawal fee. Returns
+	/// the amount given back to the proposer.
+	fn refund_withdrawn_bond(index: ProposalIndex, proposal: &ProposalOf<T, I>) -> BalanceOf<T, I> {
+		let fee = T::ProposalWithdrawalFee::get() * proposal.remaining_bond;
+		let imbalance = Self::slash_bond(proposal, fee);
+		T::OnSlash::on_unbalanced(imbalance);
 
-			Ok(())
+		let refunded = proposal.remaining_bond.saturating_sub(fee);
+		Self::return_bond(index, proposal.proposer.as_ref(), refunded);
+		refunded
+	}
+
+	/// Unreserve `amount` of the bond held for the proposal at `index`, emitting `BondReturned`
+	/// with whatever could not be unreserved. Nothing is emitted for a zero amount, nor for a
+	/// spend, which has no proposer and holds no bond.
+	fn return_bond(index: ProposalIndex, proposer: Option<&T::AccountId>, amount: BalanceOf<T, I>) {
+		let proposer = match proposer {
+			Some(proposer) if !amount.is_zero() => proposer,
+			_ => return,
+		};
+		let shortfall = T::Currency::unreserve(proposer, amount);
+		Self::deposit_event(Event::BondReturned(
+			index,
+			proposer.clone(),
+			amount.saturating_sub(shortfall),
+			shortfall,
+		));
+	}
+
+	/// Remove an approved proposal that has expired, returning or slashing the bond still held
+	/// according to `T::ExpiredBondPolicy`. The caller takes it out of `Approvals`.
+	fn expire_proposal(
+		index: ProposalIndex,
+		proposal: &ProposalOf<T, I>,
+		calls: &mut Vec<LifecycleCall<T, I>>,
+	) {
+		Self::remove_approved_proposal(index, proposal, calls);
+
+		match T::ExpiredBondPolicy::get() {
+			ExpiredBondPolicy::Return => {
+				Self::return_bond(index, proposal.proposer.as_ref(), proposal.remaining_bond);
+			},
+			ExpiredBondPolicy::Slash => {
+				let imbalance = Self::slash_bond(proposal, proposal.remaining_bond);
+				T::OnSlash::on_unbalanced(imbalance);
+			},
 		}
+
+		Self::deposit_event(Event::ProposalExpired(index, proposal.remaining_occurs));
 	}
-}
 
-impl<T: Config<I>, I: 'static> Pallet<T, I> {
-	// Add public immutables and private mutables.
+	/// Whether the approval of `index` is older than `T::ApprovalTtlPeriods` spend periods at
+	/// block `now`.
+	fn approval_lapsed(index: ProposalIndex, now: T::BlockNumber) -> bool {
+		match (T::ApprovalTtlPeriods::get(), Self::approved_at(index)) {
+			(Some(periods), Some(approved_at)) => {
+				let ttl = T::SpendPeriod::get().saturating_mul(periods.into());
+				now.saturating_sub(approved_at) >= ttl
+			},
+			_ => false,
+		}
+	}
 
-	/// The account ID of the treasury pot.
-	///
-	/// This actually does computation. If you need to keep using it, then make sure you cache the
-	/// value and only call this once.
-	pub fn account_id() -> T::AccountId {
-		T::PalletId::get().into_account()
+	/// Remove an approved proposal that could not be paid in time, returning the bond still
+	/// held. The caller takes it out of `Approvals`.
+	fn lapse_approval(
+		index: ProposalIndex,
+		proposal: &ProposalOf<T, I>,
+		calls: &mut Vec<LifecycleCall<T, I>>,
+	) {
+		Self::remove_approved_proposal(index, proposal, calls);
+		Self::return_bond(index, proposal.proposer.as_ref(), proposal.remaining_bond);
+		Self::deposit_event(Event::ApprovalLapsed(index));
 	}
 
-	/// The needed bond for a proposal whose spend is `value`.
-	fn calculate_bond(value: BalanceOf<T, I>) -> BalanceOf<T, I> {
-		T::ProposalBondMinimum::get().max(T::ProposalBond::get() * value)
+	/// Remove an approved proposal from storage and stop counting it as pending and committed,
+	/// adding its rejection to the lifecycle `calls` to make.
+	fn remove_approved_proposal(
+		index: ProposalIndex,
+		proposal: &ProposalOf<T, I>,
+		calls: &mut Vec<LifecycleCall<T, I>>,
+	) {
+		<Proposals<T, I>>::remove(index);
+		<Reporters<T, I>>::remove(index);
+		Self::forget_approval(index);
+		Self::untrack_proposer(&proposal, index, false);
+		Self::clear_metadata(index);
+		Self::drop_description(index, false);
+		<Statuses<T, I>>::insert(index, ProposalStatus::Rejected);
+		Self::note_uncommitted(proposal.remaining_value());
+		calls.push(LifecycleCall::Rejected(index, proposal.clone()));
+	}
+
+	/// Set whether the payouts of a proposal are on hold, failing if nothing would change.
+	fn set_paused(proposal_id: ProposalIndex, paused: bool) -> DispatchResult {
+		Proposals::<T, I>::try_mutate(proposal_id, |maybe_proposal| {
+			let proposal = maybe_proposal.as_mut().ok_or(Error::<T, I>::ProposalNotFound)?;
+			match (proposal.paused, paused) {
+				(true, true) => Err(Error::<T, I>::AlreadyPaused.into()),
+				(false, false) => Err(Error::<T, I>::NotPaused.into()),
+				_ => {
+					proposal.paused = paused;
+					Ok(())
+				},
+			}
+		})
+	}
+
+	/// The position of an approved proposal in the spending queue: its priority, highest first,
+	/// then its approval block, then its index.
+	fn approval_key(index: ProposalIndex) -> (Reverse<u8>, T::BlockNumber, ProposalIndex) {
+		(
+			Reverse(Self::approval_priority(index)),
+			Self::approved_at(index).unwrap_or_else(Zero::zero),
+			index,
+		)
+	}
+
+	/// Forget when and with what priority a proposal that is leaving `Approvals` was approved.
+	fn forget_approval(index: ProposalIndex) {
+		<ApprovedAt<T, I>>::remove(index);
+		<ApprovalPriority<T, I>>::remove(index);
+	}
+
+	/// Record `now` as the block at which the proposal at `index` was approved, returning the
+	/// updated proposal.
+	fn note_approved(index: ProposalIndex, now: T::BlockNumber) -> Option<ProposalOf<T, I>> {
+		<ApprovedAt<T, I>>::insert(index, now);
+		<Proposals<T, I>>::mutate(index, |maybe_proposal| {
+			maybe_proposal.as_mut().map(|proposal| {
+				proposal.approved_at = Some(now);
+				proposal.clone()
+			})
+		})
+	}
+
+	/// The status of `proposal` while it is in `Approvals`.
+	fn approved_status(proposal: &ProposalOf<T, I>) -> ProposalStatus {
+		match proposal.occurs.saturating_sub(proposal.remaining_occurs) {
+			0 => ProposalStatus::Approved,
+			paid_occurrences => ProposalStatus::PartiallyPaid { paid_occurrences },
+		}
+	}
+
+	/// Put the approvals into the order in which they were approved, within each priority.
+	fn sort_approvals(approvals: &mut BoundedVec<ProposalIndex, T::MaxApprovals>) {
+		approvals.as_mut().sort_by_cached_key(|&index| Self::approval_key(index));
+	}
+
+	/// Put the approvals into the order in which they are paid under `T::PayoutOrdering`.
+	fn sort_for_payout(approvals: &mut BoundedVec<ProposalIndex, T::MaxApprovals>) {
+		match T::PayoutOrdering::get() {
+			PayoutOrdering::Fifo => Self::sort_approvals(approvals),
+			PayoutOrdering::SmallestFirst => approvals.as_mut().sort_by_cached_key(|&index| {
+				let (priority, approved_at, index) = Self::approval_key(index);
+				let award = Self::proposals(index).map(|p| p.outstanding_award());
+				(priority, award, approved_at, index)
+			}),
+		}
+	}
+
+	/// Deliver `amount` to `beneficiary` as `T::PayoutMode` requires, adding whatever is paid
+	/// straight away to `imbalance`. Beneficiaries without a local account are always paid
+	/// straight away, as they cannot claim.
+	fn pay_beneficiary(
+		beneficiary: &T::Beneficiary,
+		amount: BalanceOf<T, I>,
+		imbalance: &mut PositiveImbalanceOf<T, I>,
+	) {
+		match (T::PayoutMode::get(), T::NativePaymaster::account_of(beneficiary)) {
+			(PayoutMode::Pull, Some(who)) => {
+				<PendingPayouts<T, I>>::mutate(who, |pending| {
+					*pending = pending.saturating_add(amount)
+				});
+				<TotalPendingPayouts<T, I>>::mutate(|total| *total = total.saturating_add(amount));
+			},
+			_ => T::NativePaymaster::pay(beneficiary, amount, imbalance),
+		}
+	}
+
+	/// Lock `amount` just paid to `beneficiary` for the proposal at `index` if it has a vesting
+	/// period. Nothing is locked when it was left to be claimed or went to a beneficiary without a
+	/// local account.
+	fn vest_award(
+		index: ProposalIndex,
+		proposal: &ProposalOf<T, I>,
+		beneficiary: &T::Beneficiary,
+		amount: BalanceOf<T, I>,
+		now: T::BlockNumber,
+	) {
+		let period = match proposal.vesting_period {
+			Some(period) if T::PayoutMode::get() != PayoutMode::Pull => period,
+			_ => return,
+		};
+		let who = match T::NativePaymaster::account_of(beneficiary) {
+			Some(who) => who,
+			None => return,
+		};
+		match T::VestingHandler::vest(&who, amount, period, now) {
+			Ok(()) => Self::deposit_event(Event::AwardVested(index, who, amount, now, period)),
+			Err(_) => Self::deposit_event(Event::VestingFailed(index)),
+		}
+	}
+
+	/// Add the `shares` of an award to what each beneficiary has been `awarded` so far.
+	fn note_awarded(
+		awarded: &mut BTreeMap<T::Beneficiary, BalanceOf<T, I>>,
+		shares: &[(T::Beneficiary, BalanceOf<T, I>)],
+	) {
+		for (who, share) in shares {
+			let total = awarded.entry(who.clone()).or_insert_with(Zero::zero);
+			*total = total.saturating_add(*share);
+		}
 	}
 
 	/// Spend some money! returns number of approvals before spend.
-	pub fn spend_funds() -> Weight {
+	pub fn spend_funds(now: T::BlockNumber) -> Weight {
 		let mut total_weight: Weight = Zero::zero();
 
-		let mut budget_remaining = Self::pot();
+		let pot = Self::pot();
+		let mut budget_remaining = pot;
 		Self::deposit_event(Event::Spending(budget_remaining));
 		let account_id = Self::account_id();
 
 		let mut missed_any = false;
+		let mut missed_approvals = 0u32;
 		let mut imbalance = <PositiveImbalanceOf<T, I>>::zero();
+		// What each beneficiary has been awarded so far in this run.
+		let beneficiary_cap = T::MaxPerBeneficiaryPerPeriod::get();
+		let mut awarded = BTreeMap::<T::Beneficiary, BalanceOf<T, I>>::new();
+		let (mut awarded_any, mut deferred_any) = (false, false);
+		// What has been awarded in this run, against `T::MaxSpendPerPeriod`.
+		let period_cap = T::MaxSpendPerPeriod::get();
+		let mut spent = <BalanceOf<T, I>>::zero();
+		let mut period_cap_reached = false;
+		let smallest_first = T::PayoutOrdering::get() == PayoutOrdering::SmallestFirst;
+		let allow_partial = T::AllowPartialPayouts::get();
+		let mut lifecycle_calls = Vec::new();
 		let proposals_len = Approvals::<T, I>::mutate(|v| {
 			let proposals_approvals_len = v.len() as u32;
+			Self::sort_for_payout(v);
 			v.retain(|&index| {
 				// Should always be true, but shouldn't panic if false or we're screwed.
 				if let Some(mut p) = Self::proposals(index) {
-					if p.value <= budget_remaining {
-						budget_remaining -= p.value;
+					if p.is_expired(now) {
+						Self::expire_proposal(index, &p, &mut lifecycle_calls);
+						return false
+					}
+					// A proposal that is paused or not due yet is simply left for a later spend
+					// period; it does not count as missed.
+					if p.paused || !p.is_due(now) {
+						return true
+					}
+					// Likewise for a gated proposal whose next milestone is not confirmed yet.
+					if p.milestone_gated && !p.milestone_confirmed {
+						Self::deposit_event(Event::AwaitingMilestone(index));
+						return true
+					}
+					let award = p.outstanding_award();
+					let shares = p.shares_of(award);
+					// The first award to a beneficiary is never deferred, so that awards larger
+					// than the cap are still paid eventually.
+					let over_cap = beneficiary_cap.and_then(|cap| {
+						shares
+							.iter()
+							.find(|(who, share)| {
+								let already_awarded =
+									awarded.get(who).copied().unwrap_or_else(Zero::zero);
+								!already_awarded.is_zero() &&
+									already_awarded.saturating_add(*share) > cap
+							})
+							.map(|(who, _)| who.clone())
+					});
+					// As above, the first award of the run is never held back by the period cap.
+					let over_period_cap = period_cap
+						.map_or(false, |cap| !spent.is_zero() && spent.saturating_add(award) > cap);
+					if let Some(who) = over_cap {
+						deferred_any = true;
+						Self::deposit_event(Event::PayoutDeferred(index, who));
+						true
+					} else if over_period_cap {
+						// Not a miss: the surplus may still be burnt.
+						period_cap_reached = true;
+						true
+					} else if award <= budget_remaining {
+						budget_remaining = budget_remaining.saturating_sub(award);
+						Self::note_awarded(&mut awarded, &shares);
+						awarded_any = true;
+						spent = spent.saturating_add(award);
+
+						// return their deposit pro rata.
+						let release = p.next_bond_release();
+						p.remaining_bond = p.remaining_bond.saturating_sub(release);
+						Self::return_bond(index, p.proposer.as_ref(), release);
+
 						p.remaining_occurs = p.remaining_occurs - 1;
-						if p.remaining_occurs <= 0 {
+						p.last_paid_at = Some(now);
+						p.milestone_confirmed = false;
+						p.partially_paid = Zero::zero();
+						let finished = p.remaining_occurs.is_zero();
+						if finished {
+							Self::forget_approval(index);
 							<Proposals<T, I>>::remove(index);
+							<Reporters<T, I>>::remove(index);
+							Self::untrack_proposer(&p, index, false);
+							Self::clear_metadata(index);
+							Self::drop_description(index, false);
+							<Statuses<T, I>>::insert(index, ProposalStatus::Completed);
 						} else {
-							<Proposals<T, I>>::remove(index);
+							<Statuses<T, I>>::insert(index, Self::approved_status(&p));
 							<Proposals<T, I>>::insert(index, p.clone());
 						}
 
-						// return their deposit.
-						let err_amount = T::Currency::unreserve(&p.proposer, p.bond);
-						debug_assert!(err_amount.is_zero());
-						// provide the allocation.
-						imbalance.subsume(T::Currency::deposit_creating(&p.beneficiary, p.value));
+						// provide the allocation, one share at a time.
+						for (who, share) in shares.into_iter().filter(|(_, share)| !share.is_zero())
+						{
+							Self::pay_beneficiary(&who, share, &mut imbalance);
+							Self::vest_award(index, &p, &who, share, now);
 
-						Self::deposit_event(Event::Awarded(index, p.value, p.beneficiary.clone()));
+							Self::deposit_event(Event::Awarded(
+								index,
+								share,
+								who,
+								p.occurs - p.remaining_occurs,
+								p.remaining_occurs,
+							));
+						}
+						lifecycle_calls.push(LifecycleCall::Awarded(
+							index,
+							award,
+							p.occurs - p.remaining_occurs,
+						));
+						if finished {
+							lifecycle_calls.push(LifecycleCall::Completed(index));
+						}
+						if finished && p.occurs > 1 {
+							Self::deposit_event(Event::RecurringCompleted(index));
+						}
+						// recurring proposals stay queued until every occurrence is paid.
+						!finished
+					} else if Self::approval_lapsed(index, now) {
+						Self::lapse_approval(index, &p, &mut lifecycle_calls);
 						false
+					} else if allow_partial && !budget_remaining.is_zero() {
+						// Pay what is left and carry the rest over; the bond is only released
+						// once the whole award has been paid.
+						let paid = budget_remaining;
+						budget_remaining = Zero::zero();
+						let shares = p.shares_of(paid);
+						Self::note_awarded(&mut awarded, &shares);
+						awarded_any = true;
+						spent = spent.saturating_add(paid);
+
+						p.partially_paid = p.partially_paid.saturating_add(paid);
+						<Proposals<T, I>>::insert(index, p.clone());
+						for (who, share) in shares.into_iter().filter(|(_, share)| !share.is_zero())
+						{
+							Self::pay_beneficiary(&who, share, &mut imbalance);
+							Self::vest_award(index, &p, &who, share, now);
+						}
+
+						Self::deposit_event(Event::PartiallyAwarded(
+							index,
+							paid,
+							award.saturating_sub(paid),
+						));
+						true
 					} else {
-						log::info!("qewrasdfa");
-						missed_any = true;
+						// When paying smallest first, an award larger than the whole pot would
+						// otherwise hold back the burn for as long as it is queued.
+						if !smallest_first || award <= pot {
+							missed_any = true;
+						}
+						missed_approvals = missed_approvals.saturating_add(1);
+						Self::deposit_event(Event::ProposalMissed(index, award, budget_remaining));
 						true
 					}
 				} else {
+					Self::forget_approval(index);
 					false
 				}
 			});
 			proposals_approvals_len
 		});
+		lifecycle_calls.into_iter().for_each(LifecycleCall::make);
+		<SpentThisPeriod<T, I>>::put(spent);
+		Self::note_uncommitted(spent);
+		<TotalAwarded<T, I>>::mutate(|total| *total = total.saturating_add(spent));
+		if let (true, Some(cap)) = (period_cap_reached, period_cap) {
+			Self::deposit_event(Event::PeriodBudgetExhausted(spent, cap));
+		}
+		// Deferred payouts only hold back the burn if nothing else could be paid.
+		if deferred_any && !awarded_any {
+			missed_any = true;
+		}
 
-		total_weight += T::WeightInfo::on_initialize_proposals(proposals_len);
+		total_weight += T::WeightInfo::on_initialize_proposals(proposals_len, 0);
+		total_weight += T::WeightInfo::sort_approvals(proposals_len);
 
 		// Call Runtime hooks to external pallet using treasury to compute spend funds.
-		T::SpendFunds::spend_funds(
+		let context = SpendContext {
+			now,
+			period: Self::spend_period_index(now),
+			awarded: spent,
+			missed_approvals,
+			weight_limit: total_weight.saturating_add(T::SpendFundsWeightLimit::get()),
+		};
+		T::SpendFunds::spend_funds_with_context(
+			&context,
 			&mut budget_remaining,
 			&mut imbalance,
 			&mut total_weight,
 			&mut missed_any,
 		);
 
+		// The hooks must not report more budget than is actually left in the pot.
+		let pot_remaining = pot.saturating_sub(imbalance.peek());
+		if budget_remaining > pot_remaining {
+			log::warn!(
+				target: "runtime::treasury",
+				"SpendFunds reported a remaining budget larger than the pot can cover; \
+				clamping it to what is left.",
+			);
+			budget_remaining = pot_remaining;
+		}
+
 		if !missed_any {
-			// burn some proportion of the remaining budget if we run a surplus.
-			let burn = (T::Burn::get() * budget_remaining).min(budget_remaining);
-			budget_remaining -= burn;
+			// burn some proportion of the remaining budget above the burn threshold if we run a
+			// surplus, without going below the burn floor.
+			let burn = Self::surplus_burn(pot, budget_remaining)
+				.min(budget_remaining.saturating_sub(T::BurnFloor::get()));
+			if !burn.is_zero() {
+				budget_remaining = budget_remaining.saturating_sub(burn);
 
-			let (debit, credit) = T::Currency::pair(burn);
-			imbalance.subsume(debit);
-			T::BurnDestination::on_unbalanced(credit);
-			Self::deposit_event(Event::Burnt(burn))
+				let (debit, credit) = T::Currency::pair(burn);
+				imbalance.subsume(debit);
+				Self::distribute_burn(credit);
+				<TotalBurnt<T, I>>::mutate(|total| *total = total.saturating_add(burn));
+				Self::deposit_event(Event::Burnt(burn))
+			}
 		}
 
 		// Must never be an error, but better to be safe.
@@ -563,25 +4201,208 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			// Nothing else to do here.
 			drop(problem);
 		}
+		<LastPeriodPot<T, I>>::put(Self::pot());
+		Self::update_deactivated();
 
-		let w_proposals = Self::waiting_proposal_count();
-		for i in 0..w_proposals {
-			let c_proposals = Self::proposal_count();
-			if let Some(w) = Self::waiting_proposals(i) {
+		// Promote the waiting proposals that were queued before the last spend block and that
+		// `on_idle` did not get to since.
+		let overdue = Self::waiting_promotion_cutoff();
+		let promoted = Self::promote_waiting(overdue, ProposalIndex::MAX);
+		total_weight += T::WeightInfo::on_initialize_proposals(proposals_len, promoted)
+			.saturating_sub(T::WeightInfo::on_initialize_proposals(proposals_len, 0));
+		<WaitingPromotionCutoff<T, I>>::put(Self::waiting_queue_tail());
+
+		Self::deposit_event(Event::Rollover(budget_remaining));
+
+		total_weight
+	}
+
+	/// Promote the waiting proposals queued before position `end` in the order they were queued,
+	/// going through at most `limit` positions. Returns the number of positions gone through,
+	/// gaps included.
+	fn promote_waiting(end: ProposalIndex, limit: ProposalIndex) -> ProposalIndex {
+		let head = Self::waiting_queue_head();
+		let end = end.min(Self::waiting_queue_tail()).min(head.saturating_add(limit));
+		for i in head..end {
+			// Skip any gaps left in the waiting proposals. Nothing below can fail, so an entry
+			// is never left half promoted.
+			if let Some(w) = <WaitingProposals<T, I>>::take(i) {
+				let c_proposals = Self::proposal_count();
 				<ProposalCount<T, I>>::put(c_proposals + 1);
-				<Proposals<T, I>>::insert(c_proposals, w.clone());
+				if let Some(proposer) = &w.proposer {
+					Self::unindex_proposal(proposer, i, true);
+					Self::index_proposal(proposer, c_proposals, false);
+				}
+				if let Some(hash) = <WaitingProposalDescriptions<T, I>>::take(i) {
+					<ProposalDescriptions<T, I>>::insert(c_proposals, hash);
+				}
+				<WaitingStatuses<T, I>>::remove(i);
+				<Statuses<T, I>>::insert(c_proposals, ProposalStatus::Proposed);
+
+				Self::deposit_event(Event::WaitingProposalTransfered {
+					waiting_index: i,
+					new_proposal_index: c_proposals,
+					proposer: w.proposer.clone(),
+					beneficiary: w.beneficiary.clone(),
+					value: w.total_value,
+					chunk: w.next_award(),
+					occurs: w.occurs,
+					bond: w.bond,
+				});
+				Self::deposit_event(Event::Proposed {
+					proposal_index: c_proposals,
+					proposer: w.proposer.clone(),
+					beneficiary: w.beneficiary.clone(),
+					value: w.total_value,
+					chunk: w.next_award(),
+					occurs: w.occurs,
+					bond: w.bond,
+				});
+				<Proposals<T, I>>::insert(c_proposals, w);
 			}
+		}
+		if end > head {
+			<WaitingQueueHead<T, I>>::put(end);
+		}
+		end.saturating_sub(head)
+	}
+
+	/// Deactivate or reactivate issuance so that `Deactivated` matches the pot again. Returns
+	/// the amounts reactivated and deactivated, if anything changed.
+	fn update_deactivated() -> Option<(BalanceOf<T, I>, BalanceOf<T, I>)> {
+		let pot = Self::pot();
+		let deactivated = Self::deactivated();
+		if pot == deactivated {
+			return None
+		}
+		T::Currency::reactivate(deactivated);
+		T::Currency::deactivate(pot);
+		<Deactivated<T, I>>::put(pot);
+		Some((deactivated, pot))
+	}
 
-			<WaitingProposalCount<T, I>>::put(w_proposals - 1);
-			<WaitingProposals<T, I>>::remove(i);
+	/// The number of positions in the waiting proposal queue, including the gaps left by
+	/// removed entries.
+	pub fn waiting_proposal_count() -> ProposalIndex {
+		Self::waiting_queue_tail().saturating_sub(Self::waiting_queue_head())
+	}
+
+	/// The percentage of spare funds burnt per spend period.
+	pub fn burn() -> Permill {
+		Self::burn_override().unwrap_or_else(T::Burn::get)
+	}
 
-			Self::deposit_event(Event::WaitingProposalTransfered(w_proposals));
-			Self::deposit_event(Event::Proposed(c_proposals))
+	/// What `T::BurnMode` burns of the part of `budget_remaining` above `T::BurnThreshold`, for
+	/// a spend period that started with `pot`.
+	fn surplus_burn(pot: BalanceOf<T, I>, budget_remaining: BalanceOf<T, I>) -> BalanceOf<T, I> {
+		let surplus = budget_remaining.saturating_sub(T::BurnThreshold::get());
+		match T::BurnMode::get() {
+			BurnMode::Static => Self::burn() * surplus,
+			BurnMode::Adaptive { factor, max } => {
+				// Without a previous period there is no growth to measure.
+				let growth = Self::last_period_pot()
+					.map_or_else(Zero::zero, |last| pot.saturating_sub(last));
+				(factor * growth).min(max * surplus)
+			},
 		}
+	}
 
-		Self::deposit_event(Event::Rollover(budget_remaining));
+	/// How far block `n` is into its spend period, counting from the spend block.
+	pub fn spend_period_phase(n: T::BlockNumber) -> T::BlockNumber {
+		n.saturating_add(T::SpendPeriodOffset::get()) % T::SpendPeriod::get()
+	}
 
-		total_weight
+	/// Whether a spend round is due at block `n`: either it is a spend block, or a whole
+	/// `T::SpendPeriod` has passed since the last round because spend blocks were skipped.
+	/// However many spend blocks were skipped, a single round catches up on them.
+	pub fn is_spend_due(n: T::BlockNumber) -> bool {
+		Self::spend_period_phase(n).is_zero() ||
+			n.saturating_sub(Self::last_spend_block()) >= T::SpendPeriod::get()
+	}
+
+	/// The index of the spend period that block `n` is in, counting from the spend block.
+	pub fn spend_period_index(n: T::BlockNumber) -> T::BlockNumber {
+		n.saturating_add(T::SpendPeriodOffset::get()) / T::SpendPeriod::get()
+	}
+
+	/// The number of blocks at the start of each spend period in which proposals are accepted
+	/// directly.
+	pub fn allowed_proposal_period() -> T::BlockNumber {
+		Self::allowed_proposal_period_override().unwrap_or_else(T::AllowedProposalPeriod::get)
+	}
+
+	/// The sum of the burn destination shares, in parts per million.
+	fn total_burn_share(destinations: &[(T::AccountId, Permill)]) -> u32 {
+		destinations
+			.iter()
+			.fold(0u32, |total, (_, share)| total.saturating_add(share.deconstruct()))
+	}
+
+	/// Share the burnt funds out between the `BurnDestinations`, sending the rest to
+	/// `T::BurnDestination`.
+	fn distribute_burn(mut credit: NegativeImbalanceOf<T, I>) {
+		let burn = credit.peek();
+		let destinations = Self::burn_destinations();
+		let mut amounts: Vec<BalanceOf<T, I>> =
+			destinations.iter().map(|(_, share)| *share * burn).collect();
+
+		if Self::total_burn_share(&destinations) == Permill::one().deconstruct() {
+			// Nothing is meant to be burnt, so the rounding dust goes to the first destination.
+			let shared =
+				amounts.iter().fold(Zero::zero(), |a: BalanceOf<T, I>, b| a.saturating_add(*b));
+			if let Some(first) = amounts.first_mut() {
+				*first = first.saturating_add(burn.saturating_sub(shared));
+			}
+		}
+
+		for ((who, _), amount) in destinations.iter().zip(amounts) {
+			let (part, rest) = credit.split(amount);
+			credit = rest;
+			T::Currency::resolve_creating(who, part);
+			Self::deposit_event(Event::BurnShared(who.clone(), amount));
+		}
+		T::BurnDestination::on_unbalanced(credit);
+	}
+
+	/// The proposal at `proposal_id`, if any.
+	pub fn proposal_details(
+		proposal_id: ProposalIndex,
+	) -> Option<ProposalDetails<T::AccountId, BalanceOf<T, I>, T::BlockNumber, T::Beneficiary>> {
+		Self::proposals(proposal_id).map(Into::into)
+	}
+
+	/// All proposals, by index.
+	pub fn all_proposals() -> Vec<(
+		ProposalIndex,
+		ProposalDetails<T::AccountId, BalanceOf<T, I>, T::BlockNumber, T::Beneficiary>,
+	)> {
+		let mut proposals: Vec<_> = <Proposals<T, I>>::iter()
+			.map(|(index, proposal)| (index, proposal.into()))
+			.collect();
+		proposals.sort_by_key(|(index, _)| *index);
+		proposals
+	}
+
+	/// All waiting proposals, in the order they will be promoted.
+	pub fn all_waiting_proposals() -> Vec<(
+		ProposalIndex,
+		ProposalDetails<T::AccountId, BalanceOf<T, I>, T::BlockNumber, T::Beneficiary>,
+	)> {
+		(Self::waiting_queue_head()..Self::waiting_queue_tail())
+			.filter_map(|index| Self::waiting_proposals(index).map(|p| (index, p.into())))
+			.collect()
+	}
+
+	/// The indices of the approved proposals, in the order they are paid.
+	pub fn approval_queue() -> Vec<ProposalIndex> {
+		let mut approvals = Self::approvals();
+		Self::sort_for_payout(&mut approvals);
+		approvals.into_inner()
+	}
+
+	/// The part of the pot that is not committed to the proposals in `Approvals`.
+	pub fn uncommitted_pot() -> BalanceOf<T, I> {
+		Self::pot().saturating_sub(Self::committed())
 	}
 
 	/// Return the amount of money in the pot.
@@ -590,16 +4411,169 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		T::Currency::free_balance(&Self::account_id())
 			// Must never be less than 0 but better be safe.
 			.saturating_sub(T::Currency::minimum_balance())
+			// Awards waiting to be claimed are no longer ours to spend.
+			.saturating_sub(Self::total_pending_payouts())
+	}
+
+	/// Check the invariants that hold between the storage items of the pallet:
+	///
+	/// * every proposal index is below `ProposalCount`,
+	/// * every waiting proposal is between the head and the tail of the waiting queue,
+	/// * every index in `Approvals` is that of a proposal,
+	/// * every proposer has at least the bonds and metadata deposits held for them reserved.
+	///
+	/// This version of FRAME has no `try_state` hook, so the check is run from the `post_upgrade`
+	/// of `migrations::v1::Migration` and from tests only.
+	#[cfg(any(feature = "try-runtime", test))]
+	pub fn do_try_state() -> Result<(), &'static str> {
+		let count = Self::proposal_count();
+		ensure!(
+			<Proposals<T, I>>::iter_keys().all(|index| index < count),
+			"a proposal index is not below `ProposalCount`"
+		);
+
+		let (head, tail) = (Self::waiting_queue_head(), Self::waiting_queue_tail());
+		ensure!(head <= tail, "the waiting queue head is past its tail");
+		ensure!(
+			<WaitingProposals<T, I>>::iter_keys().all(|index| head <= index && index < tail),
+			"a waiting proposal is outside the waiting queue"
+		);
+
+		ensure!(
+			Self::approvals().iter().all(|index| <Proposals<T, I>>::contains_key(index)),
+			"an index in `Approvals` has no proposal"
+		);
+
+		let mut held = BTreeMap::<T::AccountId, BalanceOf<T, I>>::new();
+		let proposals =
+			<Proposals<T, I>>::iter_values().chain(<WaitingProposals<T, I>>::iter_values());
+		for proposal in proposals {
+			if let Some(proposer) = proposal.proposer {
+				let bonds = held.entry(proposer).or_insert_with(Zero::zero);
+				*bonds = bonds.saturating_add(proposal.remaining_bond);
+			}
+		}
+		for (who, deposit) in <MetadataDeposits<T, I>>::iter_values() {
+			let deposits = held.entry(who).or_insert_with(Zero::zero);
+			*deposits = deposits.saturating_add(deposit);
+		}
+		ensure!(
+			held.iter().all(|(who, amount)| T::Currency::reserved_balance(who) >= *amount),
+			"a proposer has less reserved than the bonds and deposits held for them"
+		);
+
+		Ok(())
 	}
 }
 
-impl<T: Config<I>, I: 'static> OnUnbalanced<NegativeImbalanceOf<T, I>> for Pallet<T, I> {
-	fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<T, I>) {
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Deposit `amount` into the pot, reporting it as coming from `source`. Other pallets should
+	/// deposit through here rather than into `Self::account_id()` directly, so that the deposit
+	/// is accounted for. Nothing happens for a zero amount.
+	pub fn deposit_from(source: DepositSource, amount: NegativeImbalanceOf<T, I>) {
 		let numeric_amount = amount.peek();
+		if numeric_amount.is_zero() {
+			return
+		}
 
 		// Must resolve into existing but better to be safe.
 		let _ = T::Currency::resolve_creating(&Self::account_id(), amount);
+		<TotalDeposited<T, I>>::mutate(|total| *total = total.saturating_add(numeric_amount));
+		Self::update_deactivated();
+
+		Self::deposit_event(Event::Deposit(numeric_amount, source));
+	}
+}
+
+impl<T: Config<I>, I: 'static> TreasurySpender for Pallet<T, I> {
+	type Balance = BalanceOf<T, I>;
+	type Beneficiary = T::Beneficiary;
+
+	fn queue_spend(
+		value: BalanceOf<T, I>,
+		beneficiary: T::Beneficiary,
+	) -> Result<ProposalIndex, DispatchError> {
+		Self::do_spend(value, beneficiary)
+	}
+
+	#[transactional]
+	fn spend_now(value: BalanceOf<T, I>, beneficiary: T::Beneficiary) -> DispatchResult {
+		Self::ensure_spendable(value, &beneficiary)?;
+		ensure!(value <= Self::uncommitted_pot(), Error::<T, I>::InsufficientFunds);
+		let spent = Self::spent_this_period().saturating_add(value);
+		if let Some(cap) = T::MaxSpendPerPeriod::get() {
+			ensure!(spent <= cap, Error::<T, I>::OverPeriodBudget);
+		}
+
+		// Take the funds out of the pot before anything is paid.
+		let account_id = Self::account_id();
+		let debit = T::Currency::withdraw(&account_id, value, WithdrawReasons::TRANSFER, KeepAlive)
+			.map_err(|_| Error::<T, I>::InsufficientFunds)?;
+		let mut imbalance = <PositiveImbalanceOf<T, I>>::zero();
+		T::NativePaymaster::pay(&beneficiary, value, &mut imbalance);
+		// Whatever the paymaster did not pay stays in the pot.
+		if let Ok(unpaid) = debit.offset(imbalance).try_same() {
+			T::Currency::resolve_creating(&account_id, unpaid);
+		}
+		<SpentThisPeriod<T, I>>::put(spent);
+		<TotalAwarded<T, I>>::mutate(|total| *total = total.saturating_add(value));
+		Self::update_deactivated();
+
+		Self::deposit_event(Event::SpentNow(value, beneficiary));
+		Ok(())
+	}
+}
+
+impl<T: Config<I>, I: 'static> TreasuryInspect for Pallet<T, I> {
+	type Balance = BalanceOf<T, I>;
+
+	fn pot() -> BalanceOf<T, I> {
+		Pallet::<T, I>::pot()
+	}
+
+	fn committed() -> BalanceOf<T, I> {
+		Pallet::<T, I>::committed()
+	}
+
+	fn proposal_exists(index: ProposalIndex) -> bool {
+		<Proposals<T, I>>::contains_key(index)
+	}
+}
+
+/// The account of the treasury pot, for the `Get<AccountId>` parameters of other pallets.
+pub struct TreasuryAccountId<T, I = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> Get<T::AccountId> for TreasuryAccountId<T, I> {
+	fn get() -> T::AccountId {
+		Pallet::<T, I>::account_id()
+	}
+}
+
+impl<T: Config<I>, I: 'static> OnUnbalanced<NegativeImbalanceOf<T, I>> for Pallet<T, I> {
+	fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<T, I>) {
+		Self::deposit_from(DepositSource::Unattributed, amount);
+	}
+}
+
+/// Splits imbalances between the treasury and `Other`, for example transaction fees between the
+/// treasury and the block author. The treasury gets `TreasuryShare` of each imbalance, rounded
+/// up, and `Other` gets the rest.
+pub struct SplitToTreasury<R, TreasuryShare, Other, I = ()>(
+	PhantomData<(R, TreasuryShare, Other, I)>,
+);
 
-		Self::deposit_event(Event::Deposit(numeric_amount));
+impl<R, TreasuryShare, Other, I> OnUnbalanced<NegativeImbalanceOf<R, I>>
+	for SplitToTreasury<R, TreasuryShare, Other, I>
+where
+	R: Config<I>,
+	TreasuryShare: Get<Permill>,
+	Other: OnUnbalanced<NegativeImbalanceOf<R, I>>,
+	I: 'static,
+{
+	fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<R, I>) {
+		let to_treasury = TreasuryShare::get().mul_ceil(amount.peek());
+		let (to_treasury, to_other) = amount.split(to_treasury);
+		Pallet::<R, I>::on_unbalanced(to_treasury);
+		Other::on_unbalanced(to_other);
 	}
 }
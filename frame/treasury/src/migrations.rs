@@ -0,0 +1,236 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage migrations for the treasury pallet.
+
+use super::*;
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+use sp_runtime::traits::Convert;
+use sp_std::marker::PhantomData;
+
+/// The storage layout before storage versions were introduced.
+mod v0 {
+	use super::*;
+
+	/// A proposal paying `value` per occurrence. The whole bond was returned with the first
+	/// award.
+	#[derive(Decode)]
+	pub(super) struct OldProposal<AccountId, Balance> {
+		proposer: AccountId,
+		value: Balance,
+		beneficiary: AccountId,
+		bond: Balance,
+		occurs: u32,
+		remaining_occurs: u32,
+	}
+
+	/// Upgrade `old` to the current layout, turning its beneficiary account into a
+	/// `T::Beneficiary` with `C`.
+	pub(super) fn upgraded<T, I, C>(
+		old: OldProposal<T::AccountId, BalanceOf<T, I>>,
+	) -> ProposalOf<T, I>
+	where
+		T: Config<I>,
+		I: 'static,
+		C: Convert<T::AccountId, T::Beneficiary>,
+	{
+		// A proposal made without chunks paid its value once.
+		let (occurs, remaining_occurs) = match old.occurs {
+			0 => (1, 1),
+			occurs => (occurs, old.remaining_occurs),
+		};
+		let remaining_bond = if remaining_occurs < occurs { Zero::zero() } else { old.bond };
+		Proposal {
+			proposer: Some(old.proposer),
+			total_value: old.value.saturating_mul(occurs.into()),
+			beneficiary: C::convert(old.beneficiary),
+			bond: old.bond,
+			remaining_bond,
+			occurs,
+			remaining_occurs,
+			interval: Zero::zero(),
+			last_paid_at: None,
+			paused: false,
+			schedule: None,
+			expires_at: None,
+			milestone_gated: false,
+			milestone_confirmed: false,
+			partially_paid: Zero::zero(),
+			vesting_period: None,
+			splits: Default::default(),
+			proposed_at: Zero::zero(),
+			approved_at: None,
+		}
+	}
+}
+
+/// The migration to storage version 1.
+pub mod v1 {
+	use super::*;
+	use frame_support::{storage::migration::take_storage_value, traits::PalletInfoAccess};
+
+	/// Migrate the storage of the pallet as it was before storage versions were introduced to
+	/// version 1:
+	///
+	/// * `Proposals` and `WaitingProposals` are translated to the current layout, with `C`
+	///   turning the beneficiary accounts into `T::Beneficiary`, e.g.
+	///   `sp_runtime::traits::Identity` if it is `T::AccountId`,
+	/// * `WaitingProposalCount` becomes the tail of the waiting queue,
+	/// * the proposals in `Approvals` count as approved at the current block,
+	/// * the statuses, the proposer index, the pending proposal counts, the committed amount and
+	///   the last spend block are recorded.
+	///
+	/// Does nothing if the on chain storage version is already 1 or later.
+	pub fn migrate<T: Config<I>, I: 'static, C: Convert<T::AccountId, T::Beneficiary>>() -> Weight {
+		let on_chain_storage_version = Pallet::<T, I>::on_chain_storage_version();
+		if on_chain_storage_version >= 1 {
+			log::info!(
+				target: "runtime::treasury",
+				"migration: treasury storage is already at version {:?}, skipping migration",
+				on_chain_storage_version,
+			);
+			return T::DbWeight::get().reads(1)
+		}
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let approvals = Approvals::<T, I>::get();
+		let mut committed = <BalanceOf<T, I>>::zero();
+		let mut translated = 0u64;
+		Proposals::<T, I>::translate::<v0::OldProposal<T::AccountId, BalanceOf<T, I>>, _>(
+			|index, old| {
+				let mut proposal = v0::upgraded::<T, I, C>(old);
+				let status = if approvals.contains(&index) {
+					proposal.approved_at = Some(now);
+					ApprovedAt::<T, I>::insert(index, now);
+					committed = committed.saturating_add(proposal.remaining_value());
+					Pallet::<T, I>::approved_status(&proposal)
+				} else {
+					ProposalStatus::Proposed
+				};
+				Statuses::<T, I>::insert(index, status);
+				if let Some(proposer) = &proposal.proposer {
+					Pallet::<T, I>::note_pending(proposer);
+					Pallet::<T, I>::index_proposal(proposer, index, false);
+				}
+				translated += 1;
+				Some(proposal)
+			},
+		);
+		WaitingProposals::<T, I>::translate::<v0::OldProposal<T::AccountId, BalanceOf<T, I>>, _>(
+			|index, old| {
+				let proposal = v0::upgraded::<T, I, C>(old);
+				WaitingStatuses::<T, I>::insert(index, ProposalStatus::Waiting);
+				if let Some(proposer) = &proposal.proposer {
+					Pallet::<T, I>::note_pending(proposer);
+					Pallet::<T, I>::index_proposal(proposer, index, true);
+				}
+				translated += 1;
+				Some(proposal)
+			},
+		);
+
+		// The waiting proposals keep their indices, so they stay in the order they were made.
+		let waiting_count = take_storage_value::<ProposalIndex>(
+			Pallet::<T, I>::name().as_bytes(),
+			b"WaitingProposalCount",
+			&[],
+		)
+		.unwrap_or_default();
+		WaitingQueueTail::<T, I>::put(waiting_count);
+		Committed::<T, I>::put(committed);
+		// The last scheduled spend block counts as the last spend round, so that no catch-up
+		// round is run straight away.
+		LastSpendBlock::<T, I>::put(now.saturating_sub(Pallet::<T, I>::spend_period_phase(now)));
+		StorageVersion::new(1).put::<Pallet<T, I>>();
+
+		log::info!(
+			target: "runtime::treasury",
+			"migration: treasury storage migrated {} proposals to version 1",
+			translated,
+		);
+		T::DbWeight::get()
+			.reads_writes(3 * translated + 4, 4 * translated + approvals.len() as u64 + 5)
+	}
+
+	/// How many proposals `Migration::pre_upgrade` samples to look up again after the upgrade.
+	#[cfg(feature = "try-runtime")]
+	const TRY_RUNTIME_SAMPLE_SIZE: usize = 16;
+
+	/// Runs `migrate` on runtime upgrade. Runtimes include it in the migrations of their
+	/// executive, as the pallet cannot know how to convert old beneficiaries on its own.
+	pub struct Migration<T, I, C>(PhantomData<(T, I, C)>);
+
+	impl<T, I, C> OnRuntimeUpgrade for Migration<T, I, C>
+	where
+		T: Config<I>,
+		I: 'static,
+		C: Convert<T::AccountId, T::Beneficiary>,
+	{
+		fn on_runtime_upgrade() -> Weight {
+			migrate::<T, I, C>()
+		}
+
+		/// Note how many proposals and waiting proposals are stored, undecoded, together with
+		/// `ProposalCount` and the indices of a sample of the proposals.
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+			let count = Proposals::<T, I>::iter_keys().count() as u32 +
+				WaitingProposals::<T, I>::iter_keys().count() as u32;
+			let sample: Vec<ProposalIndex> =
+				Proposals::<T, I>::iter_keys().take(TRY_RUNTIME_SAMPLE_SIZE).collect();
+			Self::set_temp_storage(
+				(count, Pallet::<T, I>::proposal_count(), sample),
+				Pallet::<T, I>::name(),
+			);
+			Ok(())
+		}
+
+		/// Check that every proposal and waiting proposal noted before the upgrade still
+		/// decodes, that the sampled proposals kept their index, that the storage version is
+		/// current and that the storage invariants hold.
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+			let (before, proposal_count, sample): (u32, ProposalIndex, Vec<ProposalIndex>) =
+				Self::get_temp_storage(Pallet::<T, I>::name())
+					.ok_or("the treasury pre-upgrade check did not run")?;
+			let after = Proposals::<T, I>::iter_values().count() as u32 +
+				WaitingProposals::<T, I>::iter_values().count() as u32;
+			frame_support::ensure!(
+				before == after,
+				"treasury proposals were lost in the migration"
+			);
+			frame_support::ensure!(
+				Pallet::<T, I>::proposal_count() == proposal_count,
+				"the treasury proposal count changed in the migration"
+			);
+			frame_support::ensure!(
+				sample.into_iter().all(|index| Proposals::<T, I>::get(index).is_some()),
+				"a sampled treasury proposal is missing after the migration"
+			);
+			frame_support::ensure!(
+				Pallet::<T, I>::on_chain_storage_version() ==
+					Pallet::<T, I>::current_storage_version(),
+				"the treasury storage version was not updated"
+			);
+			Pallet::<T, I>::do_try_state()
+		}
+	}
+}
@@ -0,0 +1,221 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The payment abstractions behind awards and asset spends.
+
+use super::*;
+use frame_support::{
+	dispatch::{DispatchError, DispatchResult, Parameter},
+	traits::{tokens::fungibles, VestingSchedule},
+};
+use sp_runtime::traits::SaturatedConversion;
+use sp_std::marker::PhantomData;
+
+/// How a payment made with `Pay::pay` is going.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum PaymentStatus {
+	/// The payment has not concluded yet.
+	InProgress,
+	/// The payment was made.
+	Success,
+	/// The payment failed. It may be made again.
+	Failure,
+	/// Nothing is known about the payment, e.g. because it was never made.
+	Unknown,
+}
+
+/// Pays some amount of a kind of asset to a beneficiary. A payment need not conclude when `pay`
+/// returns; its outcome is looked up with `check_payment`.
+pub trait Pay {
+	/// The type in which amounts are paid.
+	type Balance: AtLeast32BitUnsigned + Parameter + MaxEncodedLen + Copy;
+	/// Who is paid.
+	type Beneficiary: Parameter + MaxEncodedLen;
+	/// The kind of asset that is paid.
+	type AssetKind: Parameter + MaxEncodedLen;
+	/// Identifies a payment, to check on it later.
+	type Id: Parameter + MaxEncodedLen + Copy;
+	/// Why a payment could not be made.
+	type Error: Debug;
+
+	/// Start paying `amount` of `asset_kind` to `who`.
+	fn pay(
+		who: &Self::Beneficiary,
+		asset_kind: Self::AssetKind,
+		amount: Self::Balance,
+	) -> Result<Self::Id, Self::Error>;
+
+	/// How the payment `id` is going.
+	fn check_payment(id: Self::Id) -> PaymentStatus;
+
+	/// Make sure that paying `amount` of `asset_kind` to `who` succeeds.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_successful(
+		_who: &Self::Beneficiary,
+		_asset_kind: Self::AssetKind,
+		_amount: Self::Balance,
+	) {
+	}
+
+	/// Make sure that the payment `id` has concluded.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_concluded(_id: Self::Id) {}
+}
+
+/// Disables asset spends: no payment can be made.
+impl Pay for () {
+	type Balance = u128;
+	type Beneficiary = ();
+	type AssetKind = ();
+	type Id = ();
+	type Error = ();
+
+	fn pay(_who: &(), _asset_kind: (), _amount: u128) -> Result<(), ()> {
+		Err(())
+	}
+
+	fn check_payment(_id: ()) -> PaymentStatus {
+		PaymentStatus::Unknown
+	}
+}
+
+/// Pays the fungible assets `F` out of the account `A`. Payments conclude right away.
+pub struct PayFromAccount<F, A, AccountId>(PhantomData<(F, A, AccountId)>);
+
+impl<F, A, AccountId> Pay for PayFromAccount<F, A, AccountId>
+where
+	F: fungibles::Transfer<AccountId> + fungibles::Mutate<AccountId>,
+	F::AssetId: Parameter + MaxEncodedLen,
+	F::Balance: Parameter + MaxEncodedLen,
+	A: Get<AccountId>,
+	AccountId: Parameter + MaxEncodedLen,
+{
+	type Balance = F::Balance;
+	type Beneficiary = AccountId;
+	type AssetKind = F::AssetId;
+	type Id = ();
+	type Error = DispatchError;
+
+	fn pay(
+		who: &AccountId,
+		asset_kind: F::AssetId,
+		amount: F::Balance,
+	) -> Result<(), DispatchError> {
+		<F as fungibles::Transfer<_>>::transfer(asset_kind, &A::get(), who, amount, false)
+			.map(|_| ())
+	}
+
+	fn check_payment(_id: ()) -> PaymentStatus {
+		PaymentStatus::Success
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_successful(_who: &AccountId, asset_kind: F::AssetId, amount: F::Balance) {
+		let _ = <F as fungibles::Mutate<_>>::mint_into(asset_kind, &A::get(), amount);
+	}
+}
+
+/// Delivers the awards of approved proposals to their beneficiaries, e.g. to a local account or
+/// to a location on another chain.
+pub trait Paymaster<T: Config<I>, I: 'static = ()> {
+	/// The local account that `who` refers to, if any. Only a local account can propose to pay
+	/// itself, veto its own proposals or claim awards left pending under `PayoutMode::Pull`.
+	fn account_of(who: &T::Beneficiary) -> Option<T::AccountId>;
+
+	/// Pay `amount` to `who`. Whatever is subsumed into `imbalance` is withdrawn from the pot
+	/// once the spend round is settled.
+	fn pay(
+		who: &T::Beneficiary,
+		amount: BalanceOf<T, I>,
+		imbalance: &mut PositiveImbalanceOf<T, I>,
+	);
+
+	/// A beneficiary that refers to the local account `who`.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn beneficiary_of(who: T::AccountId) -> T::Beneficiary;
+}
+
+/// Pays awards into local accounts with `T::Currency`, for beneficiaries that are accounts.
+pub struct CurrencyPaymaster;
+
+impl<T, I> Paymaster<T, I> for CurrencyPaymaster
+where
+	T: Config<I, Beneficiary = <T as frame_system::Config>::AccountId>,
+	I: 'static,
+{
+	fn account_of(who: &T::AccountId) -> Option<T::AccountId> {
+		Some(who.clone())
+	}
+
+	fn pay(who: &T::AccountId, amount: BalanceOf<T, I>, imbalance: &mut PositiveImbalanceOf<T, I>) {
+		imbalance.subsume(T::Currency::deposit_creating(who, amount));
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn beneficiary_of(who: T::AccountId) -> T::AccountId {
+		who
+	}
+}
+
+/// Locks awards paid into local accounts, releasing them linearly over time.
+pub trait VestingHandler<AccountId, Balance, BlockNumber> {
+	/// Lock `amount` of what was just paid to `who`, releasing it linearly over the `period`
+	/// blocks from `starting_block`.
+	fn vest(
+		who: &AccountId,
+		amount: Balance,
+		period: BlockNumber,
+		starting_block: BlockNumber,
+	) -> DispatchResult;
+}
+
+/// Awards cannot be vested.
+impl<AccountId, Balance, BlockNumber> VestingHandler<AccountId, Balance, BlockNumber> for () {
+	fn vest(
+		_who: &AccountId,
+		_amount: Balance,
+		_period: BlockNumber,
+		_starting_block: BlockNumber,
+	) -> DispatchResult {
+		Err(DispatchError::Other("Vesting is not supported"))
+	}
+}
+
+/// Vests awards with a `VestingSchedule` such as `pallet_vesting`. An equal part of the award is
+/// released every block, rounded up so that all of it is released within the period.
+pub struct VestingScheduleHandler<V>(PhantomData<V>);
+
+impl<AccountId, BlockNumber, V>
+	VestingHandler<AccountId, <V::Currency as Currency<AccountId>>::Balance, BlockNumber>
+	for VestingScheduleHandler<V>
+where
+	V: VestingSchedule<AccountId, Moment = BlockNumber>,
+	BlockNumber: AtLeast32BitUnsigned,
+{
+	fn vest(
+		who: &AccountId,
+		amount: <V::Currency as Currency<AccountId>>::Balance,
+		period: BlockNumber,
+		starting_block: BlockNumber,
+	) -> DispatchResult {
+		let period: <V::Currency as Currency<AccountId>>::Balance =
+			period.saturated_into::<u128>().saturated_into();
+		let period = period.max(One::one());
+		let per_block = (amount.saturating_add(period - One::one()) / period).max(One::one());
+		V::add_vesting_schedule(who, amount, per_block, starting_block)
+	}
+}
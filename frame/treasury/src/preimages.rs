@@ -0,0 +1,43 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Keeping the full descriptions of proposals available through a preimage provider.
+
+/// Requests the preimages of hashes from a preimage provider, as the `request` and `unrequest`
+/// functions of the `QueryPreimage` trait of later FRAME versions do. Requests are counted: the
+/// provider keeps a preimage while any request for it has not been dropped.
+pub trait QueryPreimage<Hash> {
+	/// Whether the preimage of `hash` is requested.
+	fn is_requested(hash: &Hash) -> bool;
+
+	/// Request the preimage of `hash` once more.
+	fn request(hash: &Hash);
+
+	/// Drop one request for the preimage of `hash`.
+	fn unrequest(hash: &Hash);
+}
+
+/// No preimages are kept.
+impl<Hash> QueryPreimage<Hash> for () {
+	fn is_requested(_hash: &Hash) -> bool {
+		false
+	}
+
+	fn request(_hash: &Hash) {}
+
+	fn unrequest(_hash: &Hash) {}
+}
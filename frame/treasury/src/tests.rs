@@ -19,22 +19,83 @@
 
 #![cfg(test)]
 
-use std::cell::RefCell;
+use std::{cell::RefCell, collections::BTreeMap, convert::TryInto};
 
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	traits::{BadOrigin, BlakeTwo256, Convert, Identity, IdentityLookup},
 };
 
 use frame_support::{
-	assert_noop, assert_ok, pallet_prelude::GenesisBuild, parameter_types, traits::OnInitialize,
-	PalletId,
+	assert_noop, assert_ok,
+	instances::{Instance1, Instance2},
+	pallet_prelude::GenesisBuild,
+	parameter_types,
+	traits::{GetStorageVersion, OnIdle, OnInitialize, StorageVersion, VestingSchedule},
+	BoundedVec, PalletId,
 };
 
 use super::*;
 use crate as treasury;
 
+// A pallet which pays from the treasury as part of its own calls, and charges fees into it.
+#[frame_support::pallet]
+pub mod spender {
+	use super::{ProposalIndex, TreasuryInspect, TreasurySpender};
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, ExistenceRequirement::KeepAlive},
+	};
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Treasury: TreasurySpender<Balance = u64, Beneficiary = u128>
+			+ TreasuryInspect<Balance = u64>;
+		type Currency: Currency<Self::AccountId, Balance = u64>;
+		type FeeSink: Get<Self::AccountId>;
+	}
+
+	/// The index of the last spend queued with the treasury.
+	#[pallet::storage]
+	pub type LastQueued<T: Config> = StorageValue<_, ProposalIndex, OptionQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::weight(0)]
+		pub fn pay(origin: OriginFor<T>, amount: u64, who: u128, now: bool) -> DispatchResult {
+			ensure_signed(origin)?;
+			if now {
+				T::Treasury::spend_now(amount, who)
+			} else {
+				let index = T::Treasury::queue_spend(amount, who)?;
+				LastQueued::<T>::put(index);
+				Ok(())
+			}
+		}
+
+		#[pallet::weight(0)]
+		pub fn charge(origin: OriginFor<T>, fee: u64) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			T::Currency::transfer(&who, &T::FeeSink::get(), fee, KeepAlive)
+		}
+
+		#[pallet::weight(0)]
+		pub fn pay_if_affordable(origin: OriginFor<T>, amount: u64, who: u128) -> DispatchResult {
+			ensure_signed(origin)?;
+			ensure!(
+				T::Treasury::pot().saturating_sub(T::Treasury::committed()) >= amount,
+				"not affordable"
+			);
+			T::Treasury::spend_now(amount, who)
+		}
+	}
+}
+
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
@@ -46,7 +107,11 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
-		Treasury: treasury::{Pallet, Call, Storage, Config, Event<T>},
+		Treasury: treasury::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Treasury1: treasury::<Instance1>::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Treasury2: treasury::<Instance2>::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Vesting: pallet_vesting::{Pallet, Call, Storage, Event<T>},
+		Spender: spender::{Pallet, Call, Storage},
 	}
 );
 
@@ -81,7 +146,7 @@ impl frame_system::Config for Test {
 	type OnSetCode = ();
 }
 parameter_types! {
-	pub const ExistentialDeposit: u64 = 1;
+	pub static ExistentialDeposit: u64 = 1;
 }
 impl pallet_balances::Config for Test {
 	type MaxLocks = ();
@@ -94,12 +159,217 @@ impl pallet_balances::Config for Test {
 	type AccountStore = System;
 	type WeightInfo = ();
 }
+parameter_types! {
+	pub const MinVestedTransfer: u64 = 1;
+}
+impl pallet_vesting::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type BlockNumberToBalance = Identity;
+	type MinVestedTransfer = MinVestedTransfer;
+	type WeightInfo = ();
+	const MAX_VESTING_SCHEDULES: u32 = 3;
+}
 thread_local! {
 	static TEN_TO_FOURTEEN: RefCell<Vec<u128>> = RefCell::new(vec![10,11,12,13,14]);
+	static SPEND_FUNDS_UNREPORTED: RefCell<u64> = RefCell::new(0);
+	static SPEND_FUNDS_INFLATION: RefCell<u64> = RefCell::new(0);
+	static SPEND_FUNDS_CONTEXT: RefCell<Option<SpendContext<u64, u64>>> = RefCell::new(None);
+	static SPEND_FUNDS_WEIGHT: RefCell<Weight> = RefCell::new(0);
+	static SECOND_SPEND_FUNDS_CALLS: RefCell<u32> = RefCell::new(0);
+	static PAID: RefCell<BTreeMap<(u128, u32), u64>> = RefCell::new(BTreeMap::new());
+	static PAYMENT_STATUS: RefCell<BTreeMap<u64, PaymentStatus>> = RefCell::new(BTreeMap::new());
+	static LAST_PAYMENT_ID: RefCell<u64> = RefCell::new(0);
+	static REFUSE_PAYMENTS: RefCell<bool> = RefCell::new(false);
+	static SENT: RefCell<BTreeMap<(u32, u128), u64>> = RefCell::new(BTreeMap::new());
+	static PREIMAGE_REQUESTS: RefCell<BTreeMap<H256, u32>> = RefCell::new(BTreeMap::new());
+	static LIFECYCLE: RefCell<Vec<Lifecycle>> = RefCell::new(vec![]);
+	static SPEND_ON_COMPLETION: RefCell<Option<(u64, u128)>> = RefCell::new(None);
+	static OTHER_SHARES: RefCell<Vec<u64>> = RefCell::new(vec![]);
+}
+/// A `Pay` which records what it pays and leaves every payment in progress until it is set.
+pub struct TestPay;
+impl Pay for TestPay {
+	type Balance = u64;
+	type Beneficiary = u128;
+	type AssetKind = u32;
+	type Id = u64;
+	type Error = ();
+
+	fn pay(who: &u128, asset_kind: u32, amount: u64) -> Result<u64, ()> {
+		if REFUSE_PAYMENTS.with(|v| *v.borrow()) {
+			return Err(())
+		}
+		PAID.with(|paid| *paid.borrow_mut().entry((*who, asset_kind)).or_default() += amount);
+		Ok(LAST_PAYMENT_ID.with(|lpi| {
+			*lpi.borrow_mut() += 1;
+			let id = *lpi.borrow();
+			PAYMENT_STATUS.with(|s| s.borrow_mut().insert(id, PaymentStatus::InProgress));
+			id
+		}))
+	}
+
+	fn check_payment(id: u64) -> PaymentStatus {
+		PAYMENT_STATUS.with(|s| s.borrow().get(&id).copied().unwrap_or(PaymentStatus::Unknown))
+	}
+}
+/// Pays the beneficiaries of the second instance, which are accounts on a chain. Chain 0 is the
+/// local one; awards to other chains leave this one and are recorded as sent.
+pub struct TestRemotePaymaster;
+impl Paymaster<Test, Instance1> for TestRemotePaymaster {
+	fn account_of(who: &(u32, u128)) -> Option<u128> {
+		match who {
+			(0, account) => Some(*account),
+			_ => None,
+		}
+	}
+
+	fn pay(who: &(u32, u128), amount: u64, imbalance: &mut PositiveImbalanceOf<Test, Instance1>) {
+		match Self::account_of(who) {
+			Some(account) => imbalance.subsume(Balances::deposit_creating(&account, amount)),
+			None => {
+				imbalance.subsume(Balances::burn(amount));
+				SENT.with(|sent| *sent.borrow_mut().entry(*who).or_default() += amount);
+			},
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn beneficiary_of(who: u128) -> (u32, u128) {
+		(0, who)
+	}
+}
+fn sent(who: (u32, u128)) -> u64 {
+	SENT.with(|sent| sent.borrow().get(&who).copied().unwrap_or_default())
+}
+fn paid(who: u128, asset_kind: u32) -> u64 {
+	PAID.with(|paid| paid.borrow().get(&(who, asset_kind)).copied().unwrap_or_default())
+}
+fn set_payment_status(id: u64, status: PaymentStatus) {
+	PAYMENT_STATUS.with(|s| s.borrow_mut().insert(id, status));
+}
+fn last_payment_id() -> u64 {
+	LAST_PAYMENT_ID.with(|lpi| *lpi.borrow())
+}
+/// A preimage provider which counts the outstanding requests for each hash.
+pub struct TestPreimages;
+impl QueryPreimage<H256> for TestPreimages {
+	fn is_requested(hash: &H256) -> bool {
+		preimage_requests(*hash) > 0
+	}
+
+	fn request(hash: &H256) {
+		PREIMAGE_REQUESTS.with(|r| *r.borrow_mut().entry(*hash).or_default() += 1);
+	}
+
+	fn unrequest(hash: &H256) {
+		PREIMAGE_REQUESTS.with(|r| {
+			let mut requests = r.borrow_mut();
+			let count = requests.get_mut(hash).expect("the hash was never requested");
+			*count -= 1;
+			if *count == 0 {
+				requests.remove(hash);
+			}
+		});
+	}
+}
+fn preimage_requests(hash: H256) -> u32 {
+	PREIMAGE_REQUESTS.with(|r| r.borrow().get(&hash).copied().unwrap_or_default())
+}
+/// A call made to `OnProposalLifecycle`.
+#[derive(Clone, Debug, PartialEq)]
+enum Lifecycle {
+	Approved(ProposalIndex),
+	Rejected(ProposalIndex),
+	Awarded(ProposalIndex, u64, u32),
+	Completed(ProposalIndex),
+}
+/// An `OnProposalLifecycle` which records every call in order, and queues the spend in
+/// `SPEND_ON_COMPLETION`, if any, once a proposal completes.
+pub struct TestLifecycle;
+impl OnProposalLifecycle<Test> for TestLifecycle {
+	fn on_approved(index: ProposalIndex, _: &ProposalOf<Test>) {
+		LIFECYCLE.with(|l| l.borrow_mut().push(Lifecycle::Approved(index)));
+	}
+
+	fn on_rejected(index: ProposalIndex, _: &ProposalOf<Test>) {
+		LIFECYCLE.with(|l| l.borrow_mut().push(Lifecycle::Rejected(index)));
+	}
+
+	fn on_awarded(index: ProposalIndex, amount: u64, occurrence: u32) {
+		LIFECYCLE.with(|l| l.borrow_mut().push(Lifecycle::Awarded(index, amount, occurrence)));
+	}
+
+	fn on_completed(index: ProposalIndex) {
+		LIFECYCLE.with(|l| l.borrow_mut().push(Lifecycle::Completed(index)));
+		if let Some((value, beneficiary)) = SPEND_ON_COMPLETION.with(|s| *s.borrow()) {
+			assert_ok!(<Treasury as TreasurySpender>::queue_spend(value, beneficiary));
+		}
+	}
+}
+fn lifecycle() -> Vec<Lifecycle> {
+	LIFECYCLE.with(|l| l.borrow().clone())
+}
+/// Takes what `SplitToTreasury` does not give the treasury, recording each amount.
+pub struct TestOther;
+impl OnUnbalanced<NegativeImbalanceOf<Test>> for TestOther {
+	fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<Test>) {
+		OTHER_SHARES.with(|o| o.borrow_mut().push(amount.peek()));
+	}
+}
+fn other_shares() -> Vec<u64> {
+	OTHER_SHARES.with(|o| o.borrow().clone())
+}
+parameter_types! {
+	pub static TreasuryShare: Permill = Permill::from_percent(80);
+}
+type FeeSplit = SplitToTreasury<Test, TreasuryShare, TestOther>;
+/// A misbehaving `SpendFunds` which spends without deducting from the budget and inflates it.
+pub struct TestSpendFunds;
+impl SpendFunds<Test> for TestSpendFunds {
+	fn spend_funds(
+		budget_remaining: &mut u64,
+		imbalance: &mut PositiveImbalanceOf<Test>,
+		total_weight: &mut Weight,
+		_missed_any: &mut bool,
+	) {
+		*total_weight += SPEND_FUNDS_WEIGHT.with(|v| *v.borrow());
+		let unreported = SPEND_FUNDS_UNREPORTED.with(|v| *v.borrow());
+		imbalance.subsume(Balances::deposit_creating(&10, unreported));
+		*budget_remaining += SPEND_FUNDS_INFLATION.with(|v| *v.borrow());
+	}
+
+	fn spend_funds_with_context(
+		context: &SpendContext<u64, u64>,
+		budget_remaining: &mut u64,
+		imbalance: &mut PositiveImbalanceOf<Test>,
+		total_weight: &mut Weight,
+		missed_any: &mut bool,
+	) {
+		SPEND_FUNDS_CONTEXT.with(|v| *v.borrow_mut() = Some(context.clone()));
+		Self::spend_funds(budget_remaining, imbalance, total_weight, missed_any)
+	}
+}
+/// A `SpendFunds` which only counts how often it is called.
+pub struct SecondSpendFunds;
+impl SpendFunds<Test> for SecondSpendFunds {
+	fn spend_funds(
+		_budget_remaining: &mut u64,
+		_imbalance: &mut PositiveImbalanceOf<Test>,
+		_total_weight: &mut Weight,
+		_missed_any: &mut bool,
+	) {
+		SECOND_SPEND_FUNDS_CALLS.with(|v| *v.borrow_mut() += 1);
+	}
 }
 parameter_types! {
 	pub const ProposalBond: Permill = Permill::from_percent(5);
 	pub const ProposalBondMinimum: u64 = 1;
+	pub static ProposalBondMaximum: Option<u64> = None;
+	pub static MaxProposalValue: Option<Permill> = None;
+	pub static MaxPerBeneficiaryPerPeriod: Option<u64> = None;
+	pub static MaxSpendPerPeriod: Option<u64> = None;
+	pub static ApprovalTtlPeriods: Option<u32> = None;
 	pub const SpendPeriod: u64 = 2;
 	pub const Burn: Permill = Permill::from_percent(50);
 	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
@@ -107,11 +377,129 @@ parameter_types! {
 	pub const BountyCuratorDeposit: Permill = Permill::from_percent(50);
 	pub const BountyValueMinimum: u64 = 1;
 	pub const MaxApprovals: u32 = 100;
-	pub const AllowedProposalPeriod: u64 = 2;
+	pub static SpendFundsWeightLimit: Weight = Weight::MAX;
+	pub static FlagSelfProposals: bool = false;
+	pub static EnsureAffordable: bool = false;
+	pub static AllowPartialPayouts: bool = false;
+	pub const MaxOccurrences: u32 = 12;
+	pub const MaxBurnDestinations: u32 = 4;
+	pub const MaxBeneficiaries: u32 = 3;
+	pub const MaxMetadataLen: u32 = 32;
+	pub const MetadataDepositBase: u64 = 1;
+	pub const MetadataDepositPerByte: u64 = 1;
+	pub static BurnFloor: u64 = 0;
+	pub static BurnThreshold: u64 = 0;
+	pub static RejectionSlashRatio: Permill = Permill::from_percent(100);
+	pub static ReporterReward: Permill = Permill::from_percent(20);
+	pub static MaxPendingPerAccount: u32 = u32::MAX;
+	pub static ProposalCooldown: u64 = 0;
+	pub const MaxWaitingProposals: u32 = 5;
+	pub const MaxProposers: u32 = 2;
+	pub static TreasuryPayoutMode: PayoutMode = PayoutMode::Push;
+	pub static TreasuryPayoutOrdering: PayoutOrdering = PayoutOrdering::Fifo;
+	pub static TreasuryExpiredBondPolicy: ExpiredBondPolicy = ExpiredBondPolicy::Return;
+	pub static TreasuryBurnMode: BurnMode = BurnMode::Static;
+	pub const MaxReasonLength: u32 = 16;
+	pub const ProposalWithdrawalFee: Permill = Permill::from_percent(20);
+	pub const AllowedProposalPeriod: u64 = 1;
+}
+frame_support::ord_parameter_types! {
+	pub const ExemptProposer: u128 = 14;
+}
+pub struct TestSpendOrigin;
+impl frame_support::traits::EnsureOrigin<Origin> for TestSpendOrigin {
+	type Success = u64;
+	fn try_origin(o: Origin) -> Result<Self::Success, Origin> {
+		Result::<frame_system::RawOrigin<_>, Origin>::from(o).and_then(|o| match o {
+			frame_system::RawOrigin::Root => Ok(u64::max_value()),
+			frame_system::RawOrigin::Signed(10) => Ok(5),
+			frame_system::RawOrigin::Signed(11) => Ok(10),
+			frame_system::RawOrigin::Signed(12) => Ok(20),
+			frame_system::RawOrigin::Signed(13) => Ok(50),
+			r => Err(Origin::from(r)),
+		})
+	}
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		Origin::root()
+	}
 }
 impl Config for Test {
 	type PalletId = TreasuryPalletId;
 	type Currency = pallet_balances::Pallet<Test>;
+	type Beneficiary = u128;
+	type BeneficiaryLookup = IdentityLookup<u128>;
+	type NativePaymaster = CurrencyPaymaster;
+	type VestingHandler = VestingScheduleHandler<Vesting>;
+	type ApproveOrigin = frame_system::EnsureRoot<u128>;
+	type RejectOrigin = frame_system::EnsureRoot<u128>;
+	type AllowedProposalPeriod = AllowedProposalPeriod;
+	type Event = Event;
+	type OnSlash = ();
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type ProposalBondMaximum = ProposalBondMaximum;
+	type MaxProposalValue = MaxProposalValue;
+	type MaxPerBeneficiaryPerPeriod = MaxPerBeneficiaryPerPeriod;
+	type MaxSpendPerPeriod = MaxSpendPerPeriod;
+	type ApprovalTtlPeriods = ApprovalTtlPeriods;
+	type SpendPeriodOffset = ();
+	type SpendPeriod = SpendPeriod;
+	type Burn = Burn;
+	type BurnDestination = (); // Just gets burned.
+	type WeightInfo = ();
+	type SpendFunds = (TestSpendFunds, SecondSpendFunds);
+	type SpendFundsWeightLimit = SpendFundsWeightLimit;
+	type MaxApprovals = MaxApprovals;
+	type ProposalWithdrawalFee = ProposalWithdrawalFee;
+	type SpendOrigin = TestSpendOrigin;
+	type Paymaster = TestPay;
+	type SpendAssetOrigin = TestSpendOrigin;
+	type AdminOrigin = frame_system::EnsureRoot<u128>;
+	type MaxReasonLength = MaxReasonLength;
+	type PayoutMode = TreasuryPayoutMode;
+	type PayoutOrdering = TreasuryPayoutOrdering;
+	type ProposerManagementOrigin = frame_system::EnsureRoot<u128>;
+	type MaxProposers = MaxProposers;
+	type MaxWaitingProposals = MaxWaitingProposals;
+	type MaxPendingPerAccount = MaxPendingPerAccount;
+	type ProposalCooldown = ProposalCooldown;
+	type RejectionSlashRatio = RejectionSlashRatio;
+	type ReporterReward = ReporterReward;
+	type BurnFloor = BurnFloor;
+	type BurnThreshold = BurnThreshold;
+	type MaxBurnDestinations = MaxBurnDestinations;
+	type MaxBeneficiaries = MaxBeneficiaries;
+	type MaxMetadataLen = MaxMetadataLen;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type Preimages = TestPreimages;
+	type OnProposalLifecycle = TestLifecycle;
+	type BondExemptOrigin = frame_system::EnsureSignedBy<ExemptProposer, u128>;
+	type MaxOccurrences = MaxOccurrences;
+	type ExpiredBondPolicy = TreasuryExpiredBondPolicy;
+	type BurnMode = TreasuryBurnMode;
+	type FlagSelfProposals = FlagSelfProposals;
+	type EnsureAffordable = EnsureAffordable;
+	type AllowPartialPayouts = AllowPartialPayouts;
+}
+impl spender::Config for Test {
+	type Treasury = Treasury;
+	type Currency = Balances;
+	type FeeSink = TreasuryAccountId<Test>;
+}
+
+parameter_types! {
+	pub const Treasury1PalletId: PalletId = PalletId(*b"py/trsr1");
+	pub const SpendPeriodOffset1: u64 = 1;
+}
+impl Config<Instance1> for Test {
+	type PalletId = Treasury1PalletId;
+	type Currency = pallet_balances::Pallet<Test>;
+	type Beneficiary = (u32, u128);
+	type BeneficiaryLookup = IdentityLookup<(u32, u128)>;
+	type NativePaymaster = TestRemotePaymaster;
+	type VestingHandler = ();
 	type ApproveOrigin = frame_system::EnsureRoot<u128>;
 	type RejectOrigin = frame_system::EnsureRoot<u128>;
 	type AllowedProposalPeriod = AllowedProposalPeriod;
@@ -119,12 +507,127 @@ impl Config for Test {
 	type OnSlash = ();
 	type ProposalBond = ProposalBond;
 	type ProposalBondMinimum = ProposalBondMinimum;
+	type ProposalBondMaximum = ProposalBondMaximum;
+	type MaxProposalValue = MaxProposalValue;
+	type MaxPerBeneficiaryPerPeriod = MaxPerBeneficiaryPerPeriod;
+	type MaxSpendPerPeriod = MaxSpendPerPeriod;
+	type ApprovalTtlPeriods = ApprovalTtlPeriods;
+	type SpendPeriodOffset = SpendPeriodOffset1;
 	type SpendPeriod = SpendPeriod;
 	type Burn = Burn;
 	type BurnDestination = (); // Just gets burned.
 	type WeightInfo = ();
 	type SpendFunds = ();
+	type SpendFundsWeightLimit = SpendFundsWeightLimit;
+	type MaxApprovals = MaxApprovals;
+	type ProposalWithdrawalFee = ProposalWithdrawalFee;
+	type SpendOrigin = TestSpendOrigin;
+	type Paymaster = ();
+	type SpendAssetOrigin = frame_support::traits::NeverEnsureOrigin<u128>;
+	type AdminOrigin = frame_system::EnsureRoot<u128>;
+	type MaxReasonLength = MaxReasonLength;
+	type PayoutMode = TreasuryPayoutMode;
+	type PayoutOrdering = TreasuryPayoutOrdering;
+	type ProposerManagementOrigin = frame_system::EnsureRoot<u128>;
+	type MaxProposers = MaxProposers;
+	type MaxWaitingProposals = MaxWaitingProposals;
+	type MaxPendingPerAccount = MaxPendingPerAccount;
+	type ProposalCooldown = ProposalCooldown;
+	type RejectionSlashRatio = RejectionSlashRatio;
+	type ReporterReward = ReporterReward;
+	type BurnFloor = BurnFloor;
+	type BurnThreshold = BurnThreshold;
+	type MaxBurnDestinations = MaxBurnDestinations;
+	type MaxBeneficiaries = MaxBeneficiaries;
+	type MaxMetadataLen = MaxMetadataLen;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type Preimages = ();
+	type OnProposalLifecycle = ();
+	type BondExemptOrigin = frame_system::EnsureSignedBy<ExemptProposer, u128>;
+	type MaxOccurrences = MaxOccurrences;
+	type ExpiredBondPolicy = TreasuryExpiredBondPolicy;
+	type BurnMode = TreasuryBurnMode;
+	type FlagSelfProposals = FlagSelfProposals;
+	type EnsureAffordable = EnsureAffordable;
+	type AllowPartialPayouts = AllowPartialPayouts;
+}
+parameter_types! {
+	pub const Treasury2PalletId: PalletId = PalletId(*b"py/trsr2");
+	pub const SpendPeriod2: u64 = 4;
+	pub const Burn2: Permill = Permill::from_percent(10);
+}
+impl Config<Instance2> for Test {
+	type PalletId = Treasury2PalletId;
+	type Currency = pallet_balances::Pallet<Test>;
+	type Beneficiary = u128;
+	type BeneficiaryLookup = IdentityLookup<u128>;
+	type NativePaymaster = CurrencyPaymaster;
+	type VestingHandler = ();
+	type ApproveOrigin = frame_system::EnsureRoot<u128>;
+	type RejectOrigin = frame_system::EnsureRoot<u128>;
+	type AllowedProposalPeriod = AllowedProposalPeriod;
+	type Event = Event;
+	type OnSlash = ();
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type ProposalBondMaximum = ProposalBondMaximum;
+	type MaxProposalValue = MaxProposalValue;
+	type MaxPerBeneficiaryPerPeriod = MaxPerBeneficiaryPerPeriod;
+	type MaxSpendPerPeriod = MaxSpendPerPeriod;
+	type ApprovalTtlPeriods = ApprovalTtlPeriods;
+	type SpendPeriodOffset = ();
+	type SpendPeriod = SpendPeriod2;
+	type Burn = Burn2;
+	type BurnDestination = (); // Just gets burned.
+	type WeightInfo = ();
+	type SpendFunds = ();
+	type SpendFundsWeightLimit = SpendFundsWeightLimit;
 	type MaxApprovals = MaxApprovals;
+	type ProposalWithdrawalFee = ProposalWithdrawalFee;
+	type SpendOrigin = TestSpendOrigin;
+	type Paymaster = ();
+	type SpendAssetOrigin = frame_support::traits::NeverEnsureOrigin<u128>;
+	type AdminOrigin = frame_system::EnsureRoot<u128>;
+	type MaxReasonLength = MaxReasonLength;
+	type PayoutMode = TreasuryPayoutMode;
+	type PayoutOrdering = TreasuryPayoutOrdering;
+	type ProposerManagementOrigin = frame_system::EnsureRoot<u128>;
+	type MaxProposers = MaxProposers;
+	type MaxWaitingProposals = MaxWaitingProposals;
+	type MaxPendingPerAccount = MaxPendingPerAccount;
+	type ProposalCooldown = ProposalCooldown;
+	type RejectionSlashRatio = RejectionSlashRatio;
+	type ReporterReward = ReporterReward;
+	type BurnFloor = BurnFloor;
+	type BurnThreshold = BurnThreshold;
+	type MaxBurnDestinations = MaxBurnDestinations;
+	type MaxBeneficiaries = MaxBeneficiaries;
+	type MaxMetadataLen = MaxMetadataLen;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type Preimages = ();
+	type OnProposalLifecycle = ();
+	type BondExemptOrigin = frame_system::EnsureSignedBy<ExemptProposer, u128>;
+	type MaxOccurrences = MaxOccurrences;
+	type ExpiredBondPolicy = TreasuryExpiredBondPolicy;
+	type BurnMode = TreasuryBurnMode;
+	type FlagSelfProposals = FlagSelfProposals;
+	type EnsureAffordable = EnsureAffordable;
+	type AllowPartialPayouts = AllowPartialPayouts;
+}
+
+fn treasury_events() -> Vec<treasury::Event<Test>> {
+	System::events()
+		.into_iter()
+		.filter_map(|r| if let Event::Treasury(inner) = r.event { Some(inner) } else { None })
+		.collect()
+}
+
+/// Run the hooks of block `n`, with all the weight `on_idle` asks for left over.
+fn run_block(n: u64) {
+	<Treasury as OnInitialize<u64>>::on_initialize(n);
+	<Treasury as OnIdle<u64>>::on_idle(n, Weight::MAX);
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -135,7 +638,9 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
-	GenesisBuild::<Test>::assimilate_storage(&crate::GenesisConfig, &mut t).unwrap();
+	GenesisBuild::<Test>::assimilate_storage(&crate::GenesisConfig::default(), &mut t).unwrap();
+	GenesisBuild::<Test, Instance2>::assimilate_storage(&crate::GenesisConfig::default(), &mut t)
+		.unwrap();
 	t.into()
 }
 
@@ -159,7 +664,7 @@ fn minting_works() {
 #[test]
 fn spend_proposal_takes_min_deposit() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 1, 3, 1));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 1, 3, 1, 0, None, None));
 		assert_eq!(Balances::free_balance(0), 99);
 		assert_eq!(Balances::reserved_balance(0), 1);
 	});
@@ -168,7 +673,7 @@ fn spend_proposal_takes_min_deposit() {
 #[test]
 fn spend_proposal_takes_proportional_deposit() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
 		assert_eq!(Balances::free_balance(0), 95);
 		assert_eq!(Balances::reserved_balance(0), 5);
 	});
@@ -178,7 +683,7 @@ fn spend_proposal_takes_proportional_deposit() {
 fn spend_proposal_fails_when_proposer_poor() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			Treasury::propose_spend(Origin::signed(2), 100, 3, 1),
+			Treasury::propose_spend(Origin::signed(2), 100, 3, 1, 0, None, None),
 			Error::<Test, _>::InsufficientProposersBalance,
 		);
 	});
@@ -189,7 +694,7 @@ fn accepted_spend_proposal_ignored_outside_spend_period() {
 	new_test_ext().execute_with(|| {
 		Balances::make_free_balance_be(&Treasury::account_id(), 101);
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
 		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
 
 		<Treasury as OnInitialize<u64>>::on_initialize(1);
@@ -211,12 +716,44 @@ fn unused_pot_should_diminish() {
 	});
 }
 
+#[test]
+fn lifetime_totals_reconcile_with_the_pot() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		Treasury::on_unbalanced(NegativeImbalanceOf::<Test, ()>::new(50));
+		assert_eq!(Treasury::total_deposited(), 50);
+		assert_eq!(Treasury::pot(), 150);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 3, 2, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+		// 30 is awarded and half of the remaining 120 is burnt.
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Treasury::total_awarded(), 30);
+		assert_eq!(Treasury::total_burnt(), 60);
+		assert_eq!(Treasury::pot(), 60);
+
+		Treasury::on_unbalanced(NegativeImbalanceOf::<Test, ()>::new(20));
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(Treasury::total_deposited(), 70);
+		assert_eq!(Treasury::total_awarded(), 60);
+		assert_eq!(Treasury::total_burnt(), 85);
+
+		assert_eq!(Balances::free_balance(3), Treasury::total_awarded());
+		assert_eq!(
+			Treasury::pot(),
+			100 + Treasury::total_deposited() - Treasury::total_awarded() - Treasury::total_burnt()
+		);
+	});
+}
+
 #[test]
 fn rejected_spend_proposal_ignored_on_spend_period() {
 	new_test_ext().execute_with(|| {
 		Balances::make_free_balance_be(&Treasury::account_id(), 101);
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
 		assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
 
 		<Treasury as OnInitialize<u64>>::on_initialize(2);
@@ -230,23 +767,32 @@ fn reject_already_rejected_spend_proposal_fails() {
 	new_test_ext().execute_with(|| {
 		Balances::make_free_balance_be(&Treasury::account_id(), 101);
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
 		assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
-		assert_noop!(Treasury::reject_proposal(Origin::root(), 0), Error::<Test, _>::InvalidIndex);
+		assert_noop!(
+			Treasury::reject_proposal(Origin::root(), 0),
+			Error::<Test, _>::ProposalNotFound
+		);
 	});
 }
 
 #[test]
 fn reject_non_existent_spend_proposal_fails() {
 	new_test_ext().execute_with(|| {
-		assert_noop!(Treasury::reject_proposal(Origin::root(), 0), Error::<Test, _>::InvalidIndex);
+		assert_noop!(
+			Treasury::reject_proposal(Origin::root(), 0),
+			Error::<Test, _>::ProposalNotFound
+		);
 	});
 }
 
 #[test]
 fn accept_non_existent_spend_proposal_fails() {
 	new_test_ext().execute_with(|| {
-		assert_noop!(Treasury::approve_proposal(Origin::root(), 0), Error::<Test, _>::InvalidIndex);
+		assert_noop!(
+			Treasury::approve_proposal(Origin::root(), 0),
+			Error::<Test, _>::ProposalNotFound
+		);
 	});
 }
 
@@ -255,9 +801,73 @@ fn accept_already_rejected_spend_proposal_fails() {
 	new_test_ext().execute_with(|| {
 		Balances::make_free_balance_be(&Treasury::account_id(), 101);
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
 		assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
-		assert_noop!(Treasury::approve_proposal(Origin::root(), 0), Error::<Test, _>::InvalidIndex);
+		assert_noop!(
+			Treasury::approve_proposal(Origin::root(), 0),
+			Error::<Test, _>::ProposalNotFound
+		);
+	});
+}
+
+#[test]
+fn accept_already_approved_spend_proposal_fails() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 40, 3, 1, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_noop!(
+			Treasury::approve_proposal(Origin::root(), 0),
+			Error::<Test, _>::AlreadyApproved
+		);
+		assert_eq!(Treasury::approvals().into_inner(), vec![0]);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 40);
+		assert_eq!(Treasury::pot(), 30);
+
+		// A fully awarded proposal is gone and cannot be approved again.
+		assert_noop!(
+			Treasury::approve_proposal(Origin::root(), 0),
+			Error::<Test, _>::ProposalNotFound
+		);
+	});
+}
+
+#[test]
+fn proposal_lookups_report_precise_errors() {
+	new_test_ext().execute_with(|| {
+		// Outside the allowed proposal period, so the proposal waits at waiting index 0. That
+		// does not make proposal index 0 refer to it.
+		System::set_block_number(3);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 40, 3, 1, 0, None, None));
+		assert_noop!(
+			Treasury::approve_proposal(Origin::root(), 0),
+			Error::<Test, _>::ProposalNotFound
+		);
+		assert_noop!(
+			Treasury::reject_proposal(Origin::root(), 0),
+			Error::<Test, _>::ProposalNotFound
+		);
+
+		// Once promoted, it is a regular proposal that is not yet approved.
+		run_block(4);
+		assert_eq!(Treasury::proposal_count(), 1);
+		assert_noop!(
+			Treasury::remove_approval(Origin::root(), 0),
+			Error::<Test, _>::NotInApprovalQueue
+		);
+
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_noop!(
+			Treasury::approve_proposal(Origin::root(), 0),
+			Error::<Test, _>::AlreadyApproved
+		);
+		assert_noop!(
+			Treasury::approve_proposal(Origin::root(), 1),
+			Error::<Test, _>::ProposalNotFound
+		);
 	});
 }
 
@@ -267,7 +877,7 @@ fn accepted_spend_proposal_enacted_on_spend_period() {
 		Balances::make_free_balance_be(&Treasury::account_id(), 101);
 		assert_eq!(Treasury::pot(), 100);
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
 		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
 
 		<Treasury as OnInitialize<u64>>::on_initialize(2);
@@ -282,7 +892,7 @@ fn pot_underflow_should_not_diminish() {
 		Balances::make_free_balance_be(&Treasury::account_id(), 101);
 		assert_eq!(Treasury::pot(), 100);
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 150, 3, 1));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 150, 3, 1, 0, None, None));
 		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
 
 		<Treasury as OnInitialize<u64>>::on_initialize(2);
@@ -304,13 +914,29 @@ fn treasury_account_doesnt_get_deleted() {
 		assert_eq!(Treasury::pot(), 100);
 		let treasury_balance = Balances::free_balance(&Treasury::account_id());
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), treasury_balance, 3, 1));
+		assert_ok!(Treasury::propose_spend(
+			Origin::signed(0),
+			treasury_balance,
+			3,
+			1,
+			0,
+			None,
+			None
+		));
 		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
 
 		<Treasury as OnInitialize<u64>>::on_initialize(2);
 		assert_eq!(Treasury::pot(), 100); // Pot hasn't changed
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), Treasury::pot(), 3, 1));
+		assert_ok!(Treasury::propose_spend(
+			Origin::signed(0),
+			Treasury::pot(),
+			3,
+			1,
+			0,
+			None,
+			None
+		));
 		assert_ok!(Treasury::approve_proposal(Origin::root(), 1));
 
 		<Treasury as OnInitialize<u64>>::on_initialize(4);
@@ -334,9 +960,9 @@ fn inexistent_account_works() {
 		assert_eq!(Balances::free_balance(Treasury::account_id()), 0); // Account does not exist
 		assert_eq!(Treasury::pot(), 0); // Pot is empty
 
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 99, 3, 1));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 99, 3, 1, 0, None, None));
 		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 1, 3, 1));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 1, 3, 1, 0, None, None));
 		assert_ok!(Treasury::approve_proposal(Origin::root(), 1));
 		<Treasury as OnInitialize<u64>>::on_initialize(2);
 		assert_eq!(Treasury::pot(), 0); // Pot hasn't changed
@@ -363,7 +989,7 @@ fn genesis_funding_works() {
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
-	GenesisBuild::<Test>::assimilate_storage(&crate::GenesisConfig, &mut t).unwrap();
+	GenesisBuild::<Test>::assimilate_storage(&crate::GenesisConfig::default(), &mut t).unwrap();
 	let mut t: sp_io::TestExternalities = t.into();
 
 	t.execute_with(|| {
@@ -372,22 +998,4764 @@ fn genesis_funding_works() {
 	});
 }
 
+#[test]
+fn genesis_config_funds_the_pot() {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(0, 100)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let config = crate::GenesisConfig::<Test> { pot: 50, ..Default::default() };
+	GenesisBuild::<Test>::assimilate_storage(&config, &mut t).unwrap();
+	let mut t: sp_io::TestExternalities = t.into();
+
+	t.execute_with(|| {
+		assert_eq!(Treasury::pot(), 50);
+		assert_eq!(Balances::free_balance(Treasury::account_id()), 51);
+		assert_eq!(Balances::total_issuance(), 151);
+	});
+}
+
+fn genesis_with(config: crate::GenesisConfig<Test>) -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(0, 100), (1, 98), (2, 1)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	GenesisBuild::<Test>::assimilate_storage(&config, &mut t).unwrap();
+	t.into()
+}
+
+#[test]
+fn genesis_config_makes_proposals() {
+	let config =
+		crate::GenesisConfig::<Test> { pot: 100, proposals: vec![(0, 40, 3, 2), (1, 10, 4, 0)] };
+	genesis_with(config).execute_with(|| {
+		assert_eq!(Treasury::proposal_count(), 2);
+		let details = Treasury::proposal_details(0).unwrap();
+		assert_eq!((details.proposer, details.beneficiary, details.total_value), (Some(0), 3, 40));
+		assert_eq!((details.occurs, details.bond), (2, 2));
+		assert_eq!(Treasury::proposal_details(1).unwrap().occurs, 1);
+		assert_eq!(Balances::reserved_balance(0), 2);
+		assert_eq!(Balances::reserved_balance(1), 1);
+		assert_eq!(Treasury::proposals_by_proposer(0).into_inner(), vec![0]);
+		assert_eq!(Treasury::status(1), Some(ProposalStatus::Proposed));
+		assert_ok!(Treasury::do_try_state());
+
+		// They are approved and paid like any other.
+		System::set_block_number(1);
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 20);
+		assert_ok!(Treasury::reject_proposal(Origin::root(), 1));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		System::set_block_number(2);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert!(Treasury::proposals(2).is_some());
+	});
+}
+
+#[test]
+fn genesis_approvals_are_paid_in_the_first_spend_period() {
+	let config = crate::GenesisConfig::<Test> {
+		pot: 100,
+		proposals: vec![(0, 40, 3, 2), (1, 10, 4, 1), (1, 10, 5, 1)],
+		approvals: vec![1, 0],
+	};
+	genesis_with(config).execute_with(|| {
+		assert_eq!(Treasury::approvals().into_inner(), vec![1, 0]);
+		assert_eq!(Treasury::status(0), Some(ProposalStatus::Approved));
+		assert_eq!(Treasury::proposal_details(0).unwrap().approved_at, Some(0));
+		assert_eq!(Treasury::committed(), 50);
+		assert_ok!(Treasury::do_try_state());
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 20);
+		assert_eq!(Balances::free_balance(4), 10);
+		// The proposal that was not approved is left alone.
+		assert_eq!(Balances::free_balance(5), 0);
+		assert_eq!(Treasury::status(1), Some(ProposalStatus::Completed));
+		assert_eq!(Treasury::approvals().into_inner(), vec![0]);
+	});
+}
+
+#[test]
+#[should_panic(expected = "more genesis approvals than `T::MaxApprovals`")]
+fn genesis_approvals_are_limited() {
+	let proposals = (0..=MaxApprovals::get()).map(|i| (i as u128 % 2, 1, 3, 1)).collect();
+	let approvals = (0..=MaxApprovals::get()).collect();
+	genesis_with(crate::GenesisConfig::<Test> { pot: 0, proposals, approvals });
+}
+
+#[test]
+#[should_panic(expected = "genesis approval 1 is not a genesis proposal")]
+fn genesis_approvals_must_be_genesis_proposals() {
+	let config = crate::GenesisConfig::<Test> {
+		proposals: vec![(0, 40, 3, 1)],
+		approvals: vec![0, 1],
+		..Default::default()
+	};
+	genesis_with(config);
+}
+
+#[test]
+#[should_panic(expected = "the proposer of genesis proposal 1 cannot cover its bond")]
+fn genesis_config_rejects_proposals_without_bond() {
+	let config = crate::GenesisConfig::<Test> {
+		proposals: vec![(0, 40, 3, 1), (2, 40, 3, 1)],
+		..Default::default()
+	};
+	genesis_with(config);
+}
+
 #[test]
 fn max_approvals_limited() {
 	new_test_ext().execute_with(|| {
 		Balances::make_free_balance_be(&Treasury::account_id(), u64::MAX);
 		Balances::make_free_balance_be(&0, u64::MAX);
 
-		for _ in 0..MaxApprovals::get() {
-			assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1));
-			assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		for i in 0..MaxApprovals::get() {
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
+			assert_ok!(Treasury::approve_proposal(Origin::root(), i));
 		}
 
 		// One too many will fail
-		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
 		assert_noop!(
-			Treasury::approve_proposal(Origin::root(), 0),
+			Treasury::approve_proposal(Origin::root(), MaxApprovals::get()),
 			Error::<Test, _>::TooManyApprovals
 		);
 	});
 }
+
+#[test]
+fn recurring_proposal_paid_every_spend_period() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 1001);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 3, 3, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 20);
+		assert_eq!(Treasury::approvals().into_inner(), vec![0]);
+		assert_eq!(Treasury::proposals(0).unwrap().remaining_occurs, 2);
+		assert_eq!(Balances::reserved_balance(0), 2);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(Balances::free_balance(3), 40);
+		assert_eq!(Treasury::approvals().into_inner(), vec![0]);
+		assert_eq!(Balances::reserved_balance(0), 1);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(6);
+		assert_eq!(Balances::free_balance(3), 60);
+		assert!(Treasury::approvals().is_empty());
+		assert_eq!(Treasury::proposals(0), None);
+
+		// Nothing more is paid once all occurrences are exhausted.
+		<Treasury as OnInitialize<u64>>::on_initialize(8);
+		assert_eq!(Balances::free_balance(3), 60);
+		// The deposit has been fully returned.
+		assert_eq!(Balances::reserved_balance(0), 0);
+		assert_eq!(Balances::free_balance(0), 100);
+	});
+}
+
+#[test]
+fn payout_interval_is_respected_per_proposal() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 1001);
+
+		// Paid at most every 4 blocks, i.e. every other spend period.
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 40, 3, 2, 4, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		// Paid every spend period.
+		assert_ok!(Treasury::propose_spend(Origin::signed(1), 40, 4, 2, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 1));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 20);
+		assert_eq!(Balances::free_balance(4), 20);
+		assert_eq!(Treasury::proposals(0).unwrap().last_paid_at, Some(2));
+
+		System::reset_events();
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		// Only two blocks since the last payout of the first proposal.
+		assert_eq!(Balances::free_balance(3), 20);
+		assert_eq!(Balances::free_balance(4), 40);
+		assert_eq!(Treasury::approvals().into_inner(), vec![0]);
+		// A proposal that is not due does not hold back the burn.
+		assert!(treasury_events()
+			.iter()
+			.any(|event| matches!(event, treasury::Event::<Test>::Burnt(_))));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(6);
+		assert_eq!(Balances::free_balance(3), 40);
+		assert!(Treasury::approvals().is_empty());
+	});
+}
+
+#[test]
+fn paused_proposal_keeps_its_schedule() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 1001);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 3, 3, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 20);
+
+		assert_ok!(Treasury::pause_proposal(Origin::root(), 0));
+		assert_eq!(treasury_events().last(), Some(&treasury::Event::<Test>::Paused(0)));
+
+		System::reset_events();
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		<Treasury as OnInitialize<u64>>::on_initialize(6);
+		assert_eq!(Balances::free_balance(3), 20);
+		assert_eq!(Treasury::approvals().into_inner(), vec![0]);
+		assert_eq!(Treasury::proposals(0).unwrap().remaining_occurs, 2);
+		// A paused proposal does not hold back the burn.
+		assert!(treasury_events()
+			.iter()
+			.any(|event| matches!(event, treasury::Event::<Test>::Burnt(_))));
+
+		assert_ok!(Treasury::resume_proposal(Origin::root(), 0));
+		assert_eq!(treasury_events().last(), Some(&treasury::Event::<Test>::Resumed(0)));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(8);
+		<Treasury as OnInitialize<u64>>::on_initialize(10);
+		// The full original value was paid.
+		assert_eq!(Balances::free_balance(3), 60);
+		assert!(Treasury::approvals().is_empty());
+		assert_eq!(Treasury::proposals(0), None);
+		assert_eq!(Balances::reserved_balance(0), 0);
+	});
+}
+
+#[test]
+fn pause_and_resume_are_checked() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 3, 3, 0, None, None));
+
+		assert_noop!(Treasury::pause_proposal(Origin::signed(0), 0), BadOrigin);
+		assert_noop!(
+			Treasury::pause_proposal(Origin::root(), 1),
+			Error::<Test, _>::ProposalNotFound
+		);
+		assert_noop!(Treasury::resume_proposal(Origin::root(), 0), Error::<Test, _>::NotPaused);
+
+		assert_ok!(Treasury::pause_proposal(Origin::root(), 0));
+		assert_noop!(Treasury::pause_proposal(Origin::root(), 0), Error::<Test, _>::AlreadyPaused);
+		assert_noop!(Treasury::resume_proposal(Origin::signed(0), 0), BadOrigin);
+		assert_ok!(Treasury::resume_proposal(Origin::root(), 0));
+	});
+}
+
+#[test]
+fn proposal_expires_on_a_spend_block() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 1001);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 3, 3, 0, Some(4), None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 20);
+
+		// Nothing is paid from the expiry block on.
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(Balances::free_balance(3), 20);
+		assert_eq!(Treasury::proposals(0), None);
+		assert!(Treasury::approvals().is_empty());
+		assert!(treasury_events().contains(&treasury::Event::<Test>::ProposalExpired(0, 2)));
+		// The bond still held is returned.
+		assert_eq!(Balances::reserved_balance(0), 0);
+		assert_eq!(Balances::free_balance(0), 100);
+		assert_eq!(Treasury::pending_by_proposer(0), 0);
+	});
+}
+
+#[test]
+fn proposal_expires_between_spend_blocks() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 1001);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 3, 3, 0, Some(5), None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(Balances::free_balance(3), 40);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(6);
+		assert_eq!(Balances::free_balance(3), 40);
+		assert_eq!(Treasury::proposals(0), None);
+		assert!(treasury_events().contains(&treasury::Event::<Test>::ProposalExpired(0, 1)));
+	});
+}
+
+#[test]
+fn set_expiry_works_and_bond_can_be_slashed() {
+	new_test_ext().execute_with(|| {
+		TreasuryExpiredBondPolicy::set(ExpiredBondPolicy::Slash);
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 3, 3, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+		assert_noop!(Treasury::set_expiry(Origin::signed(0), 0, Some(2)), BadOrigin);
+		assert_noop!(
+			Treasury::set_expiry(Origin::root(), 1, Some(2)),
+			Error::<Test, _>::ProposalNotFound
+		);
+		assert_ok!(Treasury::set_expiry(Origin::root(), 0, Some(2)));
+		assert_eq!(treasury_events().last(), Some(&treasury::Event::<Test>::ExpirySet(0, Some(2))));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 0);
+		assert_eq!(Treasury::proposals(0), None);
+		assert!(treasury_events().contains(&treasury::Event::<Test>::ProposalExpired(0, 3)));
+		// The whole bond was slashed.
+		assert_eq!(Balances::reserved_balance(0), 0);
+		assert_eq!(Balances::free_balance(0), 97);
+	});
+}
+
+#[test]
+fn vested_award_is_released_over_the_vesting_period() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 50, 3, 1, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+		assert_noop!(Treasury::set_vesting_period(Origin::signed(0), 0, Some(10)), BadOrigin);
+		assert_noop!(
+			Treasury::set_vesting_period(Origin::root(), 0, Some(0)),
+			Error::<Test, _>::ZeroVestingPeriod
+		);
+		assert_noop!(
+			Treasury::set_vesting_period(Origin::root(), 1, Some(10)),
+			Error::<Test, _>::ProposalNotFound
+		);
+		assert_ok!(Treasury::set_vesting_period(Origin::root(), 0, Some(10)));
+		assert_eq!(
+			treasury_events().last(),
+			Some(&treasury::Event::<Test>::VestingPeriodSet(0, Some(10)))
+		);
+
+		// The award is paid in full, but locked.
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 50);
+		assert!(treasury_events().contains(&treasury::Event::<Test>::AwardVested(0, 3, 50, 2, 10)));
+		assert_eq!(<Vesting as VestingSchedule<u128>>::vesting_balance(&3), Some(50));
+		assert_eq!(Balances::locks(&3)[0].amount, 50);
+
+		// A tenth of the award is released every block.
+		System::set_block_number(6);
+		assert_eq!(<Vesting as VestingSchedule<u128>>::vesting_balance(&3), Some(30));
+		assert_ok!(Vesting::vest(Origin::signed(3)));
+		assert_eq!(Balances::locks(&3)[0].amount, 30);
+
+		// The lock is removed once the vesting period is over.
+		System::set_block_number(12);
+		assert_eq!(<Vesting as VestingSchedule<u128>>::vesting_balance(&3), Some(0));
+		assert_ok!(Vesting::vest(Origin::signed(3)));
+		assert!(Balances::locks(&3).is_empty());
+		assert_eq!(<Vesting as VestingSchedule<u128>>::vesting_balance(&3), None);
+	});
+}
+
+#[test]
+fn award_is_paid_when_it_cannot_be_vested() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury1::account_id(), 101);
+		assert_ok!(Treasury1::propose_spend(Origin::signed(0), 10, (0, 3), 1, 0, None, None));
+		assert_ok!(Treasury1::propose_spend(Origin::signed(0), 10, (7, 3), 1, 0, None, None));
+		assert_ok!(Treasury1::approve_proposal(Origin::root(), 0));
+		assert_ok!(Treasury1::approve_proposal(Origin::root(), 1));
+		assert_ok!(Treasury1::set_vesting_period(Origin::root(), 0, Some(10)));
+		assert_ok!(Treasury1::set_vesting_period(Origin::root(), 1, Some(10)));
+
+		// `Treasury1` does not support vesting, and the remote beneficiary has no account to lock.
+		<Treasury1 as OnInitialize<u64>>::on_initialize(3);
+		assert_eq!(Balances::free_balance(3), 10);
+		assert!(Balances::locks(&3).is_empty());
+		assert_eq!(sent((7, 3)), 10);
+		let events = treasury1_events();
+		assert!(events.contains(&treasury::Event::<Test, Instance1>::VestingFailed(0)));
+		assert!(!events.contains(&treasury::Event::<Test, Instance1>::VestingFailed(1)));
+	});
+}
+
+fn split_between(
+	beneficiaries: Vec<(u128, Permill)>,
+) -> BoundedVec<(u128, Permill), MaxBeneficiaries> {
+	beneficiaries.try_into().unwrap()
+}
+
+#[test]
+fn split_proposal_pays_each_share() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 1001);
+
+		let beneficiaries = split_between(vec![
+			(3, Permill::from_percent(60)),
+			(4, Permill::from_percent(30)),
+			(5, Permill::from_percent(10)),
+		]);
+		assert_ok!(Treasury::propose_spend_split(Origin::signed(0), 100, beneficiaries, 2));
+		// The bond is computed on the whole value.
+		assert_eq!(Balances::reserved_balance(0), 5);
+		let details = Treasury::proposal_details(0).unwrap();
+		assert_eq!(details.beneficiary, 3);
+		assert_eq!(
+			details.splits,
+			vec![(4, Permill::from_percent(30)), (5, Permill::from_percent(10))]
+		);
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 30);
+		assert_eq!(Balances::free_balance(4), 15);
+		assert_eq!(Balances::free_balance(5), 5);
+		let awarded: Vec<_> = treasury_events()
+			.into_iter()
+			.filter(|event| matches!(event, treasury::Event::<Test>::Awarded(..)))
+			.collect();
+		assert_eq!(
+			awarded,
+			vec![
+				treasury::Event::Awarded(0, 30, 3, 1, 1),
+				treasury::Event::Awarded(0, 15, 4, 1, 1),
+				treasury::Event::Awarded(0, 5, 5, 1, 1),
+			]
+		);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(Balances::free_balance(3), 60);
+		assert_eq!(Balances::free_balance(4), 30);
+		assert_eq!(Balances::free_balance(5), 10);
+		assert_eq!(Treasury::proposals(0), None);
+		assert_eq!(Balances::reserved_balance(0), 0);
+	});
+}
+
+#[test]
+fn split_proposal_gives_the_rest_to_the_first_beneficiary() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		// A share of 1% of 10 rounds to zero, and nobody claims the remaining 49%.
+		let beneficiaries =
+			split_between(vec![(3, Permill::from_percent(50)), (4, Permill::from_percent(1))]);
+		assert_ok!(Treasury::propose_spend_split(Origin::signed(0), 10, beneficiaries, 1));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 10);
+		assert_eq!(Balances::free_balance(4), 0);
+		let events = treasury_events();
+		assert!(events.contains(&treasury::Event::Awarded(0, 10, 3, 1, 0)));
+		// Nothing is awarded for the share that rounded to zero.
+		assert!(!events
+			.iter()
+			.any(|event| matches!(event, treasury::Event::Awarded(_, _, 4, ..))));
+	});
+}
+
+#[test]
+fn split_proposal_must_be_valid() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		let treasury = Treasury::account_id();
+
+		assert_noop!(
+			Treasury::propose_spend_split(Origin::signed(0), 10, split_between(vec![]), 1),
+			Error::<Test, _>::NoBeneficiaries
+		);
+		assert_noop!(
+			Treasury::propose_spend_split(
+				Origin::signed(0),
+				10,
+				split_between(vec![(3, Permill::from_percent(60)), (4, Permill::from_percent(50))]),
+				1
+			),
+			Error::<Test, _>::InvalidSplitShares
+		);
+		// The treasury account may be neither the first nor any other beneficiary.
+		assert_noop!(
+			Treasury::propose_spend_split(
+				Origin::signed(0),
+				10,
+				split_between(vec![(treasury, Permill::from_percent(50))]),
+				1
+			),
+			Error::<Test, _>::InvalidBeneficiary
+		);
+		assert_noop!(
+			Treasury::propose_spend_split(
+				Origin::signed(0),
+				10,
+				split_between(vec![
+					(3, Permill::from_percent(50)),
+					(treasury, Permill::from_percent(50))
+				]),
+				1
+			),
+			Error::<Test, _>::InvalidBeneficiary
+		);
+	});
+}
+
+#[test]
+fn split_proposal_is_missed_when_only_the_whole_award_is_unaffordable() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 51);
+
+		// Either share fits in the pot, but not both.
+		let beneficiaries =
+			split_between(vec![(3, Permill::from_percent(50)), (4, Permill::from_percent(50))]);
+		assert_ok!(Treasury::propose_spend_split(Origin::signed(0), 60, beneficiaries, 1));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 0);
+		assert_eq!(Balances::free_balance(4), 0);
+		let events = treasury_events();
+		assert!(events.contains(&treasury::Event::ProposalMissed(0, 60, 50)));
+		// The missed proposal holds back the burn.
+		assert!(!events.iter().any(|event| matches!(event, treasury::Event::Burnt(_))));
+		assert_eq!(Treasury::pot(), 50);
+		assert_eq!(Treasury::approvals().into_inner(), vec![0]);
+	});
+}
+
+#[test]
+fn metadata_is_set_by_the_proposer_or_approve_origin() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_eq!(Balances::reserved_balance(0), 1);
+
+		assert_noop!(
+			Treasury::set_metadata(Origin::signed(1), 0, b"cid".to_vec()),
+			Error::<Test, _>::NotProposer
+		);
+		assert_noop!(
+			Treasury::set_metadata(Origin::signed(0), 0, vec![0; 33]),
+			Error::<Test, _>::MetadataTooLong
+		);
+		assert_noop!(
+			Treasury::set_metadata(Origin::signed(0), 1, b"cid".to_vec()),
+			Error::<Test, _>::ProposalNotFound
+		);
+
+		// The proposer holds a deposit of one plus one per byte.
+		assert_ok!(Treasury::set_metadata(Origin::signed(0), 0, b"cid".to_vec()));
+		assert_eq!(Treasury::proposal_metadata(0).map(|m| m.into_inner()), Some(b"cid".to_vec()));
+		assert_eq!(Treasury::metadata_deposit(0), Some((0, 4)));
+		assert_eq!(Balances::reserved_balance(0), 5);
+		assert_eq!(treasury_events().last(), Some(&treasury::Event::<Test>::MetadataSet(0)));
+
+		// Replacing the metadata returns the previous deposit.
+		assert_ok!(Treasury::set_metadata(Origin::signed(0), 0, b"ipfs://cid".to_vec()));
+		assert_eq!(Balances::reserved_balance(0), 12);
+
+		// Empty metadata removes it.
+		assert_ok!(Treasury::set_metadata(Origin::signed(0), 0, vec![]));
+		assert_eq!(Treasury::proposal_metadata(0), None);
+		assert_eq!(Treasury::metadata_deposit(0), None);
+		assert_eq!(Balances::reserved_balance(0), 1);
+		assert_eq!(treasury_events().last(), Some(&treasury::Event::<Test>::MetadataCleared(0)));
+
+		// Once approved, only `ApproveOrigin` may change it, without a deposit.
+		assert_ok!(Treasury::set_metadata(Origin::signed(0), 0, b"cid".to_vec()));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_noop!(
+			Treasury::set_metadata(Origin::signed(0), 0, b"uri".to_vec()),
+			Error::<Test, _>::AlreadyApproved
+		);
+		assert_ok!(Treasury::set_metadata(Origin::root(), 0, b"uri".to_vec()));
+		assert_eq!(Treasury::proposal_metadata(0).map(|m| m.into_inner()), Some(b"uri".to_vec()));
+		assert_eq!(Treasury::metadata_deposit(0), None);
+		assert_eq!(Balances::reserved_balance(0), 1);
+	});
+}
+
+#[test]
+fn metadata_is_removed_with_the_proposal() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		for index in 0..4 {
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+			assert_ok!(Treasury::set_metadata(Origin::signed(0), index, b"cid".to_vec()));
+		}
+		assert_eq!(Balances::reserved_balance(0), 20);
+
+		// Rejected.
+		assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
+		// Withdrawn by the proposer.
+		assert_ok!(Treasury::withdraw_proposal(Origin::signed(0), 1));
+		// Vetoed after approval.
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 2));
+		assert_ok!(Treasury::veto_approved(Origin::root(), 2, false));
+		// Fully awarded.
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 3));
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Treasury::proposals(3), None);
+
+		let events = treasury_events();
+		for index in 0..4 {
+			assert_eq!(Treasury::proposal_metadata(index), None);
+			assert_eq!(Treasury::metadata_deposit(index), None);
+			assert!(events.contains(&treasury::Event::<Test>::MetadataCleared(index)));
+		}
+		assert_eq!(ProposalMetadata::<Test>::iter().count(), 0);
+		// Every metadata deposit and bond has been released.
+		assert_eq!(Balances::reserved_balance(0), 0);
+	});
+}
+
+#[test]
+fn description_preimage_is_requested_while_the_proposal_exists() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		let hash = H256::repeat_byte(1);
+
+		for index in 0..3 {
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, Some(hash)));
+			assert_eq!(Treasury::proposal_description(index), Some(hash));
+			assert_eq!(
+				treasury_events().last(),
+				Some(&treasury::Event::<Test>::DescriptionRequested(index, hash))
+			);
+		}
+		// Each proposal holds its own request.
+		assert_eq!(preimage_requests(hash), 3);
+		assert!(TestPreimages::is_requested(&hash));
+
+		// Approval keeps the request.
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 1));
+		assert_eq!(preimage_requests(hash), 3);
+
+		// Rejected.
+		assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
+		assert_eq!(Treasury::proposal_description(0), None);
+		assert_eq!(preimage_requests(hash), 2);
+		assert!(treasury_events().contains(&treasury::Event::<Test>::DescriptionDropped(0, hash)));
+
+		// Fully awarded.
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Treasury::proposals(1), None);
+		assert_eq!(preimage_requests(hash), 1);
+		assert!(treasury_events().contains(&treasury::Event::<Test>::DescriptionDropped(1, hash)));
+
+		// Withdrawn by the proposer.
+		assert_ok!(Treasury::withdraw_proposal(Origin::signed(0), 2));
+		assert_eq!(preimage_requests(hash), 0);
+		assert!(!TestPreimages::is_requested(&hash));
+		assert_eq!(ProposalDescriptions::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn description_of_a_waiting_proposal_follows_it() {
+	new_test_ext().execute_with(|| {
+		// Outside the allowed proposal period.
+		System::set_block_number(3);
+		let hash = H256::repeat_byte(2);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, Some(hash)));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, Some(hash)));
+		assert_eq!(Treasury::waiting_proposal_description(0), Some(hash));
+		assert_eq!(preimage_requests(hash), 2);
+
+		// A cancelled waiting proposal drops its request.
+		assert_ok!(Treasury::cancel_waiting(Origin::signed(0), 0));
+		assert_eq!(Treasury::waiting_proposal_description(0), None);
+		assert_eq!(preimage_requests(hash), 1);
+
+		// A promoted one keeps it under its new index.
+		run_block(4);
+		assert_eq!(Treasury::waiting_proposal_description(1), None);
+		assert_eq!(Treasury::proposal_description(0), Some(hash));
+		assert_eq!(preimage_requests(hash), 1);
+
+		// Proposals without a description request nothing.
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_eq!(Treasury::proposal_description(1), None);
+		assert_ok!(Treasury::reject_proposal(Origin::root(), 1));
+		assert_eq!(preimage_requests(hash), 1);
+	});
+}
+
+#[test]
+fn status_follows_a_proposal_through_its_lifecycle() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		System::set_block_number(3);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 20, 3, 2, 0, None, None));
+		assert_eq!(Treasury::waiting_status(0), Some(ProposalStatus::Waiting));
+		assert_eq!(Treasury::status(0), None);
+
+		System::set_block_number(4);
+		run_block(4);
+		assert_eq!(Treasury::waiting_status(0), None);
+		assert_eq!(Treasury::status(0), Some(ProposalStatus::Proposed));
+
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_eq!(Treasury::status(0), Some(ProposalStatus::Approved));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(6);
+		assert_eq!(
+			Treasury::status(0),
+			Some(ProposalStatus::PartiallyPaid { paid_occurrences: 1 })
+		);
+
+		// Taken out of the queue and approved again, it keeps counting what was paid.
+		assert_ok!(Treasury::remove_approval(Origin::root(), 0));
+		assert_eq!(Treasury::status(0), Some(ProposalStatus::Proposed));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_eq!(
+			Treasury::status(0),
+			Some(ProposalStatus::PartiallyPaid { paid_occurrences: 1 })
+		);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(8);
+		assert!(Treasury::proposals(0).is_none());
+		assert_eq!(Treasury::status(0), Some(ProposalStatus::Completed));
+	});
+}
+
+#[test]
+fn proposals_record_when_they_were_proposed_and_approved() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		System::set_block_number(3);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_eq!(Treasury::waiting_proposals(0).unwrap().proposed_at, 3);
+
+		// Promotion keeps the block at which it was proposed.
+		System::set_block_number(4);
+		run_block(4);
+		let details = Treasury::proposal_details(0).unwrap();
+		assert_eq!(details.proposed_at, 3);
+		assert_eq!(details.approved_at, None);
+
+		System::set_block_number(5);
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_eq!(Treasury::proposal_details(0).unwrap().approved_at, Some(5));
+
+		// Taking it out of the queue forgets the approval.
+		System::set_block_number(6);
+		assert_ok!(Treasury::remove_approval(Origin::root(), 0));
+		assert_eq!(Treasury::proposal_details(0).unwrap().approved_at, None);
+		System::set_block_number(7);
+		assert_ok!(Treasury::approve_proposals(Origin::root(), bounded_ids(vec![0])));
+		assert_eq!(Treasury::proposal_details(0).unwrap().approved_at, Some(7));
+
+		// A spend is proposed and approved at once.
+		System::set_block_number(9);
+		assert_ok!(Treasury::spend(Origin::root(), 10, 3));
+		let details = Treasury::proposal_details(1).unwrap();
+		assert_eq!((details.proposed_at, details.approved_at), (9, Some(9)));
+	});
+}
+
+#[test]
+fn status_records_rejections_and_withdrawals() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+
+		assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
+		assert_eq!(Treasury::status(0), Some(ProposalStatus::Rejected));
+
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 1));
+		assert_ok!(Treasury::veto_approved(Origin::root(), 1, false));
+		assert_eq!(Treasury::status(1), Some(ProposalStatus::Rejected));
+
+		assert_ok!(Treasury::withdraw_proposal(Origin::signed(0), 2));
+		assert_eq!(Treasury::status(2), Some(ProposalStatus::Withdrawn));
+
+		System::set_block_number(3);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_ok!(Treasury::reject_waiting_proposal(Origin::root(), 0));
+		assert_eq!(Treasury::waiting_status(0), Some(ProposalStatus::Rejected));
+		assert_ok!(Treasury::cancel_waiting(Origin::signed(0), 1));
+		assert_eq!(Treasury::waiting_status(1), Some(ProposalStatus::Withdrawn));
+	});
+}
+
+#[test]
+fn lifecycle_handler_follows_a_recurring_proposal() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 1001);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 3, 3, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_eq!(lifecycle(), vec![Lifecycle::Approved(0)]);
+
+		for block in [2, 4, 6, 8] {
+			<Treasury as OnInitialize<u64>>::on_initialize(block);
+		}
+		assert_eq!(
+			lifecycle(),
+			vec![
+				Lifecycle::Approved(0),
+				Lifecycle::Awarded(0, 20, 1),
+				Lifecycle::Awarded(0, 20, 2),
+				Lifecycle::Awarded(0, 20, 3),
+				Lifecycle::Completed(0),
+			]
+		);
+	});
+}
+
+#[test]
+fn lifecycle_handler_hears_of_rejections() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+
+		assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 1));
+		assert_ok!(Treasury::veto_approved(Origin::root(), 1, false));
+		assert_eq!(
+			lifecycle(),
+			vec![Lifecycle::Rejected(0), Lifecycle::Approved(1), Lifecycle::Rejected(1)]
+		);
+	});
+}
+
+#[test]
+fn lifecycle_handler_hears_of_expiries_and_lapses() {
+	new_test_ext().execute_with(|| {
+		ApprovalTtlPeriods::set(Some(1));
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 51);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 20, 3, 2, 0, Some(4), None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
+		assert_ok!(Treasury::approve_proposals(Origin::root(), bounded_ids(vec![0, 1])));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert!(Treasury::approvals().is_empty());
+		assert_eq!(
+			lifecycle(),
+			vec![
+				Lifecycle::Approved(0),
+				Lifecycle::Approved(1),
+				Lifecycle::Awarded(0, 10, 1),
+				Lifecycle::Rejected(0),
+				Lifecycle::Rejected(1),
+			]
+		);
+	});
+}
+
+#[test]
+fn lifecycle_handler_may_queue_spends_during_a_spend_round() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		SPEND_ON_COMPLETION.with(|s| *s.borrow_mut() = Some((10, 4)));
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Treasury::approvals().into_inner(), vec![1]);
+		assert_eq!(Treasury::committed(), 10);
+
+		SPEND_ON_COMPLETION.with(|s| *s.borrow_mut() = None);
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(Balances::free_balance(4), 10);
+	});
+}
+
+#[test]
+fn milestone_gated_proposal_waits_for_confirmation() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 1001);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 3, 3, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_ok!(Treasury::set_milestone_gated(Origin::root(), 0, true));
+
+		// Without a confirmation no funds move, and the burn is not held back.
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 0);
+		assert_eq!(Treasury::proposals(0).unwrap().remaining_occurs, 3);
+		let events = treasury_events();
+		assert!(events.contains(&treasury::Event::<Test>::AwaitingMilestone(0)));
+		assert!(events.iter().any(|event| matches!(event, treasury::Event::<Test>::Burnt(_))));
+
+		// A late confirmation is paid at the next spend period.
+		assert_ok!(Treasury::confirm_milestone(Origin::signed(3), 0));
+		assert_eq!(treasury_events().last(), Some(&treasury::Event::<Test>::MilestoneConfirmed(0)));
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(Balances::free_balance(3), 20);
+
+		// The confirmation is consumed by the payout.
+		<Treasury as OnInitialize<u64>>::on_initialize(6);
+		assert_eq!(Balances::free_balance(3), 20);
+
+		// `ApproveOrigin` may confirm as a curator.
+		assert_ok!(Treasury::confirm_milestone(Origin::root(), 0));
+		<Treasury as OnInitialize<u64>>::on_initialize(8);
+		assert_eq!(Balances::free_balance(3), 40);
+	});
+}
+
+#[test]
+fn confirm_milestone_is_checked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 3, 3, 0, None, None));
+
+		assert_noop!(
+			Treasury::confirm_milestone(Origin::signed(3), 1),
+			Error::<Test, _>::ProposalNotFound
+		);
+		assert_noop!(
+			Treasury::confirm_milestone(Origin::signed(3), 0),
+			Error::<Test, _>::NotMilestoneGated
+		);
+		assert_noop!(Treasury::set_milestone_gated(Origin::signed(0), 0, true), BadOrigin);
+		assert_ok!(Treasury::set_milestone_gated(Origin::root(), 0, true));
+
+		assert_noop!(
+			Treasury::confirm_milestone(Origin::signed(0), 0),
+			Error::<Test, _>::NotBeneficiary
+		);
+		assert_ok!(Treasury::confirm_milestone(Origin::signed(3), 0));
+		assert_noop!(
+			Treasury::confirm_milestone(Origin::signed(3), 0),
+			Error::<Test, _>::MilestoneAlreadyConfirmed
+		);
+
+		// Clearing the gate discards the pending confirmation.
+		assert_ok!(Treasury::set_milestone_gated(Origin::root(), 0, false));
+		assert!(!Treasury::proposals(0).unwrap().milestone_confirmed);
+	});
+}
+
+#[test]
+fn awarded_events_report_occurrence_progress() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 1001);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 3, 3, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 4, 1, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 1));
+
+		for block in [2, 4, 6] {
+			<Treasury as OnInitialize<u64>>::on_initialize(block);
+		}
+		let progress: Vec<_> = treasury_events()
+			.into_iter()
+			.filter(|event| {
+				matches!(
+					event,
+					treasury::Event::<Test>::Awarded(..) |
+						treasury::Event::<Test>::RecurringCompleted(_)
+				)
+			})
+			.collect();
+		assert_eq!(
+			progress,
+			vec![
+				treasury::Event::<Test>::Awarded(0, 20, 3, 1, 2),
+				// A single payout is not a recurring proposal.
+				treasury::Event::<Test>::Awarded(1, 10, 4, 1, 0),
+				treasury::Event::<Test>::Awarded(0, 20, 3, 2, 1),
+				treasury::Event::<Test>::Awarded(0, 20, 3, 3, 0),
+				treasury::Event::<Test>::RecurringCompleted(0),
+			]
+		);
+	});
+}
+
+#[test]
+fn recurring_approvals_count_towards_max_approvals() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), u64::MAX);
+		Balances::make_free_balance_be(&0, u64::MAX);
+
+		for i in 0..MaxApprovals::get() {
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 2, 0, None, None));
+			assert_ok!(Treasury::approve_proposal(Origin::root(), i));
+		}
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
+		let extra = MaxApprovals::get();
+
+		// Recurring proposals linger in the queue after their first payout.
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Treasury::approvals().len() as u32, MaxApprovals::get());
+		assert_noop!(
+			Treasury::approve_proposal(Origin::root(), extra),
+			Error::<Test, _>::TooManyApprovals
+		);
+
+		// Once every occurrence is paid the queue frees up again.
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert!(Treasury::approvals().is_empty());
+		assert_ok!(Treasury::approve_proposal(Origin::root(), extra));
+	});
+}
+
+#[test]
+fn zero_chunks_is_a_single_payout() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 0, 0, None, None));
+		let proposal = Treasury::proposals(0).unwrap();
+		assert_eq!((proposal.total_value, proposal.occurs, proposal.remaining_occurs), (10, 1, 1));
+		System::assert_last_event(
+			treasury::Event::<Test>::Proposed {
+				proposal_index: 0,
+				proposer: Some(0),
+				beneficiary: 3,
+				value: 10,
+				chunk: 10,
+				occurs: 1,
+				bond: 1,
+			}
+			.into(),
+		);
+
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 10);
+		assert_eq!(Treasury::proposals(0), None);
+		assert!(Treasury::approvals().is_empty());
+	});
+}
+
+#[test]
+fn chunks_are_stored_for_active_and_waiting_proposals() {
+	new_test_ext().execute_with(|| {
+		for (chunks, occurs) in [(0, 1), (1, 1), (4, 4)] {
+			// Inside the allowed proposal period.
+			System::set_block_number(2);
+			let index = Treasury::proposal_count();
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), 12, 3, chunks, 0, None, None));
+			let proposal = Treasury::proposals(index).unwrap();
+			assert_eq!(
+				(proposal.total_value, proposal.occurs, proposal.remaining_occurs),
+				(12, occurs, occurs)
+			);
+			System::assert_last_event(
+				treasury::Event::<Test>::Proposed {
+					proposal_index: index,
+					proposer: Some(0),
+					beneficiary: 3,
+					value: 12,
+					chunk: 12 / occurs as u64,
+					occurs,
+					bond: 1,
+				}
+				.into(),
+			);
+
+			// Outside the allowed proposal period.
+			System::set_block_number(3);
+			let index = Treasury::waiting_queue_tail();
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), 12, 3, chunks, 0, None, None));
+			let proposal = Treasury::waiting_proposals(index).unwrap();
+			assert_eq!(
+				(proposal.total_value, proposal.occurs, proposal.remaining_occurs),
+				(12, occurs, occurs)
+			);
+			System::assert_last_event(
+				treasury::Event::<Test>::WaitingProposed {
+					proposal_index: index,
+					proposer: Some(0),
+					beneficiary: 3,
+					value: 12,
+					chunk: 12 / occurs as u64,
+					occurs,
+					bond: 1,
+				}
+				.into(),
+			);
+		}
+	});
+}
+
+#[test]
+fn waiting_proposals_are_promoted_on_spend_period() {
+	new_test_ext().execute_with(|| {
+		// Outside the allowed proposal period.
+		System::set_block_number(3);
+		for value in 1..=5 {
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), value, 3, 1, 0, None, None));
+		}
+		assert_eq!(Treasury::waiting_proposal_count(), 5);
+		assert_eq!(Treasury::proposal_count(), 0);
+
+		run_block(4);
+		assert_eq!(Treasury::waiting_proposal_count(), 0);
+		assert_eq!(Treasury::proposal_count(), 5);
+		for index in 0..5 {
+			assert_eq!(Treasury::waiting_proposals(index), None);
+			assert_eq!(Treasury::proposals(index).unwrap().total_value, index as u64 + 1);
+		}
+
+		// New waiting proposals are queued after the promoted ones and are not lost.
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 6, 3, 1, 0, None, None));
+		assert_eq!(Treasury::waiting_proposal_count(), 1);
+		assert_eq!(Treasury::waiting_proposals(5).unwrap().total_value, 6);
+		run_block(6);
+		assert_eq!(Treasury::proposal_count(), 6);
+		assert_eq!(Treasury::proposals(5).unwrap().total_value, 6);
+	});
+}
+
+#[test]
+fn chunked_proposal_pays_the_full_value() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), u64::MAX);
+		Balances::make_free_balance_be(&0, 1_000);
+
+		// (value, chunks, expected payout per spend period)
+		let cases: [(u64, u32, Vec<u64>); 6] = [
+			(99, 3, vec![33, 33, 33]),
+			(100, 3, vec![33, 33, 34]),
+			(5, 3, vec![1, 1, 3]),
+			(7, 1, vec![7]),
+			(10, 4, vec![2, 2, 2, 4]),
+			(23, 12, vec![1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 12]),
+		];
+		for (value, chunks, payouts) in cases.iter() {
+			let beneficiary = 100 + *value as u128;
+			let index = Treasury::proposal_count();
+			assert_ok!(Treasury::propose_spend(
+				Origin::signed(0),
+				*value,
+				beneficiary,
+				*chunks,
+				0,
+				None,
+				None
+			));
+			assert_ok!(Treasury::approve_proposal(Origin::root(), index));
+
+			let mut paid = 0;
+			for payout in payouts {
+				<Treasury as OnInitialize<u64>>::on_initialize(2);
+				paid += payout;
+				assert_eq!(Balances::free_balance(beneficiary), paid);
+			}
+			assert_eq!(paid, *value);
+			let awarded: u64 = treasury_events()
+				.into_iter()
+				.filter_map(|event| match event {
+					treasury::Event::Awarded(i, amount, ..) if i == index => Some(amount),
+					_ => None,
+				})
+				.sum();
+			assert_eq!(awarded, *value);
+			assert_eq!(Treasury::proposals(index), None);
+			assert!(Treasury::approvals().is_empty());
+		}
+	});
+}
+
+fn bond_returned_events() -> Vec<treasury::Event<Test>> {
+	treasury_events()
+		.into_iter()
+		.filter(|event| matches!(event, treasury::Event::BondReturned(..)))
+		.collect()
+}
+
+#[test]
+fn bond_returned_event_is_emitted_once_per_proposal() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(bond_returned_events(), vec![treasury::Event::BondReturned(0, 0, 5, 0)]);
+
+		// Cancelling a waiting proposal returns its bond as well.
+		System::reset_events();
+		System::set_block_number(3);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 40, 3, 1, 0, None, None));
+		assert_ok!(Treasury::cancel_waiting(Origin::signed(0), 0));
+		assert_eq!(bond_returned_events(), vec![treasury::Event::BondReturned(0, 0, 2, 0)]);
+	});
+}
+
+#[test]
+fn bond_returned_events_follow_pro_rata_release() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), u64::MAX);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 3, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		for block in [2, 4, 6] {
+			<Treasury as OnInitialize<u64>>::on_initialize(block);
+		}
+		assert_eq!(
+			bond_returned_events(),
+			vec![
+				treasury::Event::BondReturned(0, 0, 1, 0),
+				treasury::Event::BondReturned(0, 0, 1, 0),
+				treasury::Event::BondReturned(0, 0, 3, 0),
+			]
+		);
+
+		// A bond of 1 over 3 occurrences is only released with the final one.
+		System::reset_events();
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 12, 3, 3, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 1));
+		for block in [8, 10, 12] {
+			<Treasury as OnInitialize<u64>>::on_initialize(block);
+		}
+		assert_eq!(bond_returned_events(), vec![treasury::Event::BondReturned(1, 0, 1, 0)]);
+	});
+}
+
+#[test]
+fn bond_returned_event_reports_shortfall() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		// Some of the bond was released behind the treasury's back.
+		Balances::unreserve(&0, 3);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(bond_returned_events(), vec![treasury::Event::BondReturned(0, 0, 2, 3)]);
+		assert_eq!(Balances::reserved_balance(0), 0);
+	});
+}
+
+#[test]
+fn recurring_proposal_bond_released_pro_rata() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), u64::MAX);
+
+		// Bond of 5 over 3 occurrences: 1, 1 and the remaining 3 on the final payout.
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 3, 0, None, None));
+		assert_eq!(Balances::reserved_balance(0), 5);
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::reserved_balance(0), 4);
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(Balances::reserved_balance(0), 3);
+		<Treasury as OnInitialize<u64>>::on_initialize(6);
+		assert_eq!(Balances::reserved_balance(0), 0);
+		assert_eq!(Balances::free_balance(0), 100);
+	});
+}
+
+#[test]
+fn rejecting_recurring_proposal_slashes_remaining_bond() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), u64::MAX);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 4, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::reserved_balance(0), 4);
+		assert_eq!(Balances::free_balance(0), 96);
+
+		assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
+		System::assert_last_event(
+			treasury::Event::<Test>::Rejected(0, 4, 0, Default::default()).into(),
+		);
+		assert_eq!(Balances::reserved_balance(0), 0);
+		assert_eq!(Balances::free_balance(0), 96);
+
+		// The rejected stream is not paid any more.
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(Balances::free_balance(3), 25);
+		assert!(Treasury::approvals().is_empty());
+	});
+}
+
+#[test]
+fn proposed_events_describe_the_proposal() {
+	new_test_ext().execute_with(|| {
+		// Outside the allowed proposal period.
+		System::set_block_number(3);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 3, 0, None, None));
+		System::assert_last_event(
+			treasury::Event::<Test>::WaitingProposed {
+				proposal_index: 0,
+				proposer: Some(0),
+				beneficiary: 3,
+				value: 100,
+				chunk: 33,
+				occurs: 3,
+				bond: 5,
+			}
+			.into(),
+		);
+
+		// Promotion describes the proposal again, without a storage lookup.
+		run_block(4);
+		let events = treasury_events();
+		assert!(events.contains(&treasury::Event::WaitingProposalTransfered {
+			waiting_index: 0,
+			new_proposal_index: 0,
+			proposer: Some(0),
+			beneficiary: 3,
+			value: 100,
+			chunk: 33,
+			occurs: 3,
+			bond: 5,
+		}));
+		assert!(events.contains(&treasury::Event::Proposed {
+			proposal_index: 0,
+			proposer: Some(0),
+			beneficiary: 3,
+			value: 100,
+			chunk: 33,
+			occurs: 3,
+			bond: 5,
+		}));
+
+		// The first amount of a custom schedule is reported as the chunk.
+		System::set_block_number(4);
+		assert_ok!(Treasury::propose_spend_scheduled(Origin::signed(0), schedule(vec![7, 3]), 3));
+		System::assert_last_event(
+			treasury::Event::<Test>::Proposed {
+				proposal_index: 1,
+				proposer: Some(0),
+				beneficiary: 3,
+				value: 10,
+				chunk: 7,
+				occurs: 2,
+				bond: 1,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn waiting_proposal_gaps_emit_no_events() {
+	new_test_ext().execute_with(|| {
+		// Outside the allowed proposal period.
+		System::set_block_number(3);
+		for value in 1..=3 {
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), value, 3, 1, 0, None, None));
+		}
+		<WaitingProposals<Test>>::remove(1);
+		System::reset_events();
+
+		run_block(4);
+		assert_eq!(Treasury::proposal_count(), 2);
+		assert_eq!(Treasury::proposals(0).unwrap().total_value, 1);
+		assert_eq!(Treasury::proposals(1).unwrap().total_value, 3);
+		assert_eq!(Treasury::waiting_proposal_count(), 0);
+
+		let proposed: Vec<_> = treasury_events()
+			.into_iter()
+			.filter_map(|e| match e {
+				treasury::Event::Proposed { proposal_index, .. } => Some(proposal_index),
+				_ => None,
+			})
+			.collect();
+		assert_eq!(proposed, vec![0, 1]);
+		let transferred: Vec<_> = treasury_events()
+			.into_iter()
+			.filter_map(|e| match e {
+				treasury::Event::WaitingProposalTransfered {
+					waiting_index,
+					new_proposal_index,
+					..
+				} => Some((waiting_index, new_proposal_index)),
+				_ => None,
+			})
+			.collect();
+		assert_eq!(transferred, vec![(0, 0), (2, 1)]);
+	});
+}
+
+#[test]
+fn spend_proposal_fails_when_chunk_below_existential_deposit() {
+	new_test_ext().execute_with(|| {
+		// Inside and outside the allowed proposal period.
+		for block in [2, 3] {
+			System::set_block_number(block);
+			assert_noop!(
+				Treasury::propose_spend(Origin::signed(0), 2, 3, 3, 0, None, None),
+				Error::<Test, _>::ChunkTooSmall,
+			);
+			assert_noop!(
+				Treasury::propose_spend(Origin::signed(0), 0, 3, 1, 0, None, None),
+				Error::<Test, _>::InvalidValue,
+			);
+			// Exactly the existential deposit per chunk is fine.
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), 3, 3, 3, 0, None, None));
+		}
+		assert_eq!(Treasury::proposal_count(), 1);
+		assert_eq!(Treasury::waiting_proposal_count(), 1);
+	});
+}
+
+#[test]
+fn spend_proposal_value_must_reach_existential_deposit() {
+	new_test_ext().execute_with(|| {
+		ExistentialDeposit::set(5);
+		// Inside and outside the allowed proposal period.
+		for block in [2, 3] {
+			System::set_block_number(block);
+			for value in [0, 4] {
+				assert_noop!(
+					Treasury::propose_spend(Origin::signed(0), value, 3, 1, 0, None, None),
+					Error::<Test, _>::InvalidValue,
+				);
+				assert_noop!(
+					Treasury::propose_spend_exempt(Origin::signed(14), value, 3, 1, 0, None),
+					Error::<Test, _>::InvalidValue,
+				);
+			}
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), 5, 3, 1, 0, None, None));
+		}
+		assert_eq!(Treasury::proposal_count(), 1);
+		assert_eq!(Treasury::waiting_proposal_count(), 1);
+	});
+}
+
+#[test]
+fn treasury_account_cannot_be_beneficiary() {
+	new_test_ext().execute_with(|| {
+		let treasury = Treasury::account_id();
+		// Inside and outside the allowed proposal period.
+		for block in [2, 3] {
+			System::set_block_number(block);
+			assert_noop!(
+				Treasury::propose_spend(Origin::signed(0), 10, treasury, 1, 0, None, None),
+				Error::<Test, _>::InvalidBeneficiary,
+			);
+		}
+
+		System::set_block_number(2);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_noop!(
+			Treasury::update_beneficiary(Origin::signed(0), 0, treasury),
+			Error::<Test, _>::InvalidBeneficiary,
+		);
+		assert_noop!(
+			Treasury::amend_proposal(Origin::root(), 0, 10, treasury, 1),
+			Error::<Test, _>::InvalidBeneficiary,
+		);
+		assert_noop!(
+			Treasury::spend(Origin::root(), 10, treasury),
+			Error::<Test, _>::InvalidBeneficiary,
+		);
+	});
+}
+
+#[test]
+fn self_proposals_are_flagged_if_configured() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 0, 1, 0, None, None));
+		assert!(!treasury_events()
+			.iter()
+			.any(|event| matches!(event, treasury::Event::SelfProposal(_))));
+
+		FlagSelfProposals::set(true);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert!(!treasury_events()
+			.iter()
+			.any(|event| matches!(event, treasury::Event::SelfProposal(_))));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 0, 1, 0, None, None));
+		System::assert_last_event(treasury::Event::<Test>::SelfProposal(2).into());
+
+		// Waiting proposals are flagged as well.
+		System::set_block_number(3);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 0, 1, 0, None, None));
+		System::assert_last_event(treasury::Event::<Test>::SelfProposal(0).into());
+	});
+}
+
+#[test]
+fn inflated_spend_funds_budget_is_clamped() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		SPEND_FUNDS_INFLATION.with(|v| *v.borrow_mut() = 1_000);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		// Only half of the actual pot is burnt.
+		assert_eq!(Treasury::pot(), 50);
+	});
+}
+
+#[test]
+fn unreported_spend_funds_spending_is_clamped() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		SPEND_FUNDS_UNREPORTED.with(|v| *v.borrow_mut() = 60);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(10), 60);
+		// The burn is computed on the 40 actually left over.
+		assert_eq!(Treasury::pot(), 20);
+	});
+}
+
+#[test]
+fn on_initialize_weight_accounts_for_waiting_proposals() {
+	new_test_ext().execute_with(|| {
+		let idle_weight = <Treasury as OnInitialize<u64>>::on_initialize(2);
+
+		// Outside the allowed proposal period.
+		System::set_block_number(3);
+		for _ in 0..3 {
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		}
+		// They are left for `on_idle` at the first spend block.
+		assert_eq!(<Treasury as OnInitialize<u64>>::on_initialize(4), idle_weight);
+		assert_eq!(Treasury::waiting_proposal_count(), 3);
+
+		// And promoted at the next one if `on_idle` never got to them.
+		let weight = <Treasury as OnInitialize<u64>>::on_initialize(6);
+		assert_eq!(
+			weight,
+			idle_weight + <() as WeightInfo>::on_initialize_proposals(0, 3)
+				- <() as WeightInfo>::on_initialize_proposals(0, 0)
+		);
+		assert!(weight > idle_weight);
+		assert_eq!(Treasury::waiting_proposal_count(), 0);
+	});
+}
+
+#[test]
+fn propose_spend_charges_the_weight_of_the_path_taken() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		let active = Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None).unwrap();
+		assert_eq!(active.actual_weight, Some(<() as WeightInfo>::propose_spend()));
+
+		// Outside the allowed proposal period.
+		System::set_block_number(3);
+		let waiting = Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None).unwrap();
+		assert_eq!(Treasury::waiting_proposal_count(), 1);
+		assert_eq!(waiting.actual_weight, Some(<() as WeightInfo>::propose_spend_waiting()));
+		assert_ne!(active.actual_weight, waiting.actual_weight);
+	});
+}
+
+#[test]
+fn waiting_proposals_are_promoted_as_weight_allows() {
+	new_test_ext().execute_with(|| {
+		// Outside the allowed proposal period.
+		System::set_block_number(3);
+		for value in 1..=5 {
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), value, 3, 1, 0, None, None));
+		}
+		let per_proposal = <() as WeightInfo>::on_initialize_waiting(1) -
+			<() as WeightInfo>::on_initialize_waiting(0);
+		let room_for = |n: u64| <() as WeightInfo>::on_initialize_waiting(0) + n * per_proposal;
+
+		// Nothing is promoted outside the allowed proposal period.
+		assert_eq!(<Treasury as OnIdle<u64>>::on_idle(3, Weight::MAX), 0);
+		assert_eq!(Treasury::waiting_proposal_count(), 5);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(<Treasury as OnIdle<u64>>::on_idle(4, room_for(2) - 1), room_for(1));
+		assert_eq!(Treasury::proposal_count(), 1);
+		assert_eq!(Treasury::waiting_queue_head(), 1);
+
+		// Promotion carries on from where it stopped.
+		assert_eq!(<Treasury as OnIdle<u64>>::on_idle(4, room_for(2)), room_for(2));
+		assert_eq!(Treasury::proposal_count(), 3);
+		assert_eq!(Treasury::proposals(2).unwrap().total_value, 3);
+
+		// No more than is waiting is used.
+		assert_eq!(<Treasury as OnIdle<u64>>::on_idle(4, Weight::MAX), room_for(2));
+		assert_eq!(Treasury::proposal_count(), 5);
+		assert_eq!(Treasury::waiting_proposal_count(), 0);
+		assert_eq!(<Treasury as OnIdle<u64>>::on_idle(4, room_for(0) - 1), 0);
+	});
+}
+
+#[test]
+fn waiting_proposals_fall_back_to_the_spend_block() {
+	new_test_ext().execute_with(|| {
+		// Outside the allowed proposal period.
+		System::set_block_number(3);
+		for value in 1..=3 {
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), value, 3, 1, 0, None, None));
+		}
+
+		// `on_idle` gets no weight at all in the allowed proposal period.
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(<Treasury as OnIdle<u64>>::on_idle(4, 0), 0);
+		assert_eq!(Treasury::waiting_proposal_count(), 3);
+		// Those queued since wait for `on_idle` in the next allowed proposal period.
+		System::set_block_number(5);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 4, 3, 1, 0, None, None));
+
+		System::reset_events();
+		<Treasury as OnInitialize<u64>>::on_initialize(6);
+		assert_eq!(Treasury::proposal_count(), 3);
+		assert_eq!((Treasury::waiting_queue_head(), Treasury::waiting_queue_tail()), (3, 4));
+		assert!(treasury_events().contains(&treasury::Event::WaitingProposalTransfered {
+			waiting_index: 2,
+			new_proposal_index: 2,
+			proposer: Some(0),
+			beneficiary: 3,
+			value: 3,
+			chunk: 3,
+			occurs: 1,
+			bond: 1,
+		}));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(8);
+		assert_eq!(Treasury::proposal_count(), 4);
+		assert_eq!(Treasury::waiting_proposal_count(), 0);
+	});
+}
+
+#[test]
+fn remove_approval_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		for i in 0..5 {
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+			assert_ok!(Treasury::approve_proposal(Origin::root(), i));
+		}
+
+		// First, middle and last.
+		for (index, remaining) in [(0, vec![1, 2, 3, 4]), (2, vec![1, 3, 4]), (4, vec![1, 3])] {
+			assert_ok!(Treasury::remove_approval(Origin::root(), index));
+			System::assert_last_event(treasury::Event::<Test>::ApprovalRemoved(index).into());
+			assert_eq!(Treasury::approvals().into_inner(), remaining);
+			assert!(Treasury::proposals(index).is_some());
+		}
+		assert_noop!(
+			Treasury::remove_approval(Origin::root(), 0),
+			Error::<Test, _>::NotInApprovalQueue
+		);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 20);
+		assert!(Treasury::approvals().is_empty());
+		for index in [0, 2, 4] {
+			assert!(Treasury::proposals(index).is_some());
+		}
+
+		// A removed proposal can be approved again.
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 2));
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(Balances::free_balance(3), 30);
+	});
+}
+
+#[test]
+fn withdraw_proposal_refunds_bond_minus_fee() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
+		assert_eq!(Balances::reserved_balance(0), 5);
+
+		assert_noop!(
+			Treasury::withdraw_proposal(Origin::signed(1), 0),
+			Error::<Test, _>::NotProposer
+		);
+		assert_ok!(Treasury::withdraw_proposal(Origin::signed(0), 0));
+		System::assert_last_event(treasury::Event::<Test>::Withdrawn(0, 4).into());
+		assert_eq!(Balances::reserved_balance(0), 0);
+		assert_eq!(Balances::free_balance(0), 99);
+		assert_eq!(Treasury::proposals(0), None);
+		assert_noop!(
+			Treasury::withdraw_proposal(Origin::signed(0), 0),
+			Error::<Test, _>::ProposalNotFound
+		);
+	});
+}
+
+#[test]
+fn withdraw_approved_proposal_fails() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_noop!(
+			Treasury::withdraw_proposal(Origin::signed(0), 0),
+			Error::<Test, _>::AlreadyApproved
+		);
+	});
+}
+
+#[test]
+fn reject_waiting_proposal_works() {
+	new_test_ext().execute_with(|| {
+		// Outside the allowed proposal period.
+		System::set_block_number(3);
+		for value in 1..=3 {
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), value, 3, 1, 0, None, None));
+		}
+		assert_eq!(Balances::reserved_balance(0), 3);
+
+		// Rejecting the last waiting proposal frees its index.
+		assert_ok!(Treasury::reject_waiting_proposal(Origin::root(), 2));
+		System::assert_last_event(
+			treasury::Event::<Test>::Rejected(2, 1, 0, Default::default()).into(),
+		);
+		assert_eq!(Treasury::waiting_proposal_count(), 2);
+
+		// Rejecting the oldest one moves the head of the queue past it.
+		assert_ok!(Treasury::reject_waiting_proposal(Origin::root(), 0));
+		assert_eq!(Treasury::waiting_proposal_count(), 1);
+		assert_eq!(Balances::reserved_balance(0), 1);
+		assert_eq!(Balances::free_balance(0), 98);
+		assert_noop!(
+			Treasury::reject_waiting_proposal(Origin::root(), 0),
+			Error::<Test, _>::ProposalNotFound
+		);
+
+		run_block(4);
+		assert_eq!(Treasury::proposal_count(), 1);
+		assert_eq!(Treasury::proposals(0).unwrap().total_value, 2);
+		assert_eq!(Treasury::waiting_proposal_count(), 0);
+	});
+}
+
+#[test]
+fn amend_proposal_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
+		assert_eq!(Balances::reserved_balance(0), 5);
+
+		// Fund at 60% of the ask, in two payments, to another account.
+		assert_ok!(Treasury::amend_proposal(Origin::root(), 0, 60, 4, 2));
+		System::assert_last_event(treasury::Event::<Test>::Amended(0, 100, 60).into());
+		assert_eq!(Balances::reserved_balance(0), 3);
+		assert_eq!(Balances::free_balance(0), 97);
+		let proposal = Treasury::proposals(0).unwrap();
+		assert_eq!((proposal.total_value, proposal.beneficiary, proposal.occurs), (60, 4, 2));
+
+		// Raising the value reserves more.
+		assert_ok!(Treasury::amend_proposal(Origin::root(), 0, 80, 4, 2));
+		assert_eq!(Balances::reserved_balance(0), 4);
+
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(4), 40);
+	});
+}
+
+#[test]
+fn amend_proposal_fails_cleanly() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Treasury::amend_proposal(Origin::root(), 0, 60, 3, 1),
+			Error::<Test, _>::ProposalNotFound
+		);
+
+		// Proposer 1 can only just cover the bond of the original proposal.
+		Balances::make_free_balance_be(&1, 6);
+		assert_ok!(Treasury::propose_spend(Origin::signed(1), 100, 3, 1, 0, None, None));
+		assert_noop!(
+			Treasury::amend_proposal(Origin::root(), 0, 0, 3, 1),
+			Error::<Test, _>::ChunkTooSmall
+		);
+		assert_noop!(
+			Treasury::amend_proposal(Origin::root(), 0, 1_000, 3, 1),
+			Error::<Test, _>::InsufficientProposersBalance
+		);
+		assert_noop!(
+			Treasury::amend_proposal(Origin::root(), 0, 100, 3, MaxOccurrences::get() + 1),
+			Error::<Test, _>::TooManyOccurrences
+		);
+
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_noop!(
+			Treasury::amend_proposal(Origin::root(), 0, 60, 3, 1),
+			Error::<Test, _>::AlreadyApproved
+		);
+	});
+}
+
+#[test]
+fn amend_proposal_keeps_a_proposal_without_a_bond_unbonded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Treasury::propose_spend_exempt(Origin::signed(14), 40, 3, 1, 0, None));
+		assert_ok!(Treasury::amend_proposal(Origin::root(), 0, 80, 3, 2));
+		let proposal = Treasury::proposals(0).unwrap();
+		assert_eq!((proposal.total_value, proposal.bond, proposal.remaining_bond), (80, 0, 0));
+		assert_eq!(Balances::total_balance(&14), 0);
+	});
+}
+
+#[test]
+fn amend_proposal_refuses_partly_paid_proposals() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 1001);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 3, 3, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 20);
+
+		assert_ok!(Treasury::remove_approval(Origin::root(), 0));
+		assert_noop!(
+			Treasury::amend_proposal(Origin::root(), 0, 60, 3, 3),
+			Error::<Test, _>::PartlyPaid
+		);
+	});
+}
+
+#[test]
+fn amend_proposal_respects_the_maximum_value() {
+	new_test_ext().execute_with(|| {
+		MaxProposalValue::set(Some(Permill::from_percent(50)));
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 40, 3, 1, 0, None, None));
+		assert_noop!(
+			Treasury::amend_proposal(Origin::root(), 0, 60, 3, 1),
+			Error::<Test, _>::ProposalTooLarge
+		);
+		assert_ok!(Treasury::amend_proposal(Origin::root(), 0, 50, 3, 1));
+	});
+}
+
+#[test]
+fn extend_proposal_continues_the_payout_stream() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), u64::MAX);
+
+		// Bond of 3 for 60 over 3 occurrences.
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 3, 3, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 20);
+		assert_eq!(Balances::reserved_balance(0), 2);
+
+		// Two more occurrences for 41, topping the bond up to 5 for the total of 101.
+		assert_ok!(Treasury::extend_proposal(Origin::root(), 0, 41, 2));
+		System::assert_last_event(treasury::Event::<Test>::Extended(0, 41, 2).into());
+		assert_eq!(Balances::reserved_balance(0), 4);
+		let proposal = Treasury::proposals(0).unwrap();
+		assert_eq!(
+			(proposal.total_value, proposal.bond, proposal.occurs, proposal.remaining_occurs),
+			(101, 5, 5, 4)
+		);
+		assert_eq!(proposal.schedule, Some(schedule(vec![20, 20, 20, 20, 21])));
+
+		for (block, paid) in [(4, 40), (6, 60), (8, 80), (10, 101)] {
+			<Treasury as OnInitialize<u64>>::on_initialize(block);
+			assert_eq!(Balances::free_balance(3), paid);
+		}
+		System::assert_has_event(treasury::Event::<Test>::Awarded(0, 21, 3, 5, 0).into());
+		assert_eq!(Treasury::proposals(0), None);
+		assert_eq!(Balances::reserved_balance(0), 0);
+		assert_eq!(Balances::free_balance(0), 100);
+	});
+}
+
+#[test]
+fn extend_proposal_fails_cleanly() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		assert_noop!(
+			Treasury::extend_proposal(Origin::root(), 0, 60, 1),
+			Error::<Test, _>::ProposalNotFound
+		);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 3, 3, 0, None, None));
+		assert_noop!(Treasury::extend_proposal(Origin::signed(0), 0, 60, 1), BadOrigin);
+		assert_noop!(
+			Treasury::extend_proposal(Origin::root(), 0, 1, 2),
+			Error::<Test, _>::ChunkTooSmall
+		);
+		assert_noop!(
+			Treasury::extend_proposal(Origin::root(), 0, 100, MaxOccurrences::get() - 2),
+			Error::<Test, _>::TooManyOccurrences
+		);
+		Balances::make_free_balance_be(&0, 1);
+		assert_noop!(
+			Treasury::extend_proposal(Origin::root(), 0, 1_000, 1),
+			Error::<Test, _>::InsufficientProposersBalance
+		);
+
+		// A proposal without a bond stays without one.
+		assert_ok!(Treasury::propose_spend_exempt(Origin::signed(14), 40, 3, 1, 0, None));
+		assert_ok!(Treasury::extend_proposal(Origin::root(), 1, 1_000, 1));
+		let proposal = Treasury::proposals(1).unwrap();
+		assert_eq!((proposal.total_value, proposal.bond, proposal.occurs), (1_040, 0, 2));
+		assert_eq!(Balances::total_balance(&14), 0);
+	});
+}
+
+#[test]
+fn spend_origin_permissioning_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Treasury::spend(Origin::signed(1), 1, 1), BadOrigin);
+		assert_noop!(
+			Treasury::spend(Origin::signed(10), 6, 1),
+			Error::<Test, _>::InsufficientPermission
+		);
+		assert_noop!(
+			Treasury::spend(Origin::signed(11), 11, 1),
+			Error::<Test, _>::InsufficientPermission
+		);
+		assert_noop!(
+			Treasury::spend(Origin::signed(12), 21, 1),
+			Error::<Test, _>::InsufficientPermission
+		);
+		assert_noop!(
+			Treasury::spend(Origin::signed(13), 51, 1),
+			Error::<Test, _>::InsufficientPermission
+		);
+	});
+}
+
+#[test]
+fn spend_origin_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_ok!(Treasury::spend(Origin::signed(10), 5, 6));
+		System::assert_last_event(treasury::Event::<Test>::SpendApproved(0, 5, 6).into());
+		assert_ok!(Treasury::spend(Origin::signed(10), 5, 6));
+		assert_ok!(Treasury::spend(Origin::signed(10), 5, 6));
+		assert_ok!(Treasury::spend(Origin::signed(10), 5, 6));
+		assert_ok!(Treasury::spend(Origin::signed(11), 10, 6));
+		assert_ok!(Treasury::spend(Origin::signed(12), 20, 6));
+		assert_ok!(Treasury::spend(Origin::signed(13), 50, 6));
+		assert_eq!(Treasury::approvals().len(), 7);
+		// No bond is taken from anybody.
+		assert_eq!(Balances::reserved_balance(6), 0);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(1);
+		assert_eq!(Balances::free_balance(6), 0);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(6), 100);
+		assert_eq!(Treasury::pot(), 0);
+	});
+}
+
+#[test]
+fn spends_have_no_proposer() {
+	new_test_ext().execute_with(|| {
+		MaxPendingPerAccount::set(1);
+		System::set_block_number(2);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+
+		// The beneficiary's own proposals do not hold the spend back.
+		assert_ok!(Treasury::spend(Origin::root(), 10, 0));
+		assert_eq!(Treasury::proposals(1).unwrap().proposer, None);
+		assert_eq!(Treasury::pending_by_proposer(0), 1);
+		assert_eq!(Treasury::proposals_by_proposer(0).into_inner(), vec![0]);
+		assert_eq!(Treasury::pending_by_proposer(Treasury::account_id()), 0);
+
+		// Nor can the beneficiary withdraw it once it is out of the queue.
+		assert_ok!(Treasury::remove_approval(Origin::root(), 1));
+		assert_noop!(
+			Treasury::withdraw_proposal(Origin::signed(0), 1),
+			Error::<Test, _>::NotProposer
+		);
+	});
+}
+
+#[test]
+fn fund_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(Treasury::pot(), 0);
+
+		assert_ok!(Treasury::fund(Origin::signed(0), 40));
+		System::assert_last_event(treasury::Event::<Test>::Funded(0, 40).into());
+		assert_eq!(Treasury::pot(), 40);
+		assert_eq!(Balances::free_balance(0), 60);
+
+		assert_noop!(Treasury::fund(Origin::signed(0), 0), Error::<Test, _>::ZeroAmount);
+		// The contributor's account must be kept alive.
+		assert_noop!(
+			Treasury::fund(Origin::signed(0), 60),
+			pallet_balances::Error::<Test, _>::KeepAlive
+		);
+	});
+}
+
+#[test]
+fn pot_funds_are_deactivated_issuance() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		let total_issuance = Balances::total_issuance();
+		assert_eq!(Balances::active_issuance(), total_issuance);
+
+		// Funds stop circulating once they are in the pot.
+		assert_ok!(Treasury::fund(Origin::signed(0), 50));
+		assert_eq!(Treasury::deactivated(), 50);
+		assert_eq!(Balances::active_issuance(), total_issuance - 50);
+		Treasury::on_unbalanced(Balances::issue(20));
+		assert_eq!(Balances::total_issuance(), total_issuance + 20);
+		assert_eq!(Treasury::deactivated(), 70);
+		assert_eq!(Balances::active_issuance(), total_issuance - 50);
+
+		// Awarded funds circulate again, and burnt ones are gone altogether.
+		assert_ok!(Treasury::propose_spend(Origin::signed(1), 30, 3, 1, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 30);
+		assert_eq!(Treasury::pot(), 20);
+		assert_eq!(Treasury::deactivated(), 20);
+		assert_eq!(Balances::total_issuance(), total_issuance);
+		assert_eq!(Balances::active_issuance(), total_issuance - 20);
+
+		// Funds reaching the pot some other way are picked up at the next block.
+		Balances::make_free_balance_be(&Treasury::account_id(), 41);
+		<Treasury as OnInitialize<u64>>::on_initialize(3);
+		System::assert_last_event(treasury::Event::<Test>::UpdatedInactive(20, 40).into());
+		assert_eq!(Treasury::deactivated(), 40);
+		assert_eq!(Balances::active_issuance(), Balances::total_issuance() - 40);
+	});
+}
+
+fn bounded_ids(ids: Vec<ProposalIndex>) -> BoundedVec<ProposalIndex, MaxApprovals> {
+	ids.try_into().unwrap()
+}
+
+#[test]
+fn approve_proposals_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		for _ in 0..3 {
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		}
+
+		assert_ok!(Treasury::approve_proposals(Origin::root(), bounded_ids(vec![2, 0, 1])));
+		assert_eq!(Treasury::approvals().into_inner(), vec![2, 0, 1]);
+		let approved: Vec<_> = treasury_events()
+			.into_iter()
+			.filter(|e| matches!(e, treasury::Event::Approved(_)))
+			.collect();
+		assert_eq!(
+			approved,
+			vec![
+				treasury::Event::Approved(2),
+				treasury::Event::Approved(0),
+				treasury::Event::Approved(1)
+			]
+		);
+	});
+}
+
+#[test]
+fn approve_proposals_is_all_or_nothing() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..3 {
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		}
+
+		assert_noop!(
+			Treasury::approve_proposals(Origin::root(), bounded_ids(vec![0, 3, 1])),
+			Error::<Test, _>::ProposalNotFound
+		);
+		assert_noop!(
+			Treasury::approve_proposals(Origin::root(), bounded_ids(vec![0, 1, 0])),
+			Error::<Test, _>::AlreadyApproved
+		);
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 2));
+		assert_noop!(
+			Treasury::approve_proposals(Origin::root(), bounded_ids(vec![0, 2])),
+			Error::<Test, _>::AlreadyApproved
+		);
+		assert_eq!(Treasury::approvals().into_inner(), vec![2]);
+	});
+}
+
+#[test]
+fn approve_proposals_respects_max_approvals() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&0, u64::MAX);
+		for _ in 0..MaxApprovals::get() {
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
+		}
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+		let ids: Vec<_> = (1..MaxApprovals::get()).collect();
+		assert_ok!(Treasury::approve_proposals(Origin::root(), bounded_ids(ids)));
+		assert_eq!(Treasury::approvals().len() as u32, MaxApprovals::get());
+
+		// One too many will fail.
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
+		assert_noop!(
+			Treasury::approve_proposals(Origin::root(), bounded_ids(vec![MaxApprovals::get()])),
+			Error::<Test, _>::TooManyApprovals
+		);
+	});
+}
+
+#[test]
+fn reject_proposals_is_best_effort() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		for _ in 0..3 {
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), 20, 3, 1, 0, None, None));
+		}
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 1));
+		assert_ok!(Treasury::reject_proposal(Origin::root(), 2));
+		assert_eq!(Balances::reserved_balance(0), 2);
+		System::reset_events();
+
+		assert_ok!(Treasury::reject_proposals(Origin::root(), bounded_ids(vec![0, 2, 1, 7])));
+		assert_eq!(
+			treasury_events(),
+			vec![
+				treasury::Event::Rejected(0, 1, 0, Default::default()),
+				treasury::Event::Rejected(1, 1, 0, Default::default()),
+				treasury::Event::ProposalsRejected(2, vec![2, 7]),
+			]
+		);
+		assert_eq!(Balances::reserved_balance(0), 0);
+		assert_eq!(Balances::free_balance(0), 97);
+
+		// The rejected approval is not paid.
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 0);
+		assert!(Treasury::approvals().is_empty());
+	});
+}
+
+#[test]
+fn force_spend_works() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 40, 3, 2, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+		assert_noop!(Treasury::force_spend(Origin::signed(0)), BadOrigin);
+
+		// Mid-period the first occurrence is paid and the surplus burnt.
+		System::set_block_number(1);
+		assert_ok!(Treasury::force_spend(Origin::root()));
+		assert_eq!(Balances::free_balance(3), 20);
+		assert_eq!(Treasury::pot(), 40);
+
+		// The scheduled round still runs as normal.
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 40);
+		assert_eq!(Treasury::pot(), 10);
+		assert!(Treasury::approvals().is_empty());
+	});
+}
+
+#[test]
+fn force_spend_does_not_burn_when_missing_approvals() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 150, 3, 1, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+		assert_ok!(Treasury::force_spend(Origin::root()));
+		assert_eq!(Treasury::pot(), 100);
+		assert_eq!(Treasury::approvals().into_inner(), vec![0]);
+	});
+}
+
+#[test]
+fn set_burn_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_noop!(
+			Treasury::set_burn(Origin::signed(0), Some(Permill::from_percent(10))),
+			BadOrigin
+		);
+		assert_ok!(Treasury::set_burn(Origin::root(), Some(Permill::from_percent(10))));
+		System::assert_last_event(
+			treasury::Event::<Test>::BurnSet(Some(Permill::from_percent(10))).into(),
+		);
+		assert_eq!(Treasury::burn(), Permill::from_percent(10));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Treasury::pot(), 90);
+
+		// Clearing the override restores the configured burn.
+		assert_ok!(Treasury::set_burn(Origin::root(), None));
+		assert_eq!(Treasury::burn(), Burn::get());
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(Treasury::pot(), 45);
+	});
+}
+
+#[test]
+fn set_allowed_proposal_period_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+
+		assert_noop!(Treasury::set_allowed_proposal_period(Origin::signed(0), Some(0)), BadOrigin);
+		assert_noop!(
+			Treasury::set_allowed_proposal_period(Origin::root(), Some(3)),
+			Error::<Test, _>::InvalidProposalPeriod
+		);
+
+		// A window of zero makes every proposal wait.
+		assert_ok!(Treasury::set_allowed_proposal_period(Origin::root(), Some(0)));
+		System::assert_last_event(
+			treasury::Event::<Test>::AllowedProposalPeriodSet(Some(0)).into(),
+		);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_eq!((Treasury::proposal_count(), Treasury::waiting_proposal_count()), (0, 1));
+
+		// A window equal to the spend period makes nothing wait.
+		assert_ok!(Treasury::set_allowed_proposal_period(Origin::root(), Some(2)));
+		System::set_block_number(3);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_eq!((Treasury::proposal_count(), Treasury::waiting_proposal_count()), (1, 1));
+
+		// Clearing the override restores the configured window.
+		assert_ok!(Treasury::set_allowed_proposal_period(Origin::root(), None));
+		assert_eq!(Treasury::allowed_proposal_period(), AllowedProposalPeriod::get());
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_eq!((Treasury::proposal_count(), Treasury::waiting_proposal_count()), (1, 2));
+	});
+}
+
+#[test]
+fn veto_approved_before_payout_returns_bond() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
+		assert_noop!(
+			Treasury::veto_approved(Origin::root(), 0, false),
+			Error::<Test, _>::NotInApprovalQueue
+		);
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_noop!(Treasury::veto_approved(Origin::signed(0), 0, false), BadOrigin);
+
+		assert_ok!(Treasury::veto_approved(Origin::root(), 0, false));
+		System::assert_last_event(treasury::Event::<Test>::Vetoed(0, 5, false).into());
+		assert!(Treasury::approvals().is_empty());
+		assert_eq!(Treasury::proposals(0), None);
+		assert_eq!(Balances::reserved_balance(0), 0);
+		assert_eq!(Balances::free_balance(0), 100);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 0);
+	});
+}
+
+#[test]
+fn veto_approved_mid_stream_slashes_remaining_bond() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), u64::MAX);
+
+		// Bond of 5 over 5 occurrences, 1 released per payout.
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 5, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(Balances::free_balance(3), 40);
+		assert_eq!(Balances::reserved_balance(0), 3);
+
+		assert_ok!(Treasury::veto_approved(Origin::root(), 0, true));
+		System::assert_last_event(treasury::Event::<Test>::Vetoed(0, 3, true).into());
+		assert!(Treasury::approvals().is_empty());
+		assert_eq!(Treasury::proposals(0), None);
+		assert_eq!(Balances::reserved_balance(0), 0);
+		assert_eq!(Balances::free_balance(0), 97);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(6);
+		assert_eq!(Balances::free_balance(3), 40);
+	});
+}
+
+#[test]
+fn cancel_waiting_works() {
+	new_test_ext().execute_with(|| {
+		// Outside the allowed proposal period.
+		System::set_block_number(3);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(1), 40, 3, 1, 0, None, None));
+		assert_eq!(Treasury::waiting_proposal_count(), 2);
+
+		assert_noop!(
+			Treasury::cancel_waiting(Origin::signed(0), 2),
+			Error::<Test, _>::ProposalNotFound
+		);
+		assert_noop!(Treasury::cancel_waiting(Origin::signed(1), 0), Error::<Test, _>::NotProposer);
+
+		// Cancelling the last entry leaves a gap: its position is not given out again.
+		assert_ok!(Treasury::cancel_waiting(Origin::signed(1), 1));
+		System::assert_last_event(treasury::Event::<Test>::WaitingProposalCancelled(1, 2).into());
+		assert_eq!((Treasury::waiting_queue_head(), Treasury::waiting_queue_tail()), (0, 2));
+		assert_eq!(Treasury::waiting_status(1), Some(ProposalStatus::Withdrawn));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 98);
+
+		// No withdrawal fee is slashed: the whole bond comes back.
+		assert_ok!(Treasury::cancel_waiting(Origin::signed(0), 0));
+		System::assert_last_event(treasury::Event::<Test>::WaitingProposalCancelled(0, 5).into());
+		assert_eq!((Treasury::waiting_queue_head(), Treasury::waiting_queue_tail()), (1, 2));
+		assert_eq!(Balances::reserved_balance(0), 0);
+		assert_eq!(Balances::free_balance(0), 100);
+		assert_noop!(
+			Treasury::cancel_waiting(Origin::signed(0), 0),
+			Error::<Test, _>::ProposalNotFound
+		);
+	});
+}
+
+#[test]
+fn cancelled_waiting_proposal_is_not_promoted() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(3);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 40, 3, 1, 0, None, None));
+		assert_ok!(Treasury::cancel_waiting(Origin::signed(0), 0));
+		assert_eq!(Treasury::waiting_proposal_count(), 1);
+
+		run_block(4);
+		assert_eq!(Treasury::proposal_count(), 1);
+		assert_eq!(Treasury::proposals(0).unwrap().total_value, 40);
+		assert_eq!(Treasury::waiting_proposal_count(), 0);
+	});
+}
+
+#[test]
+fn reject_proposal_with_reason_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
+
+		assert_noop!(
+			Treasury::reject_proposal_with_reason(Origin::root(), 0, vec![0; 17]),
+			Error::<Test, _>::ReasonTooLong
+		);
+		assert_noop!(
+			Treasury::reject_proposal_with_reason(Origin::signed(0), 0, b"spam".to_vec()),
+			BadOrigin
+		);
+
+		assert_ok!(Treasury::reject_proposal_with_reason(Origin::root(), 0, b"spam".to_vec()));
+		System::assert_last_event(
+			treasury::Event::<Test>::Rejected(0, 5, 0, b"spam".to_vec().try_into().unwrap()).into(),
+		);
+		assert_eq!(Treasury::proposals(0), None);
+		assert_eq!(Balances::reserved_balance(0), 0);
+		assert_eq!(Balances::free_balance(0), 95);
+	});
+}
+
+#[test]
+fn update_beneficiary_by_proposer_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
+
+		assert_noop!(
+			Treasury::update_beneficiary(Origin::signed(0), 1, 4),
+			Error::<Test, _>::ProposalNotFound
+		);
+		assert_noop!(
+			Treasury::update_beneficiary(Origin::signed(1), 0, 4),
+			Error::<Test, _>::NotProposer
+		);
+
+		assert_ok!(Treasury::update_beneficiary(Origin::signed(0), 0, 4));
+		System::assert_last_event(treasury::Event::<Test>::BeneficiaryUpdated(0, 3, 4).into());
+		assert_eq!(Treasury::proposals(0).unwrap().beneficiary, 4);
+
+		// Once approved, only the approve origin can redirect the proposal.
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_noop!(
+			Treasury::update_beneficiary(Origin::signed(0), 0, 5),
+			Error::<Test, _>::AlreadyApproved
+		);
+	});
+}
+
+#[test]
+fn update_beneficiary_redirects_recurring_proposal_mid_stream() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), u64::MAX);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 3, 3, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 20);
+
+		assert_ok!(Treasury::update_beneficiary(Origin::root(), 0, 4));
+		System::assert_last_event(treasury::Event::<Test>::BeneficiaryUpdated(0, 3, 4).into());
+
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		<Treasury as OnInitialize<u64>>::on_initialize(6);
+		assert_eq!(Balances::free_balance(3), 20);
+		assert_eq!(Balances::free_balance(4), 40);
+		assert_eq!(Treasury::proposals(0), None);
+
+		// Fully paid proposals can no longer be redirected.
+		assert_noop!(
+			Treasury::update_beneficiary(Origin::root(), 0, 3),
+			Error::<Test, _>::ProposalNotFound
+		);
+	});
+}
+
+#[test]
+fn pull_payouts_accumulate_until_claimed() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		TreasuryPayoutMode::set(PayoutMode::Pull);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_noop!(Treasury::claim_payout(Origin::signed(3)), Error::<Test, _>::NoPendingPayout);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 30, 3, 1, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 20, 3, 1, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 1));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 0);
+		assert_eq!(Treasury::pending_payouts(3), 50);
+		assert_eq!(Treasury::total_pending_payouts(), 50);
+		// Pending payouts are kept out of the pot and are not burnt.
+		assert_eq!(Balances::free_balance(Treasury::account_id()), 76);
+		assert_eq!(Treasury::pot(), 25);
+
+		assert_ok!(Treasury::claim_payout(Origin::signed(3)));
+		System::assert_last_event(treasury::Event::<Test>::PayoutClaimed(3, 50).into());
+		assert_eq!(Balances::free_balance(3), 50);
+		assert_eq!(Treasury::pending_payouts(3), 0);
+		assert_eq!(Treasury::total_pending_payouts(), 0);
+		assert_eq!(Treasury::pot(), 25);
+		assert_noop!(Treasury::claim_payout(Origin::signed(3)), Error::<Test, _>::NoPendingPayout);
+	});
+}
+
+#[test]
+fn pull_payouts_can_be_claimed_across_spend_periods() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		TreasuryPayoutMode::set(PayoutMode::Pull);
+		Balances::make_free_balance_be(&Treasury::account_id(), 1001);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 3, 3, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_ok!(Treasury::claim_payout(Origin::signed(3)));
+		assert_eq!(Balances::free_balance(3), 20);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		<Treasury as OnInitialize<u64>>::on_initialize(6);
+		assert_eq!(Treasury::pending_payouts(3), 40);
+		assert_eq!(Balances::free_balance(3), 20);
+
+		assert_ok!(Treasury::claim_payout(Origin::signed(3)));
+		System::assert_last_event(treasury::Event::<Test>::PayoutClaimed(3, 40).into());
+		assert_eq!(Balances::free_balance(3), 60);
+		assert_eq!(Treasury::total_pending_payouts(), 0);
+	});
+}
+
+#[test]
+fn pull_payouts_are_partially_claimed_when_pot_is_short() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		TreasuryPayoutMode::set(PayoutMode::Pull);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 50, 3, 1, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Treasury::pending_payouts(3), 50);
+
+		// Something drained the treasury account below what is owed.
+		Balances::make_free_balance_be(&Treasury::account_id(), 21);
+		assert_ok!(Treasury::claim_payout(Origin::signed(3)));
+		System::assert_last_event(treasury::Event::<Test>::PayoutClaimed(3, 20).into());
+		assert_eq!(Balances::free_balance(3), 20);
+		// The treasury account is kept alive.
+		assert_eq!(Balances::free_balance(Treasury::account_id()), 1);
+		assert_eq!(Treasury::pending_payouts(3), 30);
+		assert_eq!(Treasury::total_pending_payouts(), 30);
+
+		assert_noop!(Treasury::claim_payout(Origin::signed(3)), Error::<Test, _>::InsufficientPot);
+	});
+}
+
+#[test]
+fn reprioritize_approval_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 70, 3, 1, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 40, 4, 1, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 1));
+
+		assert_noop!(
+			Treasury::reprioritize_approval(Origin::root(), 2, 0),
+			Error::<Test, _>::NotInApprovalQueue
+		);
+		assert_noop!(Treasury::reprioritize_approval(Origin::signed(0), 1, 0), BadOrigin);
+
+		// Positions are clamped to the end of the queue.
+		assert_ok!(Treasury::reprioritize_approval(Origin::root(), 0, 10));
+		System::assert_last_event(treasury::Event::<Test>::ApprovalReprioritized(0, 0, 1).into());
+		assert_eq!(Treasury::approvals().into_inner(), vec![1, 0]);
+
+		// The small proposal is now paid first, and the large one no longer fits.
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(4), 40);
+		assert_eq!(Balances::free_balance(3), 0);
+		assert_eq!(Treasury::approvals().into_inner(), vec![0]);
+	});
+}
+
+#[test]
+fn approvals_are_paid_in_approval_order() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 1001);
+
+		for beneficiary in 3..7 {
+			assert_ok!(Treasury::propose_spend(
+				Origin::signed(0),
+				10,
+				beneficiary,
+				1,
+				0,
+				None,
+				None
+			));
+		}
+		assert_ok!(Treasury::approve_proposals(Origin::root(), bounded_ids(vec![3, 1])));
+		System::set_block_number(3);
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 2));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_eq!(Treasury::approved_at(1), Some(2));
+		assert_eq!(Treasury::approved_at(0), Some(3));
+
+		// Removing from the middle leaves the others in the same order.
+		assert_ok!(Treasury::remove_approval(Origin::root(), 3));
+		assert_eq!(Treasury::approvals().into_inner(), vec![1, 2, 0]);
+
+		// Earlier approvals first, then the lower index among those approved in the same block.
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		let awarded: Vec<_> = treasury_events()
+			.into_iter()
+			.filter_map(|event| match event {
+				treasury::Event::Awarded(index, ..) => Some(index),
+				_ => None,
+			})
+			.collect();
+		assert_eq!(awarded, vec![1, 0, 2]);
+		assert!(Treasury::approvals().is_empty());
+	});
+}
+
+#[test]
+fn reprioritize_approval_adjusts_approval_block() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		for _ in 0..3 {
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		}
+		assert_ok!(Treasury::approve_proposals(Origin::root(), bounded_ids(vec![0, 1])));
+		System::set_block_number(4);
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 2));
+
+		// Proposals approved in the same block are paid in index order.
+		assert_noop!(
+			Treasury::reprioritize_approval(Origin::root(), 2, 1),
+			Error::<Test, _>::CannotReprioritize
+		);
+
+		// Moving to the front takes the latest block that is still ahead.
+		assert_ok!(Treasury::reprioritize_approval(Origin::root(), 2, 0));
+		assert_eq!(Treasury::approvals().into_inner(), vec![2, 0, 1]);
+		assert_eq!(Treasury::approved_at(2), Some(1));
+
+		// Moving to the back takes the earliest block that is still behind.
+		assert_ok!(Treasury::reprioritize_approval(Origin::root(), 0, 2));
+		System::assert_last_event(treasury::Event::<Test>::ApprovalReprioritized(0, 1, 2).into());
+		assert_eq!(Treasury::approvals().into_inner(), vec![2, 1, 0]);
+		assert_eq!(Treasury::approved_at(0), Some(3));
+	});
+}
+
+#[test]
+fn higher_priority_approvals_are_paid_first() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		for beneficiary in 3..6 {
+			assert_ok!(Treasury::propose_spend(
+				Origin::signed(0),
+				60,
+				beneficiary,
+				1,
+				0,
+				None,
+				None
+			));
+		}
+		assert_ok!(Treasury::approve_proposals(Origin::root(), bounded_ids(vec![0, 1, 2])));
+
+		assert_noop!(Treasury::set_priority(Origin::signed(0), 2, 1), BadOrigin);
+		assert_noop!(
+			Treasury::set_priority(Origin::root(), 3, 1),
+			Error::<Test, _>::NotInApprovalQueue
+		);
+		assert_ok!(Treasury::set_priority(Origin::root(), 2, 1));
+		System::assert_last_event(treasury::Event::<Test>::ApprovalPrioritySet(2, 1).into());
+		assert_eq!(Treasury::approval_priority(2), 1);
+		assert_eq!(Treasury::approval_queue(), vec![2, 0, 1]);
+
+		// Approvals are not moved out of their priority class.
+		assert_noop!(
+			Treasury::reprioritize_approval(Origin::root(), 0, 0),
+			Error::<Test, _>::CannotReprioritize
+		);
+
+		// Only one approval fits in the pot, and the last one is paid as it ranks highest.
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(5), 60);
+		assert_eq!(Balances::free_balance(3), 0);
+		assert_eq!(Balances::free_balance(4), 0);
+		assert_eq!(Treasury::approvals().into_inner(), vec![0, 1]);
+		assert_eq!(Treasury::approval_priority(2), 0);
+
+		assert_ok!(Treasury::set_priority(Origin::root(), 1, 3));
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(Balances::free_balance(4), 60);
+		assert_eq!(Balances::free_balance(3), 0);
+		assert_eq!(Treasury::approvals().into_inner(), vec![0]);
+
+		// Within the default class approvals are paid in order, as before.
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		<Treasury as OnInitialize<u64>>::on_initialize(6);
+		assert_eq!(Balances::free_balance(3), 60);
+		assert!(Treasury::approvals().is_empty());
+	});
+}
+
+#[test]
+fn payout_orderings_compared_on_the_same_queue() {
+	// The awarded proposals and whether the surplus was burnt, for each of two spend periods.
+	let run = |ordering: PayoutOrdering| {
+		new_test_ext().execute_with(|| {
+			TreasuryPayoutOrdering::set(ordering);
+			System::set_block_number(2);
+			// The first proposal is larger than the whole pot.
+			for (value, beneficiary) in &[(150, 3), (60, 4), (30, 5), (20, 6), (20, 7)] {
+				assert_ok!(Treasury::propose_spend(
+					Origin::signed(0),
+					*value,
+					*beneficiary,
+					1,
+					0,
+					None,
+					None
+				));
+			}
+			assert_ok!(Treasury::approve_proposals(
+				Origin::root(),
+				bounded_ids(vec![0, 1, 2, 3, 4])
+			));
+
+			let mut periods = vec![];
+			for now in &[2, 4] {
+				Balances::make_free_balance_be(&Treasury::account_id(), 101);
+				System::reset_events();
+				<Treasury as OnInitialize<u64>>::on_initialize(*now);
+				let events = treasury_events();
+				let awarded: Vec<_> = events
+					.iter()
+					.filter_map(|event| match event {
+						treasury::Event::Awarded(index, ..) => Some(*index),
+						_ => None,
+					})
+					.collect();
+				let burnt = events.iter().any(|event| matches!(event, treasury::Event::Burnt(_)));
+				periods.push((awarded, burnt));
+			}
+			periods
+		})
+	};
+
+	// The proposals behind the oversized one are paid in order until the pot runs out, and the
+	// oversized one holds back the burn in both periods.
+	assert_eq!(run(PayoutOrdering::Fifo), vec![(vec![1, 2], false), (vec![3, 4], false)]);
+	// More proposals are paid in the first period, and once only the oversized one is left
+	// unpaid the surplus is burnt.
+	assert_eq!(run(PayoutOrdering::SmallestFirst), vec![(vec![3, 4, 2], false), (vec![1], true)]);
+}
+
+#[test]
+fn proposer_allowlist_restricts_proposals() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+
+		// Open to everyone while the allowlist is empty.
+		assert_ok!(Treasury::propose_spend(Origin::signed(1), 10, 3, 1, 0, None, None));
+
+		assert_noop!(Treasury::add_proposer(Origin::signed(0), 0), BadOrigin);
+		assert_ok!(Treasury::add_proposer(Origin::root(), 0));
+		System::assert_last_event(treasury::Event::<Test>::ProposerAdded(0).into());
+		assert_noop!(
+			Treasury::add_proposer(Origin::root(), 0),
+			Error::<Test, _>::AlreadyApprovedProposer
+		);
+		assert_eq!(Treasury::approved_proposer_count(), 1);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_noop!(
+			Treasury::propose_spend(Origin::signed(1), 10, 3, 1, 0, None, None),
+			Error::<Test, _>::NotAllowedToPropose
+		);
+
+		assert_ok!(Treasury::add_proposer(Origin::root(), 1));
+		assert_noop!(Treasury::add_proposer(Origin::root(), 2), Error::<Test, _>::TooManyProposers);
+		assert_ok!(Treasury::propose_spend(Origin::signed(1), 10, 3, 1, 0, None, None));
+	});
+}
+
+#[test]
+fn temporary_ban_expires() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		assert_noop!(Treasury::ban_proposer(Origin::signed(1), 0, Some(6)), BadOrigin);
+		assert_noop!(
+			Treasury::ban_proposer(Origin::root(), 0, Some(2)),
+			Error::<Test, _>::BanAlreadyExpired
+		);
+		assert_ok!(Treasury::ban_proposer(Origin::root(), 0, Some(6)));
+		System::assert_last_event(treasury::Event::<Test>::ProposerBanned(0, Some(6)).into());
+
+		assert_noop!(
+			Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None),
+			Error::<Test, _>::ProposerBanned
+		);
+		let beneficiaries = split_between(vec![(3, Permill::from_percent(50))]);
+		assert_noop!(
+			Treasury::propose_spend_split(Origin::signed(0), 10, beneficiaries, 1),
+			Error::<Test, _>::ProposerBanned
+		);
+		// Others are not affected.
+		assert_ok!(Treasury::propose_spend(Origin::signed(1), 10, 3, 1, 0, None, None));
+
+		// The ban is lifted at its expiry block, and cleaned up by the next proposal.
+		System::set_block_number(6);
+		assert_eq!(Treasury::banned_until(0), Some(Some(6)));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_eq!(Treasury::banned_until(0), None);
+	});
+}
+
+#[test]
+fn permanent_ban_lasts_until_lifted() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		assert_ok!(Treasury::ban_proposer(Origin::root(), 0, None));
+		System::assert_last_event(treasury::Event::<Test>::ProposerBanned(0, None).into());
+
+		System::set_block_number(1_000);
+		assert_noop!(
+			Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None),
+			Error::<Test, _>::ProposerBanned
+		);
+
+		assert_noop!(Treasury::unban_proposer(Origin::signed(1), 0), BadOrigin);
+		assert_ok!(Treasury::unban_proposer(Origin::root(), 0));
+		System::assert_last_event(treasury::Event::<Test>::ProposerUnbanned(0).into());
+		assert_noop!(Treasury::unban_proposer(Origin::root(), 0), Error::<Test, _>::NotBanned);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+	});
+}
+
+#[test]
+fn proposals_are_spaced_by_the_cooldown() {
+	new_test_ext().execute_with(|| {
+		ProposalCooldown::set(3);
+		System::set_block_number(2);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_eq!(Treasury::last_proposal_block(0), Some(2));
+
+		System::set_block_number(4);
+		assert_noop!(
+			Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None),
+			Error::<Test, _>::CooldownActive
+		);
+		// Others are not affected.
+		assert_ok!(Treasury::propose_spend(Origin::signed(1), 10, 3, 1, 0, None, None));
+
+		// Outside the allowed proposal period the proposal waits, and still restarts the
+		// cooldown.
+		System::set_block_number(5);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_eq!(Treasury::waiting_proposal_count(), 1);
+		assert_eq!(Treasury::last_proposal_block(0), Some(5));
+
+		System::set_block_number(7);
+		assert_noop!(
+			Treasury::propose_spend_scheduled(Origin::signed(0), schedule(vec![7, 3]), 3),
+			Error::<Test, _>::CooldownActive
+		);
+		System::set_block_number(8);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+	});
+}
+
+#[test]
+fn cooldown_survives_rejection_and_withdrawal() {
+	new_test_ext().execute_with(|| {
+		ProposalCooldown::set(3);
+		System::set_block_number(2);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_ok!(Treasury::withdraw_proposal(Origin::signed(0), 0));
+		assert_noop!(
+			Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None),
+			Error::<Test, _>::CooldownActive
+		);
+
+		System::set_block_number(6);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_ok!(Treasury::reject_proposal(Origin::root(), 1));
+		System::set_block_number(8);
+		assert_noop!(
+			Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None),
+			Error::<Test, _>::CooldownActive
+		);
+	});
+}
+
+#[test]
+fn removing_last_proposer_reopens_proposals() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		assert_ok!(Treasury::add_proposer(Origin::root(), 0));
+		assert_noop!(
+			Treasury::propose_spend(Origin::signed(1), 10, 3, 1, 0, None, None),
+			Error::<Test, _>::NotAllowedToPropose
+		);
+
+		assert_noop!(
+			Treasury::remove_proposer(Origin::root(), 1),
+			Error::<Test, _>::NotApprovedProposer
+		);
+		assert_ok!(Treasury::remove_proposer(Origin::root(), 0));
+		System::assert_last_event(treasury::Event::<Test>::ProposerRemoved(0).into());
+		assert_eq!(Treasury::approved_proposer_count(), 0);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(1), 10, 3, 1, 0, None, None));
+	});
+}
+
+#[test]
+fn propose_spend_bond_is_capped_by_maximum() {
+	new_test_ext().execute_with(|| {
+		// 5% of 100 is exactly the maximum.
+		ProposalBondMaximum::set(Some(5));
+		System::set_block_number(2);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
+		assert_eq!(Balances::reserved_balance(0), 5);
+
+		// Above the maximum the bond is capped, for waiting proposals as well.
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 200, 3, 1, 0, None, None));
+		assert_eq!(Balances::reserved_balance(0), 10);
+		System::set_block_number(3);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 1_000, 3, 1, 0, None, None));
+		assert_eq!(Treasury::waiting_proposals(0).unwrap().bond, 5);
+		assert_eq!(Balances::reserved_balance(0), 15);
+	});
+}
+
+#[test]
+fn propose_spend_bond_is_uncapped_without_maximum() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&0, 1_000);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 1_000, 3, 1, 0, None, None));
+		assert_eq!(Balances::reserved_balance(0), 50);
+	});
+}
+
+#[test]
+fn waiting_proposals_are_limited() {
+	new_test_ext().execute_with(|| {
+		// Outside the allowed proposal period.
+		System::set_block_number(3);
+		for _ in 0..MaxWaitingProposals::get() {
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		}
+		assert_noop!(
+			Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None),
+			Error::<Test, _>::TooManyWaitingProposals
+		);
+
+		// Promotion frees up the queue again.
+		run_block(4);
+		assert_eq!(Treasury::proposal_count(), MaxWaitingProposals::get());
+		System::set_block_number(5);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_eq!(Treasury::waiting_proposal_count(), 1);
+	});
+}
+
+#[test]
+fn failed_propose_spend_rolls_back_the_bond() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(3);
+		for _ in 0..MaxWaitingProposals::get() {
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		}
+
+		// The full queue is only noticed after the bond is reserved.
+		assert_noop!(
+			Treasury::propose_spend(Origin::signed(1), 100, 3, 1, 0, None, None),
+			Error::<Test, _>::TooManyWaitingProposals
+		);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 98);
+		assert_eq!(Treasury::pending_by_proposer(1), 0);
+	});
+}
+
+#[test]
+fn api_helpers_report_treasury_state() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 40, 3, 2, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 4, 1, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 1));
+		// Outside the allowed proposal period.
+		System::set_block_number(3);
+		assert_ok!(Treasury::propose_spend(Origin::signed(1), 6, 3, 1, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(1), 7, 3, 1, 0, None, None));
+		assert_ok!(Treasury::cancel_waiting(Origin::signed(1), 0));
+
+		assert_eq!(Treasury::pot(), 100);
+		let details = ProposalDetails {
+			proposer: Some(0),
+			beneficiary: 3,
+			total_value: 40,
+			next_award: 20,
+			bond: 2,
+			remaining_bond: 2,
+			occurs: 2,
+			remaining_occurs: 2,
+			interval: 0,
+			last_paid_at: None,
+			paused: false,
+			schedule: None,
+			expires_at: None,
+			milestone_gated: false,
+			milestone_confirmed: false,
+			vesting_period: None,
+			splits: vec![],
+			proposed_at: 2,
+			approved_at: None,
+		};
+		assert_eq!(Treasury::proposal_details(0), Some(details.clone()));
+		assert_eq!(Treasury::proposal_details(2), None);
+
+		let proposals = Treasury::all_proposals();
+		assert_eq!(proposals.iter().map(|(index, _)| *index).collect::<Vec<_>>(), vec![0, 1]);
+		assert_eq!(proposals[0].1, details);
+		assert_eq!(proposals[1].1.beneficiary, 4);
+
+		let waiting = Treasury::all_waiting_proposals();
+		assert_eq!(waiting.len(), 1);
+		assert_eq!((waiting[0].0, waiting[0].1.total_value), (1, 7));
+		assert_eq!(Treasury::approval_queue(), vec![1]);
+	});
+}
+
+#[test]
+fn proposals_are_listed_by_proposer() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 1001);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 40, 3, 2, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(1), 10, 3, 1, 0, None, None));
+		assert_eq!(Treasury::proposals_by_proposer(0).into_inner(), vec![0, 1, 2]);
+		assert_eq!(Treasury::proposals_by_proposer(1).into_inner(), vec![3]);
+
+		assert_ok!(Treasury::reject_proposal(Origin::root(), 2));
+		assert_eq!(Treasury::proposals_by_proposer(0).into_inner(), vec![0, 1]);
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 1));
+
+		// Outside the allowed proposal period.
+		System::set_block_number(3);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_eq!(Treasury::waiting_proposals_by_proposer(0).into_inner(), vec![0, 1]);
+		assert_ok!(Treasury::cancel_waiting(Origin::signed(0), 1));
+		assert_eq!(Treasury::waiting_proposals_by_proposer(0).into_inner(), vec![0]);
+
+		// The single payout completes, the recurring one goes on and the waiting proposal is
+		// promoted.
+		run_block(4);
+		assert_eq!(Treasury::proposals_by_proposer(0).into_inner(), vec![0, 4]);
+		assert!(!<WaitingProposalsByProposer<Test>>::contains_key(0));
+
+		// The recurring proposal completes.
+		run_block(6);
+		assert_eq!(Treasury::proposals_by_proposer(0).into_inner(), vec![4]);
+
+		assert_ok!(Treasury::reject_proposal(Origin::root(), 4));
+		assert!(!<ProposalsByProposer<Test>>::contains_key(0));
+	});
+}
+
+#[test]
+fn committed_tracks_approved_proposals() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 1001);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 3, 3, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_eq!(Treasury::committed(), 0);
+		assert_eq!(Treasury::uncommitted_pot(), 1000);
+
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_eq!(Treasury::committed(), 60);
+		assert_eq!(Treasury::uncommitted_pot(), 940);
+		assert_ok!(Treasury::approve_proposals(Origin::root(), bounded_ids(vec![1, 2])));
+		assert_eq!(Treasury::committed(), 80);
+		assert_ok!(Treasury::veto_approved(Origin::root(), 1, false));
+		assert_eq!(Treasury::committed(), 70);
+		assert_ok!(Treasury::reject_proposal(Origin::root(), 2));
+		assert_eq!(Treasury::committed(), 60);
+
+		// Each award of the recurring proposal is no longer committed once paid.
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Treasury::committed(), 40);
+		assert_eq!(Treasury::uncommitted_pot(), Treasury::pot() - 40);
+
+		assert_ok!(Treasury::extend_proposal(Origin::root(), 0, 30, 1));
+		assert_eq!(Treasury::committed(), 70);
+
+		for &(now, committed) in &[(4, 50), (6, 30), (8, 0)] {
+			<Treasury as OnInitialize<u64>>::on_initialize(now);
+			assert_eq!(Treasury::committed(), committed);
+		}
+		assert_eq!(Treasury::proposals(0), None);
+		assert_eq!(Treasury::uncommitted_pot(), Treasury::pot());
+	});
+}
+
+#[test]
+fn approvals_can_be_limited_to_the_uncommitted_pot() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 51);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 3, 1, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 4, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 3, 1, 0, None, None));
+
+		// By default any proposal can be approved.
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 2));
+		assert_ok!(Treasury::remove_approval(Origin::root(), 2));
+
+		EnsureAffordable::set(true);
+		assert_noop!(
+			Treasury::approve_proposal(Origin::root(), 0),
+			Error::<Test, _>::InsufficientFunds
+		);
+		// Only the next chunk of a recurring proposal has to fit.
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 1));
+		assert_eq!(Treasury::uncommitted_pot(), 0);
+		assert_noop!(
+			Treasury::approve_proposals(Origin::root(), bounded_ids(vec![0, 2])),
+			Error::<Test, _>::InsufficientFunds
+		);
+
+		// Once the pot has grown, the proposal becomes affordable.
+		Balances::make_free_balance_be(&Treasury::account_id(), 201);
+		assert_eq!(Treasury::uncommitted_pot(), 100);
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_noop!(
+			Treasury::approve_proposal(Origin::root(), 2),
+			Error::<Test, _>::InsufficientFunds
+		);
+		assert_eq!(Treasury::approvals().into_inner(), vec![1, 0]);
+	});
+}
+
+#[test]
+fn removed_waiting_positions_are_not_reused() {
+	new_test_ext().execute_with(|| {
+		// Outside the allowed proposal period.
+		System::set_block_number(3);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 20, 3, 1, 0, None, None));
+		assert_ok!(Treasury::reject_waiting_proposal(Origin::root(), 1));
+
+		// The next proposal is queued after the rejected one, which keeps its status.
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 30, 3, 1, 0, None, None));
+		assert_eq!(Treasury::waiting_proposals(2).unwrap().total_value, 30);
+		assert_eq!(Treasury::waiting_status(1), Some(ProposalStatus::Rejected));
+		assert_eq!((Treasury::waiting_queue_head(), Treasury::waiting_queue_tail()), (0, 3));
+
+		run_block(4);
+		let promoted: Vec<_> = (0..Treasury::proposal_count())
+			.map(|index| Treasury::proposals(index).unwrap().total_value)
+			.collect();
+		assert_eq!(promoted, vec![10, 30]);
+		assert_eq!(Treasury::waiting_status(1), Some(ProposalStatus::Rejected));
+	});
+}
+
+#[test]
+fn waiting_queue_keeps_insertion_order() {
+	new_test_ext().execute_with(|| {
+		// Outside the allowed proposal period.
+		System::set_block_number(3);
+		for value in 1..=4 {
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), value, 3, 1, 0, None, None));
+		}
+		// Cancelling from the middle leaves a gap, the queue keeps its length.
+		assert_ok!(Treasury::cancel_waiting(Origin::signed(0), 1));
+		assert_eq!((Treasury::waiting_queue_head(), Treasury::waiting_queue_tail()), (0, 4));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 5, 3, 1, 0, None, None));
+		// Removing the head skips only that position.
+		assert_ok!(Treasury::reject_waiting_proposal(Origin::root(), 0));
+		assert_eq!((Treasury::waiting_queue_head(), Treasury::waiting_queue_tail()), (1, 5));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 6, 3, 1, 0, None, None));
+		assert_ok!(Treasury::cancel_waiting(Origin::signed(0), 4));
+		assert_eq!(Treasury::waiting_proposal_count(), 5);
+
+		run_block(4);
+		let promoted: Vec<_> = (0..Treasury::proposal_count())
+			.map(|index| Treasury::proposals(index).unwrap().total_value)
+			.collect();
+		assert_eq!(promoted, vec![3, 4, 6]);
+		let transferred: Vec<_> = treasury_events()
+			.into_iter()
+			.filter_map(|e| match e {
+				treasury::Event::WaitingProposalTransfered {
+					waiting_index,
+					new_proposal_index,
+					..
+				} => Some((waiting_index, new_proposal_index)),
+				_ => None,
+			})
+			.collect();
+		assert_eq!(transferred, vec![(2, 0), (3, 1), (5, 2)]);
+		assert_eq!((Treasury::waiting_queue_head(), Treasury::waiting_queue_tail()), (6, 6));
+
+		// Positions keep counting up after the queue is drained.
+		System::set_block_number(5);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 7, 3, 1, 0, None, None));
+		System::assert_last_event(
+			treasury::Event::<Test>::WaitingProposed {
+				proposal_index: 6,
+				proposer: Some(0),
+				beneficiary: 3,
+				value: 7,
+				chunk: 7,
+				occurs: 1,
+				bond: 1,
+			}
+			.into(),
+		);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 8, 3, 1, 0, None, None));
+		assert_ok!(Treasury::cancel_waiting(Origin::signed(0), 6));
+		run_block(6);
+		assert_eq!(Treasury::proposals(3).unwrap().total_value, 8);
+		assert_eq!(Treasury::proposal_count(), 4);
+		assert_eq!(Treasury::waiting_proposal_count(), 0);
+	});
+}
+
+#[test]
+fn occurrences_are_limited() {
+	new_test_ext().execute_with(|| {
+		let max = MaxOccurrences::get();
+		for block in [2, 3] {
+			// Inside and outside the allowed proposal period.
+			System::set_block_number(block);
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), 12, 3, max, 0, None, None));
+			assert_noop!(
+				Treasury::propose_spend(Origin::signed(0), 13, 3, max + 1, 0, None, None),
+				Error::<Test, _>::TooManyOccurrences
+			);
+		}
+		assert_eq!(Treasury::proposals(0).unwrap().occurs, max);
+		assert_eq!(Treasury::waiting_proposals(0).unwrap().occurs, max);
+	});
+}
+
+fn schedule(amounts: Vec<u64>) -> BoundedVec<u64, MaxOccurrences> {
+	amounts.try_into().unwrap()
+}
+
+#[test]
+fn scheduled_proposal_pays_each_amount_in_turn() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 1001);
+
+		assert_ok!(Treasury::propose_spend_scheduled(
+			Origin::signed(0),
+			schedule(vec![40, 30, 30]),
+			3
+		));
+		// The bond is computed on the sum of the schedule.
+		assert_eq!(Balances::reserved_balance(0), 5);
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 40);
+		assert_eq!(Treasury::proposals(0).unwrap().remaining_occurs, 2);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(Balances::free_balance(3), 70);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(6);
+		assert_eq!(Balances::free_balance(3), 100);
+		assert_eq!(Treasury::proposals(0), None);
+		assert!(Treasury::approvals().is_empty());
+		assert_eq!(Balances::reserved_balance(0), 0);
+	});
+}
+
+#[test]
+fn scheduled_proposal_amounts_are_checked() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Treasury::propose_spend_scheduled(Origin::signed(0), schedule(vec![40, 0, 30]), 3),
+			Error::<Test, _>::ChunkTooSmall
+		);
+		assert_noop!(
+			Treasury::propose_spend_scheduled(Origin::signed(0), schedule(vec![]), 3),
+			Error::<Test, _>::EmptySchedule
+		);
+	});
+}
+
+#[test]
+fn unaffordable_approval_emits_proposal_missed() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 3, 1, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 4, 1, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 1));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 60);
+		assert_eq!(Treasury::approvals().into_inner(), vec![1]);
+		let missed: Vec<_> = treasury_events()
+			.into_iter()
+			.filter(|event| matches!(event, treasury::Event::ProposalMissed(..)))
+			.collect();
+		assert_eq!(missed, vec![treasury::Event::ProposalMissed(1, 60, 40)]);
+	});
+}
+
+#[test]
+fn scheduled_proposal_waits_for_an_unaffordable_amount() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 21);
+
+		assert_ok!(Treasury::propose_spend_scheduled(
+			Origin::signed(0),
+			schedule(vec![10, 50, 10]),
+			3
+		));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 10);
+		assert_eq!(Treasury::pot(), 5);
+
+		// The middle amount cannot be afforded, and the later one is not paid in its place.
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(Balances::free_balance(3), 10);
+		assert_eq!(Treasury::proposals(0).unwrap().remaining_occurs, 2);
+		assert_eq!(Treasury::pot(), 5);
+
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		<Treasury as OnInitialize<u64>>::on_initialize(6);
+		assert_eq!(Balances::free_balance(3), 60);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(8);
+		assert_eq!(Balances::free_balance(3), 70);
+		assert_eq!(Treasury::proposals(0), None);
+	});
+}
+
+#[test]
+fn pending_proposals_per_account_are_limited() {
+	new_test_ext().execute_with(|| {
+		MaxPendingPerAccount::set(2);
+		System::set_block_number(2);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		// Waiting proposals count towards the same limit.
+		System::set_block_number(3);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_eq!(Treasury::pending_by_proposer(0), 2);
+		assert_noop!(
+			Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None),
+			Error::<Test, _>::TooManyPendingProposals
+		);
+		// Other accounts are unaffected.
+		assert_ok!(Treasury::propose_spend(Origin::signed(1), 10, 3, 1, 0, None, None));
+
+		// Rejecting and cancelling free up slots again.
+		assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
+		assert_eq!(Treasury::pending_by_proposer(0), 1);
+		assert_ok!(Treasury::cancel_waiting(Origin::signed(0), 0));
+		assert_eq!(Treasury::pending_by_proposer(0), 0);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+	});
+}
+
+#[test]
+fn pending_proposals_are_tracked_through_spend_funds() {
+	new_test_ext().execute_with(|| {
+		MaxPendingPerAccount::set(2);
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), u64::MAX);
+
+		// A recurring proposal stays pending until its final occurrence.
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 20, 3, 2, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		// A waiting proposal stays pending when it is promoted.
+		System::set_block_number(3);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_eq!(Treasury::pending_by_proposer(0), 2);
+
+		run_block(4);
+		assert_eq!(Treasury::proposals(0).unwrap().remaining_occurs, 1);
+		assert_eq!(Treasury::proposals(1).unwrap().total_value, 10);
+		assert_eq!(Treasury::pending_by_proposer(0), 2);
+
+		run_block(6);
+		assert_eq!(Treasury::proposals(0), None);
+		assert_eq!(Treasury::pending_by_proposer(0), 1);
+
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 1));
+		run_block(8);
+		assert_eq!(Treasury::pending_by_proposer(0), 0);
+	});
+}
+
+#[test]
+fn proposal_value_is_limited_by_pot_at_proposal_time() {
+	new_test_ext().execute_with(|| {
+		MaxProposalValue::set(Some(Permill::from_percent(50)));
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 50, 3, 1, 0, None, None));
+		// The limit applies to the total value, not to each chunk.
+		assert_noop!(
+			Treasury::propose_spend(Origin::signed(0), 60, 3, 3, 0, None, None),
+			Error::<Test, _>::ProposalTooLarge
+		);
+
+		// The pot grows; the check only ever happened at proposal time.
+		Balances::make_free_balance_be(&Treasury::account_id(), 1001);
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 3, 3, 0, None, None));
+		assert_noop!(
+			Treasury::propose_spend(Origin::signed(0), 501, 3, 1, 0, None, None),
+			Error::<Test, _>::ProposalTooLarge
+		);
+	});
+}
+
+#[test]
+fn rejection_slashes_configured_ratio_of_bond() {
+	// (slash ratio, slashed, refunded)
+	for (ratio, slashed, refunded) in [(0, 0, 10), (50, 5, 5), (100, 10, 0)] {
+		new_test_ext().execute_with(|| {
+			RejectionSlashRatio::set(Permill::from_percent(ratio));
+			System::set_block_number(2);
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), 200, 3, 1, 0, None, None));
+			assert_eq!(Balances::reserved_balance(0), 10);
+
+			assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
+			System::assert_last_event(
+				treasury::Event::<Test>::Rejected(0, slashed, refunded, Default::default()).into(),
+			);
+			assert_eq!(Balances::reserved_balance(0), 0);
+			assert_eq!(Balances::free_balance(0), 100 - slashed);
+		});
+	}
+}
+
+#[test]
+fn rejection_shares_the_slash_with_the_reporter() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 200, 3, 1, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 200, 3, 1, 0, None, None));
+		assert_ok!(Treasury::report_proposal(Origin::signed(1), 0));
+		System::assert_last_event(treasury::Event::<Test>::ProposalReported(0, 1).into());
+		assert_eq!(Treasury::reporter(0), Some(1));
+
+		assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
+		System::assert_has_event(treasury::Event::<Test>::ReporterRewarded(0, 1, 2, 8).into());
+		System::assert_last_event(
+			treasury::Event::<Test>::Rejected(0, 10, 0, Default::default()).into(),
+		);
+		assert_eq!(Balances::free_balance(1), 100);
+		assert_eq!(Treasury::reporter(0), None);
+
+		// Without a reporter the whole slash goes to `OnSlash`, as before.
+		assert_ok!(Treasury::reject_proposal(Origin::root(), 1));
+		assert!(!treasury_events()
+			.iter()
+			.any(|event| matches!(event, treasury::Event::<Test>::ReporterRewarded(1, ..))));
+		assert_eq!(Balances::free_balance(0), 80);
+		assert_eq!(Balances::free_balance(1), 100);
+	});
+}
+
+#[test]
+fn reporter_reward_may_round_to_nothing() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		// The bond is the minimum of 1, and a fifth of it rounds down to nothing.
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_ok!(Treasury::report_proposal(Origin::signed(1), 0));
+
+		assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
+		assert!(!treasury_events()
+			.iter()
+			.any(|event| matches!(event, treasury::Event::<Test>::ReporterRewarded(..))));
+		System::assert_last_event(
+			treasury::Event::<Test>::Rejected(0, 1, 0, Default::default()).into(),
+		);
+		assert_eq!(Balances::free_balance(1), 98);
+	});
+}
+
+#[test]
+fn report_proposal_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		assert_noop!(
+			Treasury::report_proposal(Origin::signed(1), 0),
+			Error::<Test, _>::ProposalNotFound
+		);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 200, 3, 1, 0, None, None));
+
+		assert_noop!(
+			Treasury::report_proposal(Origin::signed(0), 0),
+			Error::<Test, _>::ReporterIsProposer
+		);
+		assert_ok!(Treasury::report_proposal(Origin::signed(1), 0));
+		assert_noop!(
+			Treasury::report_proposal(Origin::signed(2), 0),
+			Error::<Test, _>::AlreadyReported
+		);
+
+		// The report goes with the proposal.
+		assert_ok!(Treasury::withdraw_proposal(Origin::signed(0), 0));
+		assert_eq!(Treasury::reporter(0), None);
+	});
+}
+
+#[test]
+fn spends_made_now_count_against_the_period_cap() {
+	new_test_ext().execute_with(|| {
+		MaxSpendPerPeriod::set(Some(25));
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_ok!(Spender::pay(Origin::signed(1), 20, 3, true));
+		assert_noop!(
+			Spender::pay(Origin::signed(1), 6, 3, true),
+			Error::<Test, _>::OverPeriodBudget
+		);
+		assert_ok!(Spender::pay(Origin::signed(1), 5, 3, true));
+		assert_eq!(Treasury::spent_this_period(), 25);
+
+		// The next spend period starts afresh.
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Treasury::spent_this_period(), 0);
+		assert_ok!(Spender::pay(Origin::signed(1), 25, 3, true));
+		assert_eq!(Balances::free_balance(3), 50);
+		assert_eq!(Treasury::total_awarded(), 50);
+	});
+}
+
+#[test]
+fn other_pallets_can_queue_spends_without_a_bond() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_ok!(Spender::pay(Origin::signed(1), 30, 3, false));
+		assert_eq!(spender::LastQueued::<Test>::get(), Some(0));
+		assert_eq!(Treasury::approvals().into_inner(), vec![0]);
+		assert_eq!(Treasury::proposals(0).unwrap().bond, 0);
+		assert_eq!(Treasury::committed(), 30);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		System::assert_last_event(treasury::Event::<Test>::SpendApproved(0, 30, 3).into());
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 30);
+		assert_eq!(Treasury::committed(), 0);
+	});
+}
+
+#[test]
+fn other_pallets_can_spend_uncommitted_funds_now() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_ok!(Spender::pay(Origin::signed(1), 40, 3, true));
+		assert_eq!(Balances::free_balance(3), 40);
+		assert_eq!(Treasury::pot(), 60);
+		assert_eq!(Treasury::spent_this_period(), 40);
+		assert_eq!(Treasury::total_awarded(), 40);
+		System::assert_last_event(treasury::Event::<Test>::SpentNow(40, 3).into());
+
+		// The pot cannot pay itself, now or later.
+		let treasury = Treasury::account_id();
+		assert_noop!(
+			Spender::pay(Origin::signed(1), 10, treasury, true),
+			Error::<Test, _>::InvalidBeneficiary
+		);
+		assert_noop!(
+			Spender::pay(Origin::signed(1), 10, treasury, false),
+			Error::<Test, _>::InvalidBeneficiary
+		);
+
+		// Funds committed to approved proposals may not be spent.
+		assert_ok!(Spender::pay(Origin::signed(1), 50, 4, false));
+		assert_noop!(
+			Spender::pay(Origin::signed(1), 11, 3, true),
+			Error::<Test, _>::InsufficientFunds
+		);
+		assert_noop!(Spender::pay(Origin::signed(1), 0, 3, true), Error::<Test, _>::ChunkTooSmall);
+
+		// All of the uncommitted funds may be spent, but the pot account is kept alive.
+		assert_ok!(Spender::pay(Origin::signed(1), 10, 3, true));
+		assert_eq!(Treasury::pot(), 50);
+		assert_eq!(Balances::free_balance(Treasury::account_id()), 51);
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(4), 50);
+		assert_eq!(Treasury::pot(), 0);
+	});
+}
+
+#[test]
+fn treasury_account_id_feeds_other_pallets() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(TreasuryAccountId::<Test>::get(), Treasury::account_id());
+		assert_eq!(TreasuryAccountId::<Test, Instance1>::get(), Treasury1::account_id());
+
+		let pot = Treasury::pot();
+		assert_ok!(Spender::charge(Origin::signed(0), 10));
+		assert_eq!(Treasury::pot(), pot + 10);
+		assert_eq!(Balances::free_balance(0), 90);
+	});
+}
+
+#[test]
+fn treasury_inspect_reports_state() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_eq!(<Treasury as TreasuryInspect>::pot(), 100);
+		assert_eq!(<Treasury as TreasuryInspect>::committed(), 0);
+		assert!(!<Treasury as TreasuryInspect>::proposal_exists(0));
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 60, 3, 1, 0, None, None));
+		assert!(<Treasury as TreasuryInspect>::proposal_exists(0));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_eq!(<Treasury as TreasuryInspect>::committed(), 60);
+
+		// Another pallet sees the same state through its trait bound.
+		assert_noop!(Spender::pay_if_affordable(Origin::signed(1), 41, 4), "not affordable");
+		assert_ok!(Spender::pay_if_affordable(Origin::signed(1), 40, 4));
+		assert_eq!(<Treasury as TreasuryInspect>::pot(), 60);
+	});
+}
+
+#[test]
+fn deposits_report_their_source() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let pot = Treasury::pot();
+		Treasury::deposit_from(DepositSource::Slash, Balances::issue(10));
+		Treasury::deposit_from(DepositSource::Other(7), Balances::issue(5));
+		// Zero deposits are not reported.
+		Treasury::deposit_from(DepositSource::Auction, Balances::issue(0));
+		Treasury::on_unbalanced(Balances::issue(3));
+
+		assert_eq!(Treasury::pot(), pot + 18);
+		assert_eq!(Treasury::total_deposited(), 18);
+		assert_eq!(
+			treasury_events(),
+			vec![
+				treasury::Event::Deposit(10, DepositSource::Slash),
+				treasury::Event::Deposit(5, DepositSource::Other(7)),
+				treasury::Event::Deposit(3, DepositSource::Unattributed),
+			]
+		);
+	});
+}
+
+#[test]
+fn split_to_treasury_shares_imbalances() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let pot = Treasury::pot();
+		FeeSplit::on_unbalanced(Balances::issue(100));
+		assert_eq!(Treasury::pot(), pot + 80);
+		assert_eq!(other_shares(), vec![20]);
+		// Only the treasury's share is reported as deposited.
+		assert_eq!(
+			treasury_events(),
+			vec![treasury::Event::Deposit(80, DepositSource::Unattributed)]
+		);
+
+		// Fees and tips are split together.
+		System::reset_events();
+		FeeSplit::on_unbalanceds(vec![Balances::issue(10), Balances::issue(5)].into_iter());
+		assert_eq!(Treasury::pot(), pot + 92);
+		assert_eq!(other_shares(), vec![20, 3]);
+		assert_eq!(
+			treasury_events(),
+			vec![treasury::Event::Deposit(12, DepositSource::Unattributed)]
+		);
+	});
+}
+
+#[test]
+fn split_to_treasury_gives_dust_to_the_treasury() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let pot = Treasury::pot();
+		// 80% of 7 is 5.6.
+		FeeSplit::on_unbalanced(Balances::issue(7));
+		assert_eq!(Treasury::pot(), pot + 6);
+		assert_eq!(other_shares(), vec![1]);
+
+		// With nothing left over, `Other` is not called at all.
+		TreasuryShare::set(Permill::from_percent(33));
+		FeeSplit::on_unbalanced(Balances::issue(1));
+		assert_eq!(Treasury::pot(), pot + 7);
+		assert_eq!(other_shares(), vec![1]);
+	});
+}
+
+#[test]
+fn split_to_treasury_respects_whole_shares() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let pot = Treasury::pot();
+		TreasuryShare::set(Permill::zero());
+		FeeSplit::on_unbalanced(Balances::issue(10));
+		assert_eq!(Treasury::pot(), pot);
+		assert_eq!(other_shares(), vec![10]);
+		assert!(treasury_events().is_empty());
+
+		TreasuryShare::set(Permill::from_percent(100));
+		FeeSplit::on_unbalanced(Balances::issue(10));
+		assert_eq!(Treasury::pot(), pot + 10);
+		assert_eq!(other_shares(), vec![10]);
+		assert_eq!(
+			treasury_events(),
+			vec![treasury::Event::Deposit(10, DepositSource::Unattributed)]
+		);
+	});
+}
+
+#[test]
+fn burn_respects_burn_floor() {
+	// (burn floor, burnt)
+	for (floor, burnt) in [(10, 50), (70, 30), (200, 0)] {
+		new_test_ext().execute_with(|| {
+			BurnFloor::set(floor);
+			System::set_block_number(2);
+			Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+			<Treasury as OnInitialize<u64>>::on_initialize(2);
+			// Nothing burnt, nothing reported.
+			let burnt_events: Vec<_> = treasury_events()
+				.into_iter()
+				.filter(|event| matches!(event, treasury::Event::<Test>::Burnt(_)))
+				.collect();
+			let expected = if burnt == 0 { vec![] } else { vec![treasury::Event::Burnt(burnt)] };
+			assert_eq!(burnt_events, expected);
+			assert_eq!(Treasury::pot(), 100 - burnt);
+		});
+	}
+}
+
+#[test]
+fn burn_only_applies_above_burn_threshold() {
+	// (burn threshold, burnt)
+	for (threshold, burnt) in [(150, 0), (100, 0), (60, 20), (0, 50)] {
+		new_test_ext().execute_with(|| {
+			BurnThreshold::set(threshold);
+			System::set_block_number(2);
+			Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+			<Treasury as OnInitialize<u64>>::on_initialize(2);
+			let burnt_events: Vec<_> = treasury_events()
+				.into_iter()
+				.filter(|event| matches!(event, treasury::Event::<Test>::Burnt(_)))
+				.collect();
+			let expected = if burnt == 0 { vec![] } else { vec![treasury::Event::Burnt(burnt)] };
+			assert_eq!(burnt_events, expected);
+			assert_eq!(Treasury::pot(), 100 - burnt);
+			assert_eq!(Treasury::total_burnt(), burnt);
+		});
+	}
+}
+
+#[test]
+fn adaptive_burn_follows_pot_growth() {
+	new_test_ext().execute_with(|| {
+		TreasuryBurnMode::set(BurnMode::Adaptive {
+			factor: Permill::from_percent(50),
+			max: Permill::from_percent(20),
+		});
+		System::set_block_number(2);
+
+		// (pot balance at the start of the period, burnt)
+		let periods = [
+			// Nothing to measure growth against yet.
+			(101, 0),
+			// Grew by 60: half of it is burnt.
+			(161, 30),
+			// Grew by 100, but at most a fifth of the 230 is burnt.
+			(231, 46),
+			// Shrank: nothing is burnt.
+			(101, 0),
+			// Grew again by 30.
+			(131, 15),
+		];
+		for (period, (balance, burnt)) in periods.iter().enumerate() {
+			Balances::make_free_balance_be(&Treasury::account_id(), *balance);
+			System::reset_events();
+			<Treasury as OnInitialize<u64>>::on_initialize(2 * (period as u64 + 1));
+
+			let burnt_events: Vec<_> = treasury_events()
+				.into_iter()
+				.filter(|event| matches!(event, treasury::Event::<Test>::Burnt(_)))
+				.collect();
+			let expected = if *burnt == 0 { vec![] } else { vec![treasury::Event::Burnt(*burnt)] };
+			assert_eq!(burnt_events, expected, "period {}", period);
+			assert_eq!(Treasury::pot(), balance - 1 - burnt);
+			assert_eq!(Treasury::last_period_pot(), Some(balance - 1 - burnt));
+		}
+	});
+}
+
+#[test]
+fn static_burn_records_the_pot_for_later_periods() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_eq!(Treasury::last_period_pot(), None);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert!(treasury_events().contains(&treasury::Event::Burnt(50)));
+		assert_eq!(Treasury::last_period_pot(), Some(50));
+
+		// Switching to the adaptive mode measures growth from there.
+		TreasuryBurnMode::set(BurnMode::Adaptive {
+			factor: Permill::from_percent(100),
+			max: Permill::from_percent(100),
+		});
+		Balances::make_free_balance_be(&Treasury::account_id(), 71);
+		System::reset_events();
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert!(treasury_events().contains(&treasury::Event::Burnt(20)));
+		assert_eq!(Treasury::pot(), 50);
+	});
+}
+
+#[test]
+fn burn_threshold_does_not_lift_a_missed_proposal_hold() {
+	new_test_ext().execute_with(|| {
+		BurnThreshold::set(20);
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		// Too large for the pot, so it is missed and nothing is burnt.
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 150, 3, 1, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert!(!treasury_events()
+			.iter()
+			.any(|event| matches!(event, treasury::Event::<Test>::Burnt(_))));
+		assert_eq!(Treasury::pot(), 100);
+
+		// Once nothing is missed, only the part of the pot above the threshold is burnt.
+		assert_ok!(Treasury::veto_approved(Origin::root(), 0, false));
+		System::reset_events();
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert!(treasury_events().contains(&treasury::Event::Burnt(40)));
+		assert_eq!(Treasury::pot(), 60);
+	});
+}
+
+fn burn_destinations(
+	destinations: Vec<(u128, Permill)>,
+) -> BoundedVec<(u128, Permill), MaxBurnDestinations> {
+	destinations.try_into().unwrap()
+}
+
+#[test]
+fn burn_is_shared_between_destinations() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_noop!(
+			Treasury::set_burn_destinations(
+				Origin::root(),
+				burn_destinations(vec![
+					(20, Permill::from_percent(60)),
+					(21, Permill::from_percent(50))
+				])
+			),
+			Error::<Test, _>::InvalidBurnShares
+		);
+		assert_ok!(Treasury::set_burn_destinations(
+			Origin::root(),
+			burn_destinations(vec![
+				(20, Permill::from_percent(30)),
+				(21, Permill::from_percent(20))
+			])
+		));
+		System::assert_last_event(treasury::Event::<Test>::BurnDestinationsSet.into());
+
+		let init_total_issuance = Balances::total_issuance();
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		let events = treasury_events();
+		assert!(events.contains(&treasury::Event::<Test>::Burnt(50)));
+		assert!(events.contains(&treasury::Event::<Test>::BurnShared(20, 15)));
+		assert!(events.contains(&treasury::Event::<Test>::BurnShared(21, 10)));
+		assert_eq!(Balances::free_balance(20), 15);
+		assert_eq!(Balances::free_balance(21), 10);
+		// The unshared 50% is still burnt.
+		assert_eq!(Balances::total_issuance(), init_total_issuance - 25);
+	});
+}
+
+#[test]
+fn burn_dust_goes_to_first_destination() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 103);
+		assert_ok!(Treasury::set_burn_destinations(
+			Origin::root(),
+			burn_destinations(vec![
+				(20, Permill::from_percent(70)),
+				(21, Permill::from_percent(30))
+			])
+		));
+
+		let init_total_issuance = Balances::total_issuance();
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert!(treasury_events().contains(&treasury::Event::<Test>::Burnt(51)));
+		assert_eq!(Balances::free_balance(20), 36);
+		assert_eq!(Balances::free_balance(21), 15);
+		assert_eq!(Balances::total_issuance(), init_total_issuance);
+	});
+}
+
+#[test]
+fn spend_period_offset_shifts_spend_blocks() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		Balances::make_free_balance_be(&Treasury1::account_id(), 101);
+
+		// Without an offset the spend, and so the burn, happens at block 2.
+		<Treasury as OnInitialize<u64>>::on_initialize(1);
+		assert_eq!(Treasury::pot(), 100);
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Treasury::pot(), 50);
+
+		// With an offset of one it happens at block 1 instead.
+		<Treasury1 as OnInitialize<u64>>::on_initialize(1);
+		assert_eq!(Treasury1::pot(), 50);
+		<Treasury1 as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Treasury1::pot(), 50);
+	});
+}
+
+#[test]
+fn skipped_spend_blocks_are_caught_up_once() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Treasury::last_spend_block(), 2);
+		assert_eq!(Treasury::pot(), 50);
+		let spending_rounds = || {
+			treasury_events()
+				.iter()
+				.filter(|event| matches!(event, treasury::Event::Spending(_)))
+				.count()
+		};
+
+		// Block production stalls across the spend blocks 4, 6 and 8.
+		System::reset_events();
+		<Treasury as OnInitialize<u64>>::on_initialize(9);
+		assert_eq!(spending_rounds(), 1);
+		assert_eq!(Treasury::last_spend_block(), 9);
+		assert_eq!(Treasury::pot(), 25);
+
+		// The next spend block is on schedule again.
+		System::reset_events();
+		<Treasury as OnInitialize<u64>>::on_initialize(10);
+		<Treasury as OnInitialize<u64>>::on_initialize(11);
+		assert_eq!(spending_rounds(), 1);
+		assert_eq!(Treasury::last_spend_block(), 10);
+	});
+}
+
+#[test]
+fn spend_period_offset_shifts_proposal_window() {
+	new_test_ext().execute_with(|| {
+		// Block 1 is a spend block for the offset instance, so proposals go straight in.
+		System::set_block_number(1);
+		assert_ok!(Treasury1::propose_spend(Origin::signed(0), 10, (0, 3), 1, 0, None, None));
+		assert_eq!(Treasury1::proposal_count(), 1);
+
+		System::set_block_number(2);
+		assert_ok!(Treasury1::propose_spend(Origin::signed(0), 10, (0, 3), 1, 0, None, None));
+		assert_eq!(Treasury1::waiting_proposal_count(), 1);
+	});
+}
+
+fn treasury2_events() -> Vec<treasury::Event<Test, Instance2>> {
+	System::events()
+		.into_iter()
+		.filter_map(|r| if let Event::Treasury2(inner) = r.event { Some(inner) } else { None })
+		.collect()
+}
+
+#[test]
+fn instances_have_their_own_accounts() {
+	new_test_ext().execute_with(|| {
+		assert_ne!(Treasury::account_id(), Treasury2::account_id());
+		assert_ne!(Treasury1::account_id(), Treasury2::account_id());
+		// Both genesis builds created their account.
+		assert_eq!(Balances::free_balance(Treasury::account_id()), 1);
+		assert_eq!(Balances::free_balance(Treasury2::account_id()), 1);
+
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		Balances::make_free_balance_be(&Treasury2::account_id(), 51);
+		assert_eq!(Treasury::pot(), 100);
+		assert_eq!(Treasury2::pot(), 50);
+	});
+}
+
+#[test]
+fn instances_count_and_approve_proposals_independently() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(4);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_ok!(Treasury2::propose_spend(Origin::signed(0), 20, 3, 1, 0, None, None));
+		assert_ok!(Treasury2::propose_spend(Origin::signed(1), 30, 3, 1, 0, None, None));
+		assert_eq!(Treasury::proposal_count(), 1);
+		assert_eq!(Treasury2::proposal_count(), 2);
+		assert_eq!(Treasury::proposals(0).unwrap().total_value, 10);
+		assert_eq!(Treasury2::proposals(0).unwrap().total_value, 20);
+
+		// Index 1 only exists on the second instance.
+		assert_noop!(
+			Treasury::approve_proposal(Origin::root(), 1),
+			Error::<Test, _>::ProposalNotFound
+		);
+		assert_ok!(Treasury2::approve_proposal(Origin::root(), 1));
+		assert!(Treasury::approvals().is_empty());
+		assert_eq!(Treasury2::approvals().into_inner(), vec![1]);
+
+		// Approving index 0 on one instance leaves the other's proposal 0 untouched.
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_eq!(Treasury::approvals().into_inner(), vec![0]);
+		assert_eq!(Treasury2::approvals().into_inner(), vec![1]);
+		assert_noop!(
+			Treasury::approve_proposal(Origin::root(), 0),
+			Error::<Test, _>::AlreadyApproved
+		);
+		assert_ok!(Treasury2::approve_proposal(Origin::root(), 0));
+	});
+}
+
+#[test]
+fn instances_spend_and_burn_on_their_own_schedule() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		Balances::make_free_balance_be(&Treasury2::account_id(), 101);
+		System::set_block_number(4);
+		assert_ok!(Treasury2::propose_spend(Origin::signed(0), 20, 3, 1, 0, None, None));
+		assert_ok!(Treasury2::approve_proposal(Origin::root(), 0));
+
+		// Block 2 is a spend block for the first instance only.
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		<Treasury2 as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Treasury::pot(), 50);
+		assert_eq!(Treasury2::pot(), 100);
+
+		// At block 4 the second instance pays its proposal and burns 10% of what is left.
+		<Treasury2 as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(Balances::free_balance(3), 20);
+		assert_eq!(Treasury2::pot(), 72);
+		assert_eq!(Treasury::pot(), 50);
+		assert!(treasury2_events().contains(&treasury::Event::Burnt(8)));
+	});
+}
+
+#[test]
+fn imbalances_are_credited_to_their_own_instance() {
+	new_test_ext().execute_with(|| {
+		Treasury2::on_unbalanced(Balances::issue(10));
+		assert_eq!(Treasury2::pot(), 10);
+		assert_eq!(Treasury::pot(), 0);
+		assert!(
+			treasury2_events().contains(&treasury::Event::Deposit(10, DepositSource::Unattributed))
+		);
+		assert!(treasury_events().is_empty());
+	});
+}
+
+#[test]
+fn payouts_per_beneficiary_are_capped_per_period() {
+	new_test_ext().execute_with(|| {
+		MaxPerBeneficiaryPerPeriod::set(Some(25));
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), u64::MAX);
+
+		for index in 0..3 {
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+			assert_ok!(Treasury::approve_proposal(Origin::root(), index));
+		}
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 4, 1, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 3));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 20);
+		assert_eq!(Balances::free_balance(4), 10);
+		assert_eq!(Treasury::approvals().into_inner(), vec![2]);
+		let events = treasury_events();
+		assert!(events.contains(&treasury::Event::<Test>::PayoutDeferred(2, 3)));
+		// Other payouts were made, so the surplus is still burnt.
+		assert!(events.iter().any(|event| matches!(event, treasury::Event::<Test>::Burnt(_))));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(Balances::free_balance(3), 30);
+		assert!(Treasury::approvals().is_empty());
+	});
+}
+
+#[test]
+fn spending_is_capped_per_period() {
+	new_test_ext().execute_with(|| {
+		MaxSpendPerPeriod::set(Some(25));
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 1001);
+
+		for (index, beneficiary) in (3..6).enumerate() {
+			assert_ok!(Treasury::propose_spend(
+				Origin::signed(0),
+				10,
+				beneficiary,
+				1,
+				0,
+				None,
+				None
+			));
+			assert_ok!(Treasury::approve_proposal(Origin::root(), index as ProposalIndex));
+		}
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 10);
+		assert_eq!(Balances::free_balance(4), 10);
+		assert_eq!(Balances::free_balance(5), 0);
+		assert_eq!(Treasury::spent_this_period(), 20);
+		assert_eq!(Treasury::approvals().into_inner(), vec![2]);
+		let events = treasury_events();
+		assert!(events.contains(&treasury::Event::<Test>::PeriodBudgetExhausted(20, 25)));
+		// The held back approval is not a miss, so the surplus is still burnt.
+		assert!(events.iter().any(|event| matches!(event, treasury::Event::<Test>::Burnt(_))));
+
+		// The overflow is paid in the next period, against a fresh budget.
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(Balances::free_balance(5), 10);
+		assert_eq!(Treasury::spent_this_period(), 10);
+		assert!(Treasury::approvals().is_empty());
+	});
+}
+
+#[test]
+fn unaffordable_approvals_lapse_after_ttl() {
+	new_test_ext().execute_with(|| {
+		ApprovalTtlPeriods::set(Some(2));
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 51);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 30, 4, 3, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 1));
+		assert_eq!(Treasury::approved_at(0), Some(2));
+
+		// The large approval is missed, holding back the burn, while the small one is paid.
+		for &now in &[2, 4] {
+			<Treasury as OnInitialize<u64>>::on_initialize(now);
+			assert_eq!(Treasury::approvals().into_inner(), vec![0, 1]);
+		}
+		assert_eq!(Balances::free_balance(4), 20);
+		assert_eq!(Treasury::pot(), 30);
+
+		// Two spend periods after its approval, it lapses.
+		System::reset_events();
+		<Treasury as OnInitialize<u64>>::on_initialize(6);
+		assert!(Treasury::approvals().is_empty());
+		assert_eq!(Treasury::proposals(0), None);
+		assert_eq!(Treasury::approved_at(0), None);
+		assert_eq!(Balances::free_balance(4), 30);
+		assert_eq!(Balances::reserved_balance(0), 0);
+		assert_eq!(Balances::free_balance(0), 100);
+		let events = treasury_events();
+		assert!(events.contains(&treasury::Event::<Test>::ApprovalLapsed(0)));
+		assert!(!events
+			.iter()
+			.any(|event| matches!(event, treasury::Event::<Test>::ProposalMissed(..))));
+		assert!(events.iter().any(|event| matches!(event, treasury::Event::<Test>::Burnt(_))));
+	});
+}
+
+#[test]
+fn partial_payouts_drain_a_proposal_over_several_periods() {
+	new_test_ext().execute_with(|| {
+		AllowPartialPayouts::set(true);
+		System::set_block_number(2);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+		// The pot only holds 40 each period, so the award is paid in parts.
+		for &(now, outstanding) in &[(2, 60), (4, 20)] {
+			Balances::make_free_balance_be(&Treasury::account_id(), 41);
+			<Treasury as OnInitialize<u64>>::on_initialize(now);
+			let event = treasury::Event::<Test>::PartiallyAwarded(0, 40, outstanding);
+			System::assert_has_event(event.into());
+			assert_eq!(Treasury::proposals(0).unwrap().partially_paid, 100 - outstanding);
+			assert_eq!(Treasury::approvals().into_inner(), vec![0]);
+			assert_eq!(Treasury::committed(), outstanding);
+			// The bond is held until the award has been paid in full.
+			assert_eq!(Balances::reserved_balance(0), 5);
+		}
+
+		Balances::make_free_balance_be(&Treasury::account_id(), 41);
+		<Treasury as OnInitialize<u64>>::on_initialize(6);
+		System::assert_has_event(treasury::Event::<Test>::Awarded(0, 20, 3, 1, 0).into());
+		assert_eq!(Balances::free_balance(3), 100);
+		assert_eq!(Treasury::total_awarded(), 100);
+		assert_eq!(Treasury::committed(), 0);
+		assert_eq!(Treasury::proposals(0), None);
+		assert!(Treasury::approvals().is_empty());
+		assert_eq!(Balances::reserved_balance(0), 0);
+	});
+}
+
+#[test]
+fn lapsed_approvals_are_not_partially_paid() {
+	new_test_ext().execute_with(|| {
+		AllowPartialPayouts::set(true);
+		ApprovalTtlPeriods::set(Some(1));
+		System::set_block_number(2);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 100, 3, 1, 0, None, None));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+
+		Balances::make_free_balance_be(&Treasury::account_id(), 41);
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		System::assert_has_event(treasury::Event::<Test>::PartiallyAwarded(0, 40, 60).into());
+
+		// Once lapsed, the rest of the award is no longer paid out of the pot.
+		System::reset_events();
+		Balances::make_free_balance_be(&Treasury::account_id(), 41);
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		let events = treasury_events();
+		assert!(events.contains(&treasury::Event::<Test>::ApprovalLapsed(0)));
+		assert!(!events
+			.iter()
+			.any(|event| matches!(event, treasury::Event::<Test>::PartiallyAwarded(..))));
+		assert_eq!(Balances::free_balance(3), 40);
+		assert_eq!(Treasury::proposals(0), None);
+		assert!(Treasury::approvals().is_empty());
+		assert_eq!(Balances::reserved_balance(0), 0);
+	});
+}
+
+#[test]
+fn spend_funds_hook_sees_the_spend_context() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 30, 3, 1, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 200, 4, 1, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 300, 5, 1, 0, None, None));
+		assert_ok!(Treasury::approve_proposals(Origin::root(), bounded_ids(vec![0, 1, 2])));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(
+			SPEND_FUNDS_CONTEXT.with(|v| v.borrow().clone()),
+			Some(SpendContext {
+				now: 4,
+				period: 2,
+				awarded: 30,
+				missed_approvals: 2,
+				weight_limit: Weight::MAX
+			})
+		);
+	});
+}
+
+#[test]
+fn spend_funds_hooks_are_skipped_past_the_weight_limit() {
+	new_test_ext().execute_with(|| {
+		SPEND_FUNDS_WEIGHT.with(|v| *v.borrow_mut() = 100);
+		let second_calls = || SECOND_SPEND_FUNDS_CALLS.with(|v| *v.borrow());
+
+		// Within the limit both hooks are called.
+		SpendFundsWeightLimit::set(100);
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(second_calls(), 1);
+		assert!(!treasury_events()
+			.iter()
+			.any(|event| matches!(event, treasury::Event::<Test>::SpendFundsTruncated(_))));
+
+		// The first hook uses more than the limit, so the second is skipped.
+		SpendFundsWeightLimit::set(50);
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(second_calls(), 1);
+		System::assert_has_event(treasury::Event::<Test>::SpendFundsTruncated(1).into());
+		// The weight used by the first hook is still accounted for.
+		assert_eq!(Treasury::spend_funds(6), 100);
+	});
+}
+
+#[test]
+fn bond_exempt_proposal_lifecycle() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_noop!(
+			Treasury::propose_spend_exempt(Origin::signed(0), 40, 3, 2, 0, None),
+			BadOrigin
+		);
+		// The exempt proposer holds no funds at all.
+		assert_ok!(Treasury::propose_spend_exempt(Origin::signed(14), 40, 3, 2, 0, None));
+		assert_eq!(
+			treasury_events(),
+			vec![
+				treasury::Event::Proposed {
+					proposal_index: 0,
+					proposer: Some(14),
+					beneficiary: 3,
+					value: 40,
+					chunk: 20,
+					occurs: 2,
+					bond: 0,
+				},
+				treasury::Event::BondExempt(0)
+			]
+		);
+		assert_eq!(Treasury::proposals(0).unwrap().bond, 0);
+
+		// Payouts release nothing.
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		<Treasury as OnInitialize<u64>>::on_initialize(4);
+		assert_eq!(Balances::free_balance(3), 40);
+		assert_eq!(Treasury::proposals(0), None);
+		assert_eq!(Balances::total_balance(&14), 0);
+	});
+}
+
+#[test]
+fn bond_exempt_proposal_can_be_rejected() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		assert_ok!(Treasury::propose_spend_exempt(Origin::signed(14), 40, 3, 1, 0, None));
+		assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
+		System::assert_last_event(
+			treasury::Event::<Test>::Rejected(0, 0, 0, Default::default()).into(),
+		);
+		assert_eq!(Treasury::proposals(0), None);
+
+		// Waiting exempt proposals can be rejected in the same way.
+		System::set_block_number(3);
+		assert_ok!(Treasury::propose_spend_exempt(Origin::signed(14), 40, 3, 1, 0, None));
+		assert_ok!(Treasury::reject_waiting_proposal(Origin::root(), 0));
+		assert_eq!(Treasury::waiting_proposals(0), None);
+		assert_eq!(Balances::total_balance(&14), 0);
+	});
+}
+
+fn treasury1_events() -> Vec<treasury::Event<Test, Instance1>> {
+	System::events()
+		.into_iter()
+		.filter_map(|r| if let Event::Treasury1(inner) = r.event { Some(inner) } else { None })
+		.collect()
+}
+
+#[test]
+fn beneficiaries_are_paid_through_the_paymaster() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury1::account_id(), 101);
+		assert_ok!(Treasury1::propose_spend(Origin::signed(0), 10, (0, 3), 1, 0, None, None));
+		assert_ok!(Treasury1::propose_spend(Origin::signed(0), 10, (7, 3), 1, 0, None, None));
+		assert_ok!(Treasury1::approve_proposal(Origin::root(), 0));
+		assert_ok!(Treasury1::approve_proposal(Origin::root(), 1));
+
+		<Treasury1 as OnInitialize<u64>>::on_initialize(3);
+		assert_eq!(Balances::free_balance(3), 10);
+		assert_eq!(sent((0, 3)), 0);
+		assert_eq!(sent((7, 3)), 10);
+		let awarded = treasury::Event::<Test, Instance1>::Awarded(1, 10, (7, 3), 1, 0);
+		assert!(treasury1_events().contains(&awarded));
+	});
+}
+
+#[test]
+fn beneficiaries_without_an_account_are_not_left_to_claim() {
+	new_test_ext().execute_with(|| {
+		TreasuryPayoutMode::set(PayoutMode::Pull);
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury1::account_id(), 101);
+		assert_ok!(Treasury1::propose_spend(Origin::signed(0), 10, (0, 3), 1, 0, None, None));
+		assert_ok!(Treasury1::propose_spend(Origin::signed(0), 10, (7, 3), 1, 0, None, None));
+		assert_ok!(Treasury1::approve_proposal(Origin::root(), 0));
+		assert_ok!(Treasury1::approve_proposal(Origin::root(), 1));
+
+		<Treasury1 as OnInitialize<u64>>::on_initialize(3);
+		assert_eq!(Treasury1::pending_payouts(3), 10);
+		assert_eq!(Treasury1::total_pending_payouts(), 10);
+		assert_eq!(sent((7, 3)), 10);
+
+		assert_ok!(Treasury1::claim_payout(Origin::signed(3)));
+		assert_eq!(Balances::free_balance(3), 10);
+	});
+}
+
+#[test]
+fn beneficiary_account_checks_use_the_paymaster() {
+	new_test_ext().execute_with(|| {
+		FlagSelfProposals::set(true);
+		System::set_block_number(1);
+		let pot = Treasury1::account_id();
+		assert_noop!(
+			Treasury1::propose_spend(Origin::signed(0), 10, (0, pot), 1, 0, None, None),
+			Error::<Test, Instance1>::InvalidBeneficiary
+		);
+		// The same account on another chain is not the pot, nor the proposer.
+		assert_ok!(Treasury1::propose_spend(Origin::signed(0), 10, (7, pot), 1, 0, None, None));
+		assert_ok!(Treasury1::propose_spend(Origin::signed(0), 10, (7, 0), 1, 0, None, None));
+		assert!(!treasury1_events()
+			.iter()
+			.any(|event| matches!(event, treasury::Event::<Test, Instance1>::SelfProposal(_))));
+		assert_ok!(Treasury1::propose_spend(Origin::signed(0), 10, (0, 0), 1, 0, None, None));
+		assert_eq!(
+			treasury1_events().last(),
+			Some(&treasury::Event::<Test, Instance1>::SelfProposal(2))
+		);
+
+		// Only a beneficiary with a local account can confirm its milestones.
+		assert_ok!(Treasury1::set_milestone_gated(Origin::root(), 1, true));
+		assert_noop!(
+			Treasury1::confirm_milestone(Origin::signed(0), 1),
+			Error::<Test, Instance1>::NotBeneficiary
+		);
+		assert_ok!(Treasury1::set_milestone_gated(Origin::root(), 2, true));
+		assert_ok!(Treasury1::confirm_milestone(Origin::signed(0), 2));
+	});
+}
+
+#[test]
+fn migration_upgrades_proposals_from_the_baseline_layout() {
+	new_test_ext().execute_with(|| {
+		// Proposals stored before storage versions were introduced pay `value` per occurrence,
+		// and returned the whole bond with the first award.
+		let put_raw = |key: Vec<u8>, old: (u128, u64, u128, u64, u32, u32)| {
+			frame_support::storage::unhashed::put_raw(&key, &old.encode());
+		};
+		put_raw(<Proposals<Test>>::hashed_key_for(0), (0, 20, 3, 3, 3, 2));
+		// A proposal made without chunks.
+		put_raw(<Proposals<Test>>::hashed_key_for(1), (1, 10, 4, 1, 0, 0));
+		put_raw(<WaitingProposals<Test>>::hashed_key_for(0), (0, 20, 3, 3, 3, 3));
+		frame_support::storage::migration::put_storage_value(
+			b"Treasury",
+			b"WaitingProposalCount",
+			&[],
+			1u32,
+		);
+		<Approvals<Test>>::put(bounded_ids(vec![0]));
+		StorageVersion::new(0).put::<Treasury>();
+
+		System::set_block_number(7);
+		migrations::v1::migrate::<Test, (), Identity>();
+		let expected = Proposal {
+			proposer: Some(0),
+			total_value: 60,
+			beneficiary: 3,
+			bond: 3,
+			remaining_bond: 0,
+			occurs: 3,
+			remaining_occurs: 2,
+			interval: 0,
+			last_paid_at: None,
+			paused: false,
+			schedule: None,
+			expires_at: None,
+			milestone_gated: false,
+			milestone_confirmed: false,
+			partially_paid: 0,
+			vesting_period: None,
+			splits: Default::default(),
+			proposed_at: 0,
+			approved_at: Some(7),
+		};
+		assert_eq!(Treasury::proposals(0), Some(expected.clone()));
+		let chunkless = Proposal {
+			proposer: Some(1),
+			total_value: 10,
+			beneficiary: 4,
+			bond: 1,
+			remaining_bond: 1,
+			occurs: 1,
+			remaining_occurs: 1,
+			approved_at: None,
+			..expected.clone()
+		};
+		assert_eq!(Treasury::proposals(1), Some(chunkless));
+		let waiting =
+			Proposal { remaining_bond: 3, remaining_occurs: 3, approved_at: None, ..expected };
+		assert_eq!(Treasury::waiting_proposals(0), Some(waiting));
+
+		// The approved proposal counts as approved at the block of the upgrade.
+		assert_eq!(Treasury::approved_at(0), Some(7));
+		assert_eq!(Treasury::committed(), 40);
+		assert_eq!(
+			Treasury::status(0),
+			Some(ProposalStatus::PartiallyPaid { paid_occurrences: 1 })
+		);
+		assert_eq!(Treasury::status(1), Some(ProposalStatus::Proposed));
+		assert_eq!(Treasury::waiting_status(0), Some(ProposalStatus::Waiting));
+
+		assert_eq!((Treasury::waiting_queue_head(), Treasury::waiting_queue_tail()), (0, 1));
+		assert_eq!(
+			frame_support::storage::migration::get_storage_value::<u32>(
+				b"Treasury",
+				b"WaitingProposalCount",
+				&[],
+			),
+			None
+		);
+		assert_eq!(Treasury::proposals_by_proposer(0).into_inner(), vec![0]);
+		assert_eq!(Treasury::waiting_proposals_by_proposer(0).into_inner(), vec![0]);
+		assert_eq!((Treasury::pending_by_proposer(0), Treasury::pending_by_proposer(1)), (2, 1));
+		// The last scheduled spend block counts as the last spend round.
+		assert_eq!(Treasury::last_spend_block(), 6);
+		assert!(!Treasury::is_spend_due(7));
+		assert_eq!(Treasury::on_chain_storage_version(), 1);
+
+		// Nothing happens once the storage is current.
+		let proposal = Treasury::proposals(0);
+		migrations::v1::migrate::<Test, (), Identity>();
+		assert_eq!(Treasury::proposals(0), proposal);
+		assert_eq!(Treasury::pending_by_proposer(0), 2);
+	});
+}
+
+/// Turns the accounts of proposals stored before beneficiaries were generalised into accounts on
+/// the local chain.
+pub struct LocalAccount;
+impl Convert<u128, (u32, u128)> for LocalAccount {
+	fn convert(who: u128) -> (u32, u128) {
+		(0, who)
+	}
+}
+
+#[test]
+fn migration_converts_beneficiary_accounts() {
+	new_test_ext().execute_with(|| {
+		frame_support::storage::unhashed::put_raw(
+			&<Proposals<Test, Instance1>>::hashed_key_for(0),
+			&(0u128, 10u64, 3u128, 1u64, 1u32, 1u32).encode(),
+		);
+		StorageVersion::new(0).put::<Treasury1>();
+
+		migrations::v1::migrate::<Test, Instance1, LocalAccount>();
+		assert_eq!(Treasury1::proposals(0).map(|p| p.beneficiary), Some((0, 3)));
+		assert_eq!(Treasury1::on_chain_storage_version(), 1);
+	});
+}
+
+#[test]
+fn try_state_catches_corrupted_storage() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		assert_ok!(Treasury::set_metadata(Origin::signed(0), 1, b"cid".to_vec()));
+		assert_ok!(Treasury::approve_proposal(Origin::root(), 0));
+		System::set_block_number(3);
+		assert_ok!(Treasury::propose_spend(Origin::signed(1), 10, 3, 1, 0, None, None));
+		assert_ok!(Treasury::do_try_state());
+
+		let corruptions: Vec<(&str, Box<dyn Fn()>)> = vec![
+			(
+				"a proposal index is not below `ProposalCount`",
+				Box::new(|| <ProposalCount<Test>>::put(1)),
+			),
+			(
+				"the waiting queue head is past its tail",
+				Box::new(|| <WaitingQueueHead<Test>>::put(2)),
+			),
+			(
+				"a waiting proposal is outside the waiting queue",
+				Box::new(|| <WaitingQueueTail<Test>>::put(0)),
+			),
+			(
+				"an index in `Approvals` has no proposal",
+				Box::new(|| <Approvals<Test>>::put(bounded_ids(vec![0, 5]))),
+			),
+			(
+				"a proposer has less reserved than the bonds and deposits held for them",
+				Box::new(|| {
+					Balances::unreserve(&0, 1);
+				}),
+			),
+			(
+				"a proposer has less reserved than the bonds and deposits held for them",
+				Box::new(|| {
+					Balances::unreserve(&1, 1);
+				}),
+			),
+		];
+		for (error, corrupt) in corruptions {
+			frame_support::storage::with_transaction(|| {
+				corrupt();
+				assert_eq!(Treasury::do_try_state(), Err(error));
+				frame_support::storage::TransactionOutcome::Rollback(())
+			});
+			assert_ok!(Treasury::do_try_state());
+		}
+	});
+}
+
+#[test]
+fn rejecting_approved_proposals_keeps_storage_consistent() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		for _ in 0..3 {
+			assert_ok!(Treasury::propose_spend(Origin::signed(0), 10, 3, 1, 0, None, None));
+		}
+		assert_ok!(Treasury::approve_proposals(Origin::root(), bounded_ids(vec![0, 1, 2])));
+		assert_eq!(Treasury::committed(), 30);
+
+		assert_ok!(Treasury::reject_proposal(Origin::root(), 0));
+		assert_ok!(Treasury::reject_proposals(Origin::root(), bounded_ids(vec![2])));
+		assert_eq!(Treasury::approvals().into_inner(), vec![1]);
+		assert_eq!(Treasury::committed(), 10);
+		assert_eq!((Treasury::approved_at(0), Treasury::approved_at(2)), (None, None));
+		assert_ok!(Treasury::do_try_state());
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Balances::free_balance(3), 10);
+		assert_ok!(Treasury::do_try_state());
+	});
+}
+
+#[cfg(feature = "try-runtime")]
+#[test]
+fn migration_checks_every_proposal_still_decodes() {
+	use frame_support::traits::OnRuntimeUpgrade;
+	type Migration = migrations::v1::Migration<Test, (), Identity>;
+
+	new_test_ext().execute_with(|| {
+		// A proposal stored before storage versions were introduced.
+		frame_support::storage::unhashed::put_raw(
+			&<Proposals<Test>>::hashed_key_for(0),
+			&(0u128, 10u64, 3u128, 1u64, 1u32, 1u32).encode(),
+		);
+		<ProposalCount<Test>>::put(1);
+		assert_ok!(Balances::reserve(&0, 1));
+		StorageVersion::new(0).put::<Treasury>();
+
+		assert_ok!(Migration::pre_upgrade());
+		Migration::on_runtime_upgrade();
+		assert_ok!(Migration::post_upgrade());
+
+		// A sampled proposal that ends up under another index is reported.
+		assert_ok!(Migration::pre_upgrade());
+		let proposal = <Proposals<Test>>::take(0).unwrap();
+		<Proposals<Test>>::insert(5, proposal);
+		Migration::on_runtime_upgrade();
+		assert_eq!(
+			Migration::post_upgrade(),
+			Err("a sampled treasury proposal is missing after the migration")
+		);
+		let proposal = <Proposals<Test>>::take(5).unwrap();
+		<Proposals<Test>>::insert(0, proposal);
+
+		// A proposal the migration does not understand is reported.
+		StorageVersion::new(0).put::<Treasury>();
+		frame_support::storage::unhashed::put_raw(&<Proposals<Test>>::hashed_key_for(1), &[0]);
+		assert_ok!(Migration::pre_upgrade());
+		Migration::on_runtime_upgrade();
+		assert!(Migration::post_upgrade().is_err());
+	});
+}
+
+#[test]
+fn asset_spend_is_paid_and_settled() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Treasury::spend_asset(Origin::signed(11), 1, 10, 6));
+		System::assert_last_event(treasury::Event::<Test>::AssetSpendApproved(0, 1, 10, 6).into());
+		assert_eq!(Treasury::asset_spend_count(), 1);
+
+		assert_ok!(Treasury::payout_asset(Origin::signed(1), 0));
+		let id = last_payment_id();
+		System::assert_last_event(treasury::Event::<Test>::AssetPaid(0, id).into());
+		assert_eq!(paid(6, 1), 10);
+		// The native pot is not touched.
+		assert_eq!(Balances::free_balance(Treasury::account_id()), 0);
+
+		assert_noop!(Treasury::check_status(Origin::signed(1), 0), Error::<Test>::Inconclusive);
+
+		set_payment_status(id, PaymentStatus::Success);
+		assert_ok!(Treasury::check_status(Origin::signed(1), 0));
+		System::assert_last_event(treasury::Event::<Test>::AssetSpendProcessed(0).into());
+		assert_eq!(Treasury::asset_spends(0), None);
+		assert_noop!(
+			Treasury::check_status(Origin::signed(1), 0),
+			Error::<Test>::AssetSpendNotFound
+		);
+	});
+}
+
+#[test]
+fn failed_asset_payment_can_be_retried() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Treasury::spend_asset(Origin::root(), 1, 10, 6));
+		assert_ok!(Treasury::payout_asset(Origin::signed(1), 0));
+		let first = last_payment_id();
+		assert_noop!(Treasury::payout_asset(Origin::signed(1), 0), Error::<Test>::AlreadyAttempted);
+
+		set_payment_status(first, PaymentStatus::Failure);
+		assert_ok!(Treasury::check_status(Origin::signed(1), 0));
+		System::assert_last_event(treasury::Event::<Test>::AssetPaymentFailed(0, first).into());
+		assert!(matches!(
+			Treasury::asset_spends(0).map(|spend| spend.status),
+			Some(PaymentState::Failed)
+		));
+		assert_noop!(Treasury::check_status(Origin::signed(1), 0), Error::<Test>::NotAttempted);
+
+		assert_ok!(Treasury::payout_asset(Origin::signed(1), 0));
+		let second = last_payment_id();
+		assert_ne!(first, second);
+		set_payment_status(second, PaymentStatus::Success);
+		assert_ok!(Treasury::check_status(Origin::signed(1), 0));
+		assert_eq!(Treasury::asset_spends(0), None);
+	});
+}
+
+#[test]
+fn refused_asset_payment_leaves_the_spend_pending() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Treasury::spend_asset(Origin::root(), 1, 10, 6));
+		REFUSE_PAYMENTS.with(|v| *v.borrow_mut() = true);
+		assert_noop!(Treasury::payout_asset(Origin::signed(1), 0), Error::<Test>::PayoutError);
+		assert!(matches!(
+			Treasury::asset_spends(0).map(|spend| spend.status),
+			Some(PaymentState::Pending)
+		));
+
+		REFUSE_PAYMENTS.with(|v| *v.borrow_mut() = false);
+		assert_ok!(Treasury::payout_asset(Origin::signed(1), 0));
+		assert_eq!(paid(6, 1), 10);
+	});
+}
+
+#[test]
+fn spend_asset_checks_origin_and_amount() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Treasury::spend_asset(Origin::signed(1), 1, 10, 6), BadOrigin);
+		assert_noop!(
+			Treasury::spend_asset(Origin::signed(10), 1, 6, 6),
+			Error::<Test>::InsufficientPermission
+		);
+		assert_noop!(Treasury::spend_asset(Origin::signed(10), 1, 0, 6), Error::<Test>::ZeroAmount);
+		assert_ok!(Treasury::spend_asset(Origin::signed(10), 1, 5, 6));
+
+		assert_noop!(
+			Treasury::payout_asset(Origin::signed(1), 1),
+			Error::<Test>::AssetSpendNotFound
+		);
+		assert_noop!(Treasury::check_status(Origin::signed(1), 0), Error::<Test>::NotAttempted);
+	});
+}
@@ -48,7 +48,47 @@ pub trait WeightInfo {
 	fn propose_spend() -> Weight;
 	fn reject_proposal() -> Weight;
 	fn approve_proposal(p: u32, ) -> Weight;
-	fn on_initialize_proposals(p: u32, ) -> Weight;
+	fn on_initialize_proposals(p: u32, w: u32, ) -> Weight;
+	fn on_initialize_waiting(p: u32, ) -> Weight;
+	fn remove_approval() -> Weight;
+	fn withdraw_proposal() -> Weight;
+	fn reject_waiting_proposal() -> Weight;
+	fn amend_proposal() -> Weight;
+	fn spend() -> Weight;
+	fn fund() -> Weight;
+	fn approve_proposals(p: u32, ) -> Weight;
+	fn reject_proposals(p: u32, ) -> Weight;
+	fn set_burn() -> Weight;
+	fn set_allowed_proposal_period() -> Weight;
+	fn veto_approved(p: u32, ) -> Weight;
+	fn cancel_waiting() -> Weight;
+	fn reject_proposal_with_reason(r: u32, ) -> Weight;
+	fn update_beneficiary() -> Weight;
+	fn claim_payout() -> Weight;
+	fn reprioritize_approval(p: u32, ) -> Weight;
+	fn add_proposer() -> Weight;
+	fn remove_proposer() -> Weight;
+	fn set_burn_destinations(d: u32, ) -> Weight;
+	fn propose_spend_exempt() -> Weight;
+	fn pause_proposal() -> Weight;
+	fn resume_proposal() -> Weight;
+	fn propose_spend_scheduled(s: u32, ) -> Weight;
+	fn set_expiry() -> Weight;
+	fn set_milestone_gated() -> Weight;
+	fn confirm_milestone() -> Weight;
+	fn extend_proposal() -> Weight;
+	fn set_priority(p: u32, ) -> Weight;
+	fn sort_approvals(p: u32, ) -> Weight;
+	fn spend_asset() -> Weight;
+	fn payout_asset() -> Weight;
+	fn check_status() -> Weight;
+	fn set_vesting_period() -> Weight;
+	fn propose_spend_split(b: u32, ) -> Weight;
+	fn set_metadata() -> Weight;
+	fn propose_spend_waiting() -> Weight;
+	fn report_proposal() -> Weight;
+	fn ban_proposer() -> Weight;
+	fn unban_proposer() -> Weight;
 }
 
 /// Weights for pallet_treasury using the Substrate node and recommended hardware.
@@ -81,14 +121,336 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: Treasury BountyApprovals (r:1 w:1)
 	// Storage: Treasury Proposals (r:2 w:2)
 	// Storage: System Account (r:4 w:4)
-	fn on_initialize_proposals(p: u32, ) -> Weight {
+	// Storage: Treasury TotalAwarded (r:1 w:1)
+	// Storage: Treasury TotalBurnt (r:1 w:1)
+	// Storage: Treasury SpentThisPeriod (r:0 w:1)
+	// Storage: Treasury WaitingPromotionCutoff (r:1 w:1)
+	// Storage: Treasury WaitingQueueHead (r:1 w:1)
+	// Storage: Treasury WaitingQueueTail (r:1 w:0)
+	// Storage: Treasury WaitingProposals (r:1 w:1)
+	// Storage: Treasury ProposalCount (r:1 w:1)
+	fn on_initialize_proposals(p: u32, w: u32, ) -> Weight {
 		(51_708_000 as Weight)
 			// Standard Error: 21_000
 			.saturating_add((57_926_000 as Weight).saturating_mul(p as Weight))
+			// Standard Error: 4_000
+			.saturating_add((10_412_000 as Weight).saturating_mul(w as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(w as Weight)))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(w as Weight)))
+	}
+	// Storage: Treasury WaitingQueueHead (r:1 w:1)
+	// Storage: Treasury WaitingQueueTail (r:1 w:1)
+	// Storage: Treasury WaitingProposals (r:1 w:1)
+	// Storage: Treasury ProposalCount (r:1 w:1)
+	// Storage: Treasury Proposals (r:0 w:1)
+	fn on_initialize_waiting(p: u32, ) -> Weight {
+		(2_415_000 as Weight)
+			// Standard Error: 4_000
+			.saturating_add((9_870_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(p as Weight)))
+	}
+	// Storage: Treasury Approvals (r:1 w:1)
+	fn remove_approval() -> Weight {
+		(14_912_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	// Storage: Treasury Approvals (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	fn withdraw_proposal() -> Weight {
+		(35_104_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury WaitingProposals (r:1 w:1)
+	// Storage: Treasury WaitingQueueHead (r:1 w:1)
+	// Storage: Treasury WaitingQueueTail (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn reject_waiting_proposal() -> Weight {
+		(37_254_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	// Storage: Treasury Approvals (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	fn amend_proposal() -> Weight {
+		(39_517_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury ProposalCount (r:1 w:1)
+	// Storage: Treasury Approvals (r:1 w:1)
+	// Storage: Treasury Proposals (r:0 w:1)
+	fn spend() -> Weight {
+		(16_540_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: System Account (r:2 w:2)
+	// Storage: Treasury TotalPendingPayouts (r:1 w:0)
+	// Storage: Treasury Deactivated (r:1 w:1)
+	// Storage: Balances InactiveIssuance (r:1 w:1)
+	fn fund() -> Weight {
+		(47_518_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:0)
+	// Storage: Treasury Approvals (r:1 w:1)
+	fn approve_proposals(p: u32, ) -> Weight {
+		(14_032_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((3_184_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn reject_proposals(p: u32, ) -> Weight {
+		(3_210_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((37_460_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+	}
+	// Storage: Treasury BurnOverride (r:0 w:1)
+	fn set_burn() -> Weight {
+		(12_160_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury AllowedProposalPeriodOverride (r:0 w:1)
+	fn set_allowed_proposal_period() -> Weight {
+		(12_480_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury Approvals (r:1 w:1)
+	// Storage: Treasury Proposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn veto_approved(p: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((100_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Treasury WaitingProposals (r:1 w:1)
+	// Storage: Treasury WaitingQueueHead (r:1 w:1)
+	// Storage: Treasury WaitingQueueTail (r:1 w:1)
+	fn cancel_waiting() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn reject_proposal_with_reason(r: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((2_000 as Weight).saturating_mul(r as Weight))
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	// Storage: Treasury Approvals (r:1 w:0)
+	fn update_beneficiary() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury PendingPayouts (r:1 w:1)
+	// Storage: Treasury TotalPendingPayouts (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn claim_payout() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Treasury Approvals (r:1 w:1)
+	// Storage: Treasury ApprovedAt (r:1 w:1)
+	fn reprioritize_approval(p: u32, ) -> Weight {
+		(18_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((60_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury ApprovedProposers (r:1 w:1)
+	// Storage: Treasury ApprovedProposerCount (r:1 w:1)
+	fn add_proposer() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury ApprovedProposers (r:1 w:1)
+	// Storage: Treasury ApprovedProposerCount (r:1 w:1)
+	fn remove_proposer() -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury BurnDestinations (r:0 w:1)
+	fn set_burn_destinations(d: u32, ) -> Weight {
+		(14_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((100_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury ProposalCount (r:1 w:1)
+	// Storage: Treasury Proposals (r:0 w:1)
+	fn propose_spend_exempt() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	fn pause_proposal() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	fn resume_proposal() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury ProposalCount (r:1 w:1)
+	// Storage: Treasury Proposals (r:0 w:1)
+	fn propose_spend_scheduled(s: u32, ) -> Weight {
+		(42_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((50_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	fn set_expiry() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	fn set_milestone_gated() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	fn confirm_milestone() -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	// Storage: System Account (r:2 w:1)
+	fn extend_proposal() -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury Approvals (r:1 w:0)
+	// Storage: Treasury ApprovalPriority (r:0 w:1)
+	fn set_priority(p: u32, ) -> Weight {
+		(14_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((20_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury ApprovalPriority (r:1 w:0)
+	// Storage: Treasury ApprovedAt (r:1 w:0)
+	// Storage: Treasury Proposals (r:1 w:0)
+	fn sort_approvals(p: u32, ) -> Weight {
+		(2_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((1_100_000 as Weight).saturating_mul(p as Weight))
 			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(p as Weight)))
+	}
+	// Storage: Treasury AssetSpendCount (r:1 w:1)
+	// Storage: Treasury AssetSpends (r:0 w:1)
+	fn spend_asset() -> Weight {
+		(16_730_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(p as Weight)))
+	}
+	// Storage: Treasury AssetSpends (r:1 w:1)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	fn payout_asset() -> Weight {
+		(58_214_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Treasury AssetSpends (r:1 w:1)
+	fn check_status() -> Weight {
+		(14_902_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	fn set_vesting_period() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury ProposalCount (r:1 w:1)
+	// Storage: Treasury Proposals (r:0 w:1)
+	fn propose_spend_split(b: u32, ) -> Weight {
+		(42_000_000 as Weight)
+			// Standard Error: 2_000
+			.saturating_add((120_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:0)
+	// Storage: Treasury Approvals (r:1 w:0)
+	// Storage: Treasury ProposalMetadata (r:1 w:1)
+	// Storage: Treasury MetadataDeposits (r:1 w:1)
+	fn set_metadata() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury AllowedProposalPeriodOverride (r:1 w:0)
+	// Storage: Treasury WaitingQueueHead (r:1 w:0)
+	// Storage: Treasury WaitingQueueTail (r:1 w:1)
+	// Storage: Treasury PendingByProposer (r:1 w:1)
+	// Storage: Treasury WaitingProposalsByProposer (r:1 w:1)
+	// Storage: Treasury WaitingProposalDescriptions (r:0 w:1)
+	// Storage: Treasury WaitingProposals (r:0 w:1)
+	fn propose_spend_waiting() -> Weight {
+		(47_215_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:0)
+	// Storage: Treasury Reporters (r:1 w:1)
+	fn report_proposal() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury BannedProposers (r:0 w:1)
+	fn ban_proposer() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury BannedProposers (r:1 w:1)
+	fn unban_proposer() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 }
 
@@ -121,13 +483,335 @@ impl WeightInfo for () {
 	// Storage: Treasury BountyApprovals (r:1 w:1)
 	// Storage: Treasury Proposals (r:2 w:2)
 	// Storage: System Account (r:4 w:4)
-	fn on_initialize_proposals(p: u32, ) -> Weight {
+	// Storage: Treasury TotalAwarded (r:1 w:1)
+	// Storage: Treasury TotalBurnt (r:1 w:1)
+	// Storage: Treasury SpentThisPeriod (r:0 w:1)
+	// Storage: Treasury WaitingPromotionCutoff (r:1 w:1)
+	// Storage: Treasury WaitingQueueHead (r:1 w:1)
+	// Storage: Treasury WaitingQueueTail (r:1 w:0)
+	// Storage: Treasury WaitingProposals (r:1 w:1)
+	// Storage: Treasury ProposalCount (r:1 w:1)
+	fn on_initialize_proposals(p: u32, w: u32, ) -> Weight {
 		(51_708_000 as Weight)
 			// Standard Error: 21_000
 			.saturating_add((57_926_000 as Weight).saturating_mul(p as Weight))
+			// Standard Error: 4_000
+			.saturating_add((10_412_000 as Weight).saturating_mul(w as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(w as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(w as Weight)))
+	}
+	// Storage: Treasury WaitingQueueHead (r:1 w:1)
+	// Storage: Treasury WaitingQueueTail (r:1 w:1)
+	// Storage: Treasury WaitingProposals (r:1 w:1)
+	// Storage: Treasury ProposalCount (r:1 w:1)
+	// Storage: Treasury Proposals (r:0 w:1)
+	fn on_initialize_waiting(p: u32, ) -> Weight {
+		(2_415_000 as Weight)
+			// Standard Error: 4_000
+			.saturating_add((9_870_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(p as Weight)))
+	}
+	// Storage: Treasury Approvals (r:1 w:1)
+	fn remove_approval() -> Weight {
+		(14_912_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	// Storage: Treasury Approvals (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	fn withdraw_proposal() -> Weight {
+		(35_104_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury WaitingProposals (r:1 w:1)
+	// Storage: Treasury WaitingQueueHead (r:1 w:1)
+	// Storage: Treasury WaitingQueueTail (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn reject_waiting_proposal() -> Weight {
+		(37_254_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	// Storage: Treasury Approvals (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	fn amend_proposal() -> Weight {
+		(39_517_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury ProposalCount (r:1 w:1)
+	// Storage: Treasury Approvals (r:1 w:1)
+	// Storage: Treasury Proposals (r:0 w:1)
+	fn spend() -> Weight {
+		(16_540_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: System Account (r:2 w:2)
+	// Storage: Treasury TotalPendingPayouts (r:1 w:0)
+	// Storage: Treasury Deactivated (r:1 w:1)
+	// Storage: Balances InactiveIssuance (r:1 w:1)
+	fn fund() -> Weight {
+		(47_518_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:0)
+	// Storage: Treasury Approvals (r:1 w:1)
+	fn approve_proposals(p: u32, ) -> Weight {
+		(14_032_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((3_184_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn reject_proposals(p: u32, ) -> Weight {
+		(3_210_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((37_460_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+	}
+	// Storage: Treasury BurnOverride (r:0 w:1)
+	fn set_burn() -> Weight {
+		(12_160_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury AllowedProposalPeriodOverride (r:0 w:1)
+	fn set_allowed_proposal_period() -> Weight {
+		(12_480_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury Approvals (r:1 w:1)
+	// Storage: Treasury Proposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn veto_approved(p: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((100_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Treasury WaitingProposals (r:1 w:1)
+	// Storage: Treasury WaitingQueueHead (r:1 w:1)
+	// Storage: Treasury WaitingQueueTail (r:1 w:1)
+	fn cancel_waiting() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn reject_proposal_with_reason(r: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((2_000 as Weight).saturating_mul(r as Weight))
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	// Storage: Treasury Approvals (r:1 w:0)
+	fn update_beneficiary() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury PendingPayouts (r:1 w:1)
+	// Storage: Treasury TotalPendingPayouts (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn claim_payout() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Treasury Approvals (r:1 w:1)
+	// Storage: Treasury ApprovedAt (r:1 w:1)
+	fn reprioritize_approval(p: u32, ) -> Weight {
+		(18_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((60_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury ApprovedProposers (r:1 w:1)
+	// Storage: Treasury ApprovedProposerCount (r:1 w:1)
+	fn add_proposer() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury ApprovedProposers (r:1 w:1)
+	// Storage: Treasury ApprovedProposerCount (r:1 w:1)
+	fn remove_proposer() -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury BurnDestinations (r:0 w:1)
+	fn set_burn_destinations(d: u32, ) -> Weight {
+		(14_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((100_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury ProposalCount (r:1 w:1)
+	// Storage: Treasury Proposals (r:0 w:1)
+	fn propose_spend_exempt() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	fn pause_proposal() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	fn resume_proposal() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury ProposalCount (r:1 w:1)
+	// Storage: Treasury Proposals (r:0 w:1)
+	fn propose_spend_scheduled(s: u32, ) -> Weight {
+		(42_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((50_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	fn set_expiry() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	fn set_milestone_gated() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	fn confirm_milestone() -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	// Storage: System Account (r:2 w:1)
+	fn extend_proposal() -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury Approvals (r:1 w:0)
+	// Storage: Treasury ApprovalPriority (r:0 w:1)
+	fn set_priority(p: u32, ) -> Weight {
+		(14_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((20_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury ApprovalPriority (r:1 w:0)
+	// Storage: Treasury ApprovedAt (r:1 w:0)
+	// Storage: Treasury Proposals (r:1 w:0)
+	fn sort_approvals(p: u32, ) -> Weight {
+		(2_000_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((1_100_000 as Weight).saturating_mul(p as Weight))
 			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(p as Weight)))
+	}
+	// Storage: Treasury AssetSpendCount (r:1 w:1)
+	// Storage: Treasury AssetSpends (r:0 w:1)
+	fn spend_asset() -> Weight {
+		(16_730_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(p as Weight)))
+	}
+	// Storage: Treasury AssetSpends (r:1 w:1)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	fn payout_asset() -> Weight {
+		(58_214_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Treasury AssetSpends (r:1 w:1)
+	fn check_status() -> Weight {
+		(14_902_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:1)
+	fn set_vesting_period() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury ProposalCount (r:1 w:1)
+	// Storage: Treasury Proposals (r:0 w:1)
+	fn propose_spend_split(b: u32, ) -> Weight {
+		(42_000_000 as Weight)
+			// Standard Error: 2_000
+			.saturating_add((120_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:0)
+	// Storage: Treasury Approvals (r:1 w:0)
+	// Storage: Treasury ProposalMetadata (r:1 w:1)
+	// Storage: Treasury MetadataDeposits (r:1 w:1)
+	fn set_metadata() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Treasury AllowedProposalPeriodOverride (r:1 w:0)
+	// Storage: Treasury WaitingQueueHead (r:1 w:0)
+	// Storage: Treasury WaitingQueueTail (r:1 w:1)
+	// Storage: Treasury PendingByProposer (r:1 w:1)
+	// Storage: Treasury WaitingProposalsByProposer (r:1 w:1)
+	// Storage: Treasury WaitingProposalDescriptions (r:0 w:1)
+	// Storage: Treasury WaitingProposals (r:0 w:1)
+	fn propose_spend_waiting() -> Weight {
+		(47_215_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Treasury Proposals (r:1 w:0)
+	// Storage: Treasury Reporters (r:1 w:1)
+	fn report_proposal() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury BannedProposers (r:0 w:1)
+	fn ban_proposer() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Treasury BannedProposers (r:1 w:1)
+	fn unban_proposer() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
 }